@@ -0,0 +1,488 @@
+//! Layouts of the messages the airdrop program's distributors sign.
+//!
+//! The program decodes claims with these same types, so off-chain signers that build their
+//! messages here encode them exactly as the program expects.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_pubkey::Pubkey;
+
+/// The message layout version the program accepts
+pub const MESSAGE_VERSION: u8 = 1;
+
+/// The operation a signed message authorizes. Every message carries its kind in its domain,
+/// so a message signed for one operation can never be redeemed as another.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageKind {
+    /// A grant signed by the distributors
+    Claim,
+    /// A refund of a project's funds signed by its authority
+    Refund,
+    /// A configuration change signed by the global authority
+    AdminOp,
+    /// A recipient's consent for a delegate to claim on its behalf
+    Delegation,
+    /// A Merkle root over a campaign's allocations signed by the distributors
+    MerkleRoot,
+}
+
+/// Generic domain fields for all signed messages
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MessageDomain {
+    pub program_id: Pubkey,
+    pub version: u8,
+    pub nonce: u64,
+    pub deadline: i64,
+    pub genesis_hash: [u8; 32],
+    pub kind: MessageKind,
+}
+
+impl MessageDomain {
+    /// A claim domain for `program_id` at the current message version. The genesis hash is
+    /// left zeroed, matching a global config that has not set one.
+    pub fn new(program_id: Pubkey, nonce: u64, deadline: i64) -> Self {
+        Self {
+            program_id,
+            version: MESSAGE_VERSION,
+            nonce,
+            deadline,
+            genesis_hash: [0; 32],
+            kind: MessageKind::Claim,
+        }
+    }
+
+    /// Binds the message to the cluster with the given genesis hash
+    pub fn with_genesis_hash(mut self, genesis_hash: [u8; 32]) -> Self {
+        self.genesis_hash = genesis_hash;
+        self
+    }
+
+    /// Sets the operation the message authorizes
+    pub fn with_kind(mut self, kind: MessageKind) -> Self {
+        self.kind = kind;
+        self
+    }
+}
+
+/// The first byte hashed into a Merkle leaf, so a leaf can never be passed off as a node
+const MERKLE_LEAF_PREFIX: u8 = 0;
+
+/// The first byte hashed into a Merkle node
+const MERKLE_NODE_PREFIX: u8 = 1;
+
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AirdropMessageData {
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub project_nonce: u64,
+    pub amount: u64,
+    pub phase: u8,
+    /// A token account the recipient may receive into even though it does not own it
+    pub destination: Option<Pubkey>,
+    /// Unix timestamp from which a scheduled grant starts releasing; set only for
+    /// `claim_vesting` (the cliff) and `create_stream` (the stream start)
+    pub cliff_ts: Option<i64>,
+    /// Unix timestamp at which a scheduled grant is fully released; set only for
+    /// `claim_vesting` and `create_stream`
+    pub vest_end_ts: Option<i64>,
+    /// The wallet credited with referring the recipient; set only for `claim`
+    pub referrer: Option<Pubkey>,
+    /// The referrer's share of the claimed amount, in basis points
+    pub referral_bps: u16,
+    /// The recipient's tier in the project's tier rules
+    pub tier: u8,
+    /// A memo written alongside the claim transfer, e.g. so an exchange can attribute a deposit
+    pub memo: Option<String>,
+}
+
+impl AirdropMessageData {
+    /// A plain grant of `amount` tokens of `mint` in phase 0 and tier 0, with no schedule,
+    /// referral, destination or memo
+    pub fn new(recipient: Pubkey, mint: Pubkey, project_nonce: u64, amount: u64) -> Self {
+        Self {
+            recipient,
+            mint,
+            project_nonce,
+            amount,
+            phase: 0,
+            destination: None,
+            cliff_ts: None,
+            vest_end_ts: None,
+            referrer: None,
+            referral_bps: 0,
+            tier: 0,
+            memo: None,
+        }
+    }
+
+    /// Sets the claim phase the grant counts against
+    pub fn with_phase(mut self, phase: u8) -> Self {
+        self.phase = phase;
+        self
+    }
+
+    /// Sets the tier the grant counts against
+    pub fn with_tier(mut self, tier: u8) -> Self {
+        self.tier = tier;
+        self
+    }
+
+    /// Pays the grant into `destination` instead of the recipient's token account
+    pub fn with_destination(mut self, destination: Pubkey) -> Self {
+        self.destination = Some(destination);
+        self
+    }
+
+    /// Releases the grant between `cliff_ts` and `vest_end_ts`
+    pub fn with_schedule(mut self, cliff_ts: i64, vest_end_ts: i64) -> Self {
+        self.cliff_ts = Some(cliff_ts);
+        self.vest_end_ts = Some(vest_end_ts);
+        self
+    }
+
+    /// Credits `referrer` with `referral_bps` of the claimed amount
+    pub fn with_referral(mut self, referrer: Pubkey, referral_bps: u16) -> Self {
+        self.referrer = Some(referrer);
+        self.referral_bps = referral_bps;
+        self
+    }
+
+    /// Writes `memo` alongside the claim transfer
+    pub fn with_memo(mut self, memo: impl Into<String>) -> Self {
+        self.memo = Some(memo.into());
+        self
+    }
+
+    /// Whether the grant carries a vesting or streaming schedule
+    pub fn is_vesting(&self) -> bool {
+        self.cliff_ts.is_some() || self.vest_end_ts.is_some()
+    }
+
+    /// Whether the grant pays a referral reward
+    pub fn has_referral(&self) -> bool {
+        self.referrer.is_some() || self.referral_bps > 0
+    }
+}
+
+/// Complete airdrop message with domain data and metadata
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AirdropMessage {
+    pub data: AirdropMessageData,
+    pub domain: MessageDomain,
+}
+
+impl AirdropMessage {
+    /// A message granting `data` under `domain`
+    pub fn new(data: AirdropMessageData, domain: MessageDomain) -> Self {
+        Self { data, domain }
+    }
+
+    /// The bytes the distributors sign: the message tagged with the current layout
+    pub fn to_bytes(&self) -> Vec<u8> {
+        VersionedAirdropMessage::from(self.clone()).to_bytes()
+    }
+}
+
+/// A claim message tagged with the layout it was signed under.
+///
+/// New layouts are added as new variants, and every variant upgrades into the latest
+/// `AirdropMessage`, so messages signed under an earlier layout stay redeemable after
+/// fields are added.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum VersionedAirdropMessage {
+    V1(AirdropMessage),
+}
+
+impl VersionedAirdropMessage {
+    /// The layout version the message was signed under, which its domain must also carry
+    pub fn version(&self) -> u8 {
+        match self {
+            Self::V1(_) => 1,
+        }
+    }
+
+    /// The message's domain as signed
+    pub fn domain(&self) -> &MessageDomain {
+        match self {
+            Self::V1(message) => &message.domain,
+        }
+    }
+
+    /// Upgrades the message into the latest layout, rewriting its domain to carry
+    /// `MESSAGE_VERSION` once the fields it lacks are filled in
+    pub fn into_latest(self) -> AirdropMessage {
+        let mut message = match self {
+            Self::V1(message) => message,
+        };
+        message.domain.version = MESSAGE_VERSION;
+        message
+    }
+
+    /// Decodes a signed claim message. Messages signed before layouts were tagged carry no
+    /// tag and are read as V1.
+    pub fn from_bytes(bytes: &[u8]) -> std::io::Result<Self> {
+        Self::try_from_slice(bytes)
+            .or_else(|_| AirdropMessage::try_from_slice(bytes).map(Self::V1))
+    }
+
+    /// Decodes a signed batch of claim messages, reading an untagged batch signed before
+    /// layouts were tagged as V1 messages
+    pub fn batch_from_bytes(bytes: &[u8]) -> std::io::Result<Vec<Self>> {
+        Vec::<Self>::try_from_slice(bytes).or_else(|_| {
+            Vec::<AirdropMessage>::try_from_slice(bytes)
+                .map(|messages| messages.into_iter().map(Self::V1).collect())
+        })
+    }
+
+    /// The Borsh encoding of the tagged message
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.try_to_vec().expect("serializing into a Vec cannot fail")
+    }
+}
+
+impl From<AirdropMessage> for VersionedAirdropMessage {
+    fn from(message: AirdropMessage) -> Self {
+        Self::V1(message)
+    }
+}
+
+/// A single Merkle root signed by the distributors over every allocation of a campaign, so
+/// one signature covers any number of claims. Each recipient redeems its allocation with a
+/// proof that `merkle_leaf(recipient, amount, nonce)` is included under `root`.
+///
+/// The program does not check the domain's nonce: replay is prevented per allocation, by the
+/// claim nonce each leaf carries, so signers may use the domain nonce freely off-chain.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MerkleRootMessage {
+    pub mint: Pubkey,
+    pub project_nonce: u64,
+    pub phase: u8,
+    pub tier: u8,
+    pub root: [u8; 32],
+    pub domain: MessageDomain,
+}
+
+impl MerkleRootMessage {
+    /// The claim data of one allocation under the root, as if it had been signed on its own
+    pub fn grant(&self, recipient: Pubkey, amount: u64) -> AirdropMessageData {
+        AirdropMessageData::new(recipient, self.mint, self.project_nonce, amount)
+            .with_phase(self.phase)
+            .with_tier(self.tier)
+    }
+
+    /// The Borsh encoding the distributors sign
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.try_to_vec().expect("serializing into a Vec cannot fail")
+    }
+}
+
+/// The Merkle leaf of an allocation of `amount` tokens to `recipient` under claim nonce `nonce`
+pub fn merkle_leaf(recipient: &Pubkey, amount: u64, nonce: u64) -> [u8; 32] {
+    solana_keccak_hasher::hashv(&[
+        &[MERKLE_LEAF_PREFIX],
+        recipient.as_ref(),
+        amount.to_le_bytes().as_ref(),
+        nonce.to_le_bytes().as_ref(),
+    ])
+    .to_bytes()
+}
+
+/// The parent of two Merkle nodes. The pair is hashed in sorted order, so proofs need no
+/// left/right flags.
+pub fn merkle_parent(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    solana_keccak_hasher::hashv(&[&[MERKLE_NODE_PREFIX], left.as_ref(), right.as_ref()]).to_bytes()
+}
+
+/// The root reached by folding `proof` into `leaf`
+pub fn merkle_root_from_proof(leaf: [u8; 32], proof: &[[u8; 32]]) -> [u8; 32] {
+    proof
+        .iter()
+        .fold(leaf, |node, sibling| merkle_parent(&node, sibling))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_message() -> AirdropMessage {
+        AirdropMessage::new(
+            AirdropMessageData::new(Pubkey::new_unique(), Pubkey::new_unique(), 7, 1_000)
+                .with_phase(1)
+                .with_memo("deposit"),
+            MessageDomain::new(Pubkey::new_unique(), 42, 1_700_000_000),
+        )
+    }
+
+    #[test]
+    fn v1_round_trips_through_into_latest() {
+        let message = sample_message();
+        let decoded = VersionedAirdropMessage::from_bytes(&message.to_bytes()).unwrap();
+
+        assert_eq!(decoded.version(), 1);
+        assert_eq!(decoded.domain(), &message.domain);
+        assert_eq!(decoded.into_latest(), message);
+    }
+
+    #[test]
+    fn into_latest_carries_the_current_version() {
+        let mut message = sample_message();
+        message.domain.version = 1;
+
+        let latest = VersionedAirdropMessage::V1(message).into_latest();
+        assert_eq!(latest.domain.version, MESSAGE_VERSION);
+    }
+
+    #[test]
+    fn untagged_messages_decode_as_v1() {
+        let message = sample_message();
+        let untagged = message.try_to_vec().unwrap();
+
+        let decoded = VersionedAirdropMessage::from_bytes(&untagged).unwrap();
+        assert_eq!(decoded, VersionedAirdropMessage::V1(message));
+    }
+
+    #[test]
+    fn untagged_batches_decode_as_v1() {
+        let messages = vec![sample_message(), sample_message()];
+        let untagged = messages.try_to_vec().unwrap();
+
+        let decoded = VersionedAirdropMessage::batch_from_bytes(&untagged).unwrap();
+        assert_eq!(
+            decoded,
+            messages
+                .into_iter()
+                .map(VersionedAirdropMessage::V1)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn tagged_batches_decode() {
+        let messages: Vec<VersionedAirdropMessage> =
+            vec![sample_message().into(), sample_message().into()];
+
+        let decoded = VersionedAirdropMessage::batch_from_bytes(&messages.try_to_vec().unwrap()).unwrap();
+        assert_eq!(decoded, messages);
+    }
+
+    #[test]
+    fn signed_bytes_are_the_tagged_encoding() {
+        let message = sample_message();
+        let bytes = message.to_bytes();
+
+        assert_eq!(bytes[0], 0);
+        assert_eq!(&bytes[1..], message.try_to_vec().unwrap().as_slice());
+    }
+
+    #[test]
+    fn domain_builders_set_their_fields() {
+        let program_id = Pubkey::new_unique();
+        let domain = MessageDomain::new(program_id, 3, 99);
+        assert_eq!(domain.program_id, program_id);
+        assert_eq!(domain.version, MESSAGE_VERSION);
+        assert_eq!((domain.nonce, domain.deadline), (3, 99));
+        assert_eq!(domain.genesis_hash, [0; 32]);
+        assert_eq!(domain.kind, MessageKind::Claim);
+
+        let domain = domain
+            .with_genesis_hash([7; 32])
+            .with_kind(MessageKind::MerkleRoot);
+        assert_eq!(domain.genesis_hash, [7; 32]);
+        assert_eq!(domain.kind, MessageKind::MerkleRoot);
+    }
+
+    #[test]
+    fn message_kinds_encode_as_their_index() {
+        let kinds = [
+            MessageKind::Claim,
+            MessageKind::Refund,
+            MessageKind::AdminOp,
+            MessageKind::Delegation,
+            MessageKind::MerkleRoot,
+        ];
+        for (index, kind) in kinds.into_iter().enumerate() {
+            assert_eq!(kind.try_to_vec().unwrap(), vec![index as u8]);
+        }
+    }
+
+    #[test]
+    fn data_builders_set_their_fields() {
+        let data = AirdropMessageData::new(Pubkey::new_unique(), Pubkey::new_unique(), 1, 10);
+        assert!(!data.is_vesting());
+        assert!(!data.has_referral());
+
+        let destination = Pubkey::new_unique();
+        let referrer = Pubkey::new_unique();
+        let data = data
+            .with_tier(2)
+            .with_destination(destination)
+            .with_schedule(100, 200)
+            .with_referral(referrer, 250);
+        assert_eq!(data.tier, 2);
+        assert_eq!(data.destination, Some(destination));
+        assert_eq!((data.cliff_ts, data.vest_end_ts), (Some(100), Some(200)));
+        assert_eq!((data.referrer, data.referral_bps), (Some(referrer), 250));
+        assert!(data.is_vesting());
+        assert!(data.has_referral());
+    }
+
+    #[test]
+    fn merkle_root_grants_inherit_the_root_fields() {
+        let root = MerkleRootMessage {
+            mint: Pubkey::new_unique(),
+            project_nonce: 5,
+            phase: 1,
+            tier: 2,
+            root: [9; 32],
+            domain: MessageDomain::new(Pubkey::new_unique(), 0, 0).with_kind(MessageKind::MerkleRoot),
+        };
+        let recipient = Pubkey::new_unique();
+
+        assert_eq!(
+            root.grant(recipient, 300),
+            AirdropMessageData::new(recipient, root.mint, 5, 300)
+                .with_phase(1)
+                .with_tier(2)
+        );
+        assert_eq!(MerkleRootMessage::try_from_slice(&root.to_bytes()).unwrap(), root);
+    }
+
+    #[test]
+    fn merkle_leaves_commit_to_every_field() {
+        let recipient = Pubkey::new_unique();
+        let leaf = merkle_leaf(&recipient, 100, 1);
+
+        assert_eq!(leaf, merkle_leaf(&recipient, 100, 1));
+        assert_ne!(leaf, merkle_leaf(&Pubkey::new_unique(), 100, 1));
+        assert_ne!(leaf, merkle_leaf(&recipient, 101, 1));
+        assert_ne!(leaf, merkle_leaf(&recipient, 100, 2));
+    }
+
+    #[test]
+    fn merkle_parents_ignore_order_and_differ_from_leaves() {
+        let a = merkle_leaf(&Pubkey::new_unique(), 1, 1);
+        let b = merkle_leaf(&Pubkey::new_unique(), 2, 2);
+
+        assert_eq!(merkle_parent(&a, &b), merkle_parent(&b, &a));
+        assert_ne!(merkle_parent(&a, &b), a);
+        assert_ne!(merkle_parent(&a, &b), b);
+    }
+
+    #[test]
+    fn every_leaf_proves_into_the_root() {
+        let leaves: Vec<[u8; 32]> = (0..4)
+            .map(|nonce| merkle_leaf(&Pubkey::new_unique(), 1_000 + nonce, nonce))
+            .collect();
+        let left = merkle_parent(&leaves[0], &leaves[1]);
+        let right = merkle_parent(&leaves[2], &leaves[3]);
+        let root = merkle_parent(&left, &right);
+
+        assert_eq!(merkle_root_from_proof(leaves[0], &[leaves[1], right]), root);
+        assert_eq!(merkle_root_from_proof(leaves[1], &[leaves[0], right]), root);
+        assert_eq!(merkle_root_from_proof(leaves[2], &[leaves[3], left]), root);
+        assert_eq!(merkle_root_from_proof(leaves[3], &[leaves[2], left]), root);
+        assert_ne!(merkle_root_from_proof(leaves[0], &[leaves[2], right]), root);
+        assert_eq!(merkle_root_from_proof(leaves[0], &[]), leaves[0]);
+    }
+}
@@ -1,4 +1,47 @@
-pub const VERSION: u8 = 1;
+pub const VERSION: u8 = airdrop_message::MESSAGE_VERSION;
+pub const GLOBAL_CONFIG_VERSION: u8 = 1;
+pub const PROJECT_VERSION: u8 = 2;
 pub const PROJECT_SEED_PREFIX: &[u8] = b"project";
+pub const PROJECT_COUNTER_SEED: &[u8] = b"project_counter";
+pub const PROJECT_REGISTRY_SEED_PREFIX: &[u8] = b"project_registry";
+pub const TOKEN_VAULT_SEED_PREFIX: &[u8] = b"token_vault";
+pub const WSOL_UNWRAP_SEED_PREFIX: &[u8] = b"wsol_unwrap";
+pub const LOCKED_CLAIM_SEED_PREFIX: &[u8] = b"locked_claim";
+pub const LOCKED_CLAIM_VAULT_SEED_PREFIX: &[u8] = b"locked_claim_vault";
+pub const STAKE_ACCOUNT_SEED_PREFIX: &[u8] = b"stake_account";
+pub const POOL_DEPOSIT_SEED_PREFIX: &[u8] = b"pool_deposit";
+pub const PROJECT_METADATA_SEED_PREFIX: &[u8] = b"project_metadata";
 pub const CLAIM_NULLIFIER_SEED_PREFIX: &[u8] = b"nullifier";
-pub const GLOBAL_CONFIG_SEED: &[u8] = b"global_config";
\ No newline at end of file
+pub const NULLIFIER_SHARD_SEED_PREFIX: &[u8] = b"nullifier_shard";
+pub const CLAIM_RECEIPT_SEED_PREFIX: &[u8] = b"claim_receipt";
+pub const CLAIM_THROTTLE_SEED_PREFIX: &[u8] = b"claim_throttle";
+pub const NFT_CLAIM_SEED_PREFIX: &[u8] = b"nft_claim";
+pub const GROUP_CLAIM_SEED_PREFIX: &[u8] = b"group_claim";
+pub const GLOBAL_CONFIG_SEED: &[u8] = b"global_config";
+pub const PROTOCOL_STATS_SEED: &[u8] = b"protocol_stats";
+pub const RECIPIENT_CLAIM_STATE_SEED_PREFIX: &[u8] = b"recipient_claim";
+pub const RECIPIENT_HISTORY_SEED_PREFIX: &[u8] = b"recipient_history";
+pub const RECIPIENT_NONCE_SEED_PREFIX: &[u8] = b"recipient_nonce";
+pub const FUNDER_SEED_PREFIX: &[u8] = b"funder";
+pub const DENYLIST_SEED_PREFIX: &[u8] = b"denylist";
+pub const CREATOR_ALLOWLIST_SEED_PREFIX: &[u8] = b"creator_allowlist";
+pub const MAX_DISTRIBUTORS: usize = 10;
+pub const MAX_OPERATORS: usize = 10;
+pub const MAX_PROJECT_MANAGERS: usize = 5;
+pub const MAX_PHASES: usize = 4;
+pub const MAX_TIERS: usize = 4;
+pub const MAX_BATCH_CLAIMS: usize = 8;
+pub const MAX_MEMO_LEN: usize = 128;
+pub const MAX_MERKLE_PROOF_LEN: usize = 32;
+pub const MAX_DEADLINE_GRACE_SECS: u32 = 300;
+pub const NULLIFIER_SHARD_NONCES: usize = 8_192;
+pub const PROJECT_REGISTRY_PAGE_SIZE: usize = 64;
+pub const MAX_PROJECT_NAME_LEN: usize = 32;
+pub const MAX_PROJECT_SYMBOL_LEN: usize = 10;
+pub const MAX_PROJECT_URI_LEN: usize = 200;
+pub const MAX_PROJECT_DESCRIPTION_LEN: usize = 256;
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// The retired stake config account, still expected by the Stake program's `DelegateStake`
+#[allow(deprecated)]
+pub const STAKE_CONFIG_ID: anchor_lang::prelude::Pubkey = solana_stake_interface::config::ID;
\ No newline at end of file
@@ -8,6 +8,24 @@ pub enum AirdropError {
     BadEd25519Program,
     #[msg("Bad Ed25519 accounts")]
     BadEd25519Accounts,
+    #[msg("Ed25519 instruction padding byte must be zero")]
+    Ed25519PaddingNotZero,
+    #[msg("Ed25519 instruction has trailing bytes after its last region")]
+    Ed25519TrailingBytes,
+    #[msg("Ed25519 instruction regions overlap each other or the header")]
+    Ed25519RegionOverlap,
+    #[msg("Ed25519 signed message is too long")]
+    Ed25519MessageTooLong,
+    #[msg("Expected secp256k1 program id")]
+    BadSecp256k1Program,
+    #[msg("Bad secp256k1 accounts")]
+    BadSecp256k1Accounts,
+    #[msg("Expected secp256r1 program id")]
+    BadSecp256r1Program,
+    #[msg("Bad secp256r1 accounts")]
+    BadSecp256r1Accounts,
+    #[msg("Secp256k1 distributors must be Ethereum addresses left-padded with zeros")]
+    InvalidDistributorKey,
     #[msg("Distributor public key mismatch")]
     DistributorMismatch,
     #[msg("Recipient mismatch in message")]
@@ -22,8 +40,202 @@ pub enum AirdropError {
     MintMismatch,
     #[msg("Nonce mismatch - signature replay attack detected")]
     NonceMismatch,
+    #[msg("Batch must hold between 1 and the maximum number of claims, with one message and nullifier per nonce")]
+    InvalidBatch,
+    #[msg("Nullifier account is invalid or already used")]
+    InvalidNullifier,
+    #[msg("Grant entry index is out of range")]
+    InvalidGrantEntry,
+    #[msg("Nullifier mode can only change before the first claim")]
+    NullifierModeLocked,
+    #[msg("Projects without nullifier PDAs only accept claim")]
+    NullifierModeRequiresClaim,
+    #[msg("Claim amount is zero or exceeds the remaining allocation")]
+    ClaimExceedsAllocation,
+    #[msg("Signed allocation does not match the recorded allocation")]
+    AllocationMismatch,
+    #[msg("Vesting schedule is missing or invalid")]
+    InvalidVestingSchedule,
+    #[msg("Scheduled grants must be claimed through claim_vesting or create_stream")]
+    VestingRequired,
+    #[msg("No vested or streamed amount is available to claim")]
+    NothingVested,
+    #[msg("Destination token account missing or not authorized for the recipient")]
+    InvalidDestination,
+    #[msg("Referrer token account missing or does not match the signed referral")]
+    InvalidReferral,
+    #[msg("Referral grants must be claimed through claim")]
+    ReferralRequiresClaim,
+    #[msg("Memo is too long or the memo program is missing")]
+    InvalidMemo,
+    #[msg("Unwrapping requires the wrapped SOL mint and no destination token account")]
+    InvalidUnwrap,
+    #[msg("Staking claims require the wrapped SOL mint and the project's vote account")]
+    InvalidStake,
+    #[msg("The project does not redeem this ticket mint, or the ticket amount is invalid")]
+    InvalidTicket,
+    #[msg("The NFT is not a verified member of the project's gating collection, or the recipient does not hold it")]
+    InvalidCollectionNft,
+    #[msg("The compressed NFT is not in the project's gating tree, or its proof accounts are missing")]
+    InvalidCompressedNft,
+    #[msg("The project must hold the mint authority to mint on claim")]
+    MintAuthorityRequired,
+    #[msg("Pool claims require the wrapped SOL mint, the project's stake pool and a recipient pool token account")]
+    InvalidStakePool,
+    #[msg("Lockup escrow accounts missing, unexpected or invalid")]
+    InvalidLockup,
+    #[msg("Deadline grace period exceeds the maximum")]
+    InvalidDeadlineGrace,
+    #[msg("The lockup has not ended yet")]
+    LockupNotExpired,
+    #[msg("Projects with a lockup only accept claims through claim")]
+    LockupRequiresClaim,
+    #[msg("The recipient's attestation is missing, expired or not issued under the project's credential and schema")]
+    AttestationRequired,
+    #[msg("Projects requiring an attestation only accept claims through claim")]
+    AttestationRequiresClaim,
+    #[msg("Group claim record missing, or given for a project outside any campaign group")]
+    InvalidGroupClaim,
+    #[msg("Projects in a campaign group only accept claims through claim")]
+    GroupRequiresClaim,
+    #[msg("Recipient delegation signature missing or invalid")]
+    InvalidDelegation,
+    #[msg("Token delegate does not match the signed message")]
+    InvalidTokenDelegate,
     #[msg("Program ID mismatch")]
     ProgramIdMismatch,
+    #[msg("Invalid off-chain message envelope")]
+    InvalidOffchainMessage,
+    #[msg("Message was signed for a different cluster")]
+    GenesisHashMismatch,
+    #[msg("Message was signed for a different operation")]
+    MessageKindMismatch,
+    #[msg("Signed admin message approves a different action")]
+    AdminActionMismatch,
+    #[msg("Merkle proof does not match the signed root")]
+    InvalidMerkleProof,
     #[msg("Version mismatch")]
     VersionMismatch,
+    #[msg("Signer is not the configured authority")]
+    Unauthorized,
+    #[msg("Distributor already exists")]
+    DistributorAlreadyExists,
+    #[msg("Distributor not found")]
+    DistributorNotFound,
+    #[msg("Too many distributors")]
+    TooManyDistributors,
+    #[msg("Distributor set is empty")]
+    EmptyDistributorSet,
+    #[msg("Threshold must be between 1 and the number of distributors")]
+    InvalidThreshold,
+    #[msg("Not enough distributor signatures to meet the threshold")]
+    ThresholdNotMet,
+    #[msg("Required weight must not exceed the total distributor weight")]
+    InvalidRequiredWeight,
+    #[msg("Not enough distributor weight signed to meet the required weight")]
+    RequiredWeightNotMet,
+    #[msg("Signer is not the pending authority")]
+    PendingAuthorityMismatch,
+    #[msg("Account layout is not recognized")]
+    InvalidAccountLayout,
+    #[msg("Account version is newer than this program supports")]
+    UnsupportedAccountVersion,
+    #[msg("Fee must not exceed 10000 basis points")]
+    InvalidFeeBps,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Recipient is denylisted")]
+    RecipientDenylisted,
+    #[msg("Operator already exists")]
+    OperatorAlreadyExists,
+    #[msg("Operator not found")]
+    OperatorNotFound,
+    #[msg("Too many operators")]
+    TooManyOperators,
+    #[msg("Claim amount exceeds the configured maximum")]
+    ClaimAmountExceedsMax,
+    #[msg("Claim amount must be greater than zero")]
+    ZeroClaimAmount,
+    #[msg("Claim amount exceeds the project's maximum")]
+    ClaimAmountExceedsProjectMax,
+    #[msg("Invalid rate limit window")]
+    InvalidRateLimitWindow,
+    #[msg("Claim rate limit exceeded")]
+    RateLimitExceeded,
+    #[msg("Project claim rate limit exceeded")]
+    ProjectRateLimitExceeded,
+    #[msg("Claim throttle account missing")]
+    InvalidThrottle,
+    #[msg("Throttled projects only accept claims through claim")]
+    ThrottleRequiresClaim,
+    #[msg("The recipient must pay for the claim")]
+    RecipientMustPay,
+    #[msg("Projects requiring the recipient to pay only accept claims through claim")]
+    RecipientPayerRequiresClaim,
+    #[msg("Invalid claim window")]
+    InvalidClaimWindow,
+    #[msg("Claim window has not started")]
+    ClaimWindowNotStarted,
+    #[msg("Claim window has ended")]
+    ClaimWindowEnded,
+    #[msg("Claim window has not ended")]
+    ClaimWindowNotEnded,
+    #[msg("Claim window is sealed because nullifiers have been closed")]
+    ClaimWindowSealed,
+    #[msg("Budget is below the amount already claimed")]
+    InvalidBudget,
+    #[msg("Claim exceeds the project budget")]
+    BudgetExceeded,
+    #[msg("Claim exceeds the per-recipient maximum")]
+    RecipientCapExceeded,
+    #[msg("Fee override token account missing or invalid")]
+    InvalidFeeOverrideAccount,
+    #[msg("Fee treasury missing or does not match the global config")]
+    InvalidFeeTreasury,
+    #[msg("Invalid phases")]
+    InvalidPhases,
+    #[msg("No phase is active")]
+    NoActivePhase,
+    #[msg("Phase mismatch")]
+    PhaseMismatch,
+    #[msg("Phase claim limit reached")]
+    PhaseClaimLimitReached,
+    #[msg("Invalid tiers")]
+    InvalidTiers,
+    #[msg("Tier may not claim yet")]
+    TierNotOpen,
+    #[msg("Refunds are only available before the claim window opens or after it ends")]
+    RefundNotAvailable,
+    #[msg("Funder deposits are still refundable")]
+    DepositsOutstanding,
+    #[msg("Mint decimals do not match the project")]
+    DecimalsMismatch,
+    #[msg("Mint has a freeze authority or a risky Token-2022 extension")]
+    RiskyMint,
+    #[msg("Metadata field too long")]
+    MetadataTooLong,
+    #[msg("Project is frozen")]
+    ProjectFrozen,
+    #[msg("Project is paused")]
+    ProjectPaused,
+    #[msg("Manager already exists")]
+    ManagerAlreadyExists,
+    #[msg("Manager not found")]
+    ManagerNotFound,
+    #[msg("Too many managers")]
+    TooManyManagers,
+    #[msg("Program is paused")]
+    ProgramPaused,
+    #[msg("Active projects remain")]
+    ActiveProjectsRemain,
+    #[msg("No project occupies the counter's next nonce")]
+    ProjectNonceAvailable,
+    #[msg("Grant does not match the claim already made with this nonce")]
+    ClaimRecordMismatch,
+    #[msg("Vesting allocations have not been fully released")]
+    VestingOutstanding,
+    #[msg("Streams have not been fully withdrawn")]
+    StreamsOutstanding,
+    #[msg("Locked claims have not all been released")]
+    LockupsOutstanding,
 }
@@ -0,0 +1,581 @@
+use crate::state::{AttestationGate, CollectionGate, CompressedNftGate, Distributor, FeeOverride, NullifierMode, Phase, ProjectCreationMode, TicketConfig, Tier};
+use anchor_lang::prelude::*;
+
+/// Emits an event with `emit!`, or as a self-CPI when built with the `event-cpi` feature so
+/// it survives log truncation. `$accounts` must hold the `event_authority` account that
+/// `#[event_cpi]` adds to the instruction's accounts.
+#[cfg(not(feature = "event-cpi"))]
+macro_rules! emit_event {
+    ($accounts:expr, $event:expr) => {
+        emit!($event)
+    };
+}
+
+#[cfg(feature = "event-cpi")]
+macro_rules! emit_event {
+    ($accounts:expr, $event:expr) => {
+        $crate::utils::emit_event_cpi(&$accounts.event_authority, &$event)?
+    };
+}
+
+#[event]
+pub struct GlobalConfigCreated {
+    pub authority: Pubkey,
+    pub distributors: Vec<Distributor>,
+    pub threshold: u8,
+    pub required_weight: u16,
+}
+
+#[event]
+pub struct ProjectCreated {
+    pub project: Pubkey,
+    pub nonce: u64,
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct ProjectNonceSkipped {
+    pub project: Pubkey,
+    pub nonce: u64,
+}
+
+#[event]
+pub struct ProjectFunded {
+    pub project: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub total_funded: u64,
+}
+
+#[event]
+pub struct RentPoolFunded {
+    pub project: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub rent_pool_lamports: u64,
+}
+
+#[event]
+pub struct FunderRefunded {
+    pub project: Pubkey,
+    pub funder: Pubkey,
+    pub deposited: u64,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct ProjectClosed {
+    pub project: Pubkey,
+    pub nonce: u64,
+    pub authority: Pubkey,
+    pub swept_amount: u64,
+    pub archived: bool,
+}
+
+#[event]
+pub struct ProjectNullifierModeUpdated {
+    pub project: Pubkey,
+    pub nullifier_mode: NullifierMode,
+}
+
+#[event]
+pub struct NullifierClosed {
+    pub project: Pubkey,
+    pub nonce: u64,
+}
+
+#[event]
+pub struct ProjectClaimWindowUpdated {
+    pub project: Pubkey,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct ProjectMetadataUpdated {
+    pub project: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+#[event]
+pub struct ProjectBudgetUpdated {
+    pub project: Pubkey,
+    pub budget: u64,
+}
+
+#[event]
+pub struct ProjectMaxPerRecipientUpdated {
+    pub project: Pubkey,
+    pub max_per_recipient: Option<u64>,
+}
+
+#[event]
+pub struct ProjectMaxClaimAmountUpdated {
+    pub project: Pubkey,
+    pub max_claim_amount: Option<u64>,
+}
+
+#[event]
+pub struct ProjectThresholdUpdated {
+    pub project: Pubkey,
+    pub threshold: Option<u8>,
+}
+
+#[event]
+pub struct ProjectTransferFeeModeUpdated {
+    pub project: Pubkey,
+    pub gross_up_transfer_fees: bool,
+}
+
+#[event]
+pub struct UnclaimedBurned {
+    pub project: Pubkey,
+    pub signer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ProjectFrozenUpdated {
+    pub project: Pubkey,
+    pub frozen: bool,
+}
+
+#[event]
+pub struct ProjectFeeOverrideUpdated {
+    pub project: Pubkey,
+    pub fee_override: Option<FeeOverride>,
+}
+
+#[event]
+pub struct ProjectClaimFeeUpdated {
+    pub project: Pubkey,
+    pub claim_fee_lamports: u64,
+}
+
+#[event]
+pub struct ManagerAdded {
+    pub project: Pubkey,
+    pub manager: Pubkey,
+}
+
+#[event]
+pub struct ManagerRemoved {
+    pub project: Pubkey,
+    pub manager: Pubkey,
+}
+
+#[event]
+pub struct ProjectLowBalanceThresholdUpdated {
+    pub project: Pubkey,
+    pub signer: Pubkey,
+    pub low_balance_threshold: u64,
+}
+
+#[event]
+pub struct VaultLowBalance {
+    pub project: Pubkey,
+    pub vault_balance: u64,
+    pub low_balance_threshold: u64,
+    pub remaining_budget: u64,
+}
+
+#[event]
+pub struct ProjectPausedUpdated {
+    pub project: Pubkey,
+    pub signer: Pubkey,
+    pub paused: bool,
+}
+
+#[event]
+pub struct ProjectPhasesUpdated {
+    pub project: Pubkey,
+    pub phases: Vec<Phase>,
+}
+
+#[event]
+pub struct ProjectTiersUpdated {
+    pub project: Pubkey,
+    pub tiers: Vec<Tier>,
+}
+
+#[event]
+pub struct ProjectStakeVoteAccountUpdated {
+    pub project: Pubkey,
+    pub stake_vote_account: Option<Pubkey>,
+}
+
+#[event]
+pub struct ProjectStakePoolUpdated {
+    pub project: Pubkey,
+    pub stake_pool: Option<Pubkey>,
+}
+
+#[event]
+pub struct ProjectLockDurationUpdated {
+    pub project: Pubkey,
+    pub lock_duration: i64,
+}
+
+#[event]
+pub struct ProjectDeadlineGraceUpdated {
+    pub project: Pubkey,
+    pub deadline_grace_secs: Option<u32>,
+}
+
+#[event]
+pub struct ProjectThrottleUpdated {
+    pub project: Pubkey,
+    pub max_claims_per_window: u64,
+    pub throttle_window_slots: u64,
+}
+
+#[event]
+pub struct ProjectRecipientMustPayUpdated {
+    pub project: Pubkey,
+    pub recipient_must_pay: bool,
+}
+
+#[event]
+pub struct ProjectMintOnClaimUpdated {
+    pub project: Pubkey,
+    pub mint_on_claim: bool,
+}
+
+#[event]
+pub struct ProjectTicketUpdated {
+    pub project: Pubkey,
+    pub ticket: Option<TicketConfig>,
+}
+
+#[event]
+pub struct ProjectCollectionGateUpdated {
+    pub project: Pubkey,
+    pub collection_gate: Option<CollectionGate>,
+}
+
+#[event]
+pub struct ProjectCompressedNftGateUpdated {
+    pub project: Pubkey,
+    pub compressed_nft_gate: Option<CompressedNftGate>,
+}
+
+#[event]
+pub struct ProjectAttestationGateUpdated {
+    pub project: Pubkey,
+    pub attestation_gate: Option<AttestationGate>,
+}
+
+#[event]
+pub struct ProjectClaimGroupUpdated {
+    pub project: Pubkey,
+    pub claim_group: Option<u64>,
+}
+
+#[event]
+pub struct ClaimEvent {
+    pub project: Pubkey,
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub nonce: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Claimed {
+    pub project: Pubkey,
+    pub recipient: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub net_amount: u64,
+    pub received_amount: u64,
+    pub fee: u64,
+    pub fee_destination: Pubkey,
+}
+
+#[event]
+pub struct ReferralPaid {
+    pub project: Pubkey,
+    pub recipient: Pubkey,
+    pub referrer_token_account: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ClaimStaked {
+    pub project: Pubkey,
+    pub recipient: Pubkey,
+    pub stake_account: Pubkey,
+    pub vote_account: Pubkey,
+    pub nonce: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ClaimPooled {
+    pub project: Pubkey,
+    pub recipient: Pubkey,
+    pub stake_pool: Pubkey,
+    pub pool_token_account: Pubkey,
+    pub nonce: u64,
+    pub amount: u64,
+    pub pool_tokens: u64,
+}
+
+#[event]
+pub struct ClaimLocked {
+    pub project: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub unlock_ts: i64,
+}
+
+#[event]
+pub struct ClaimUnlocked {
+    pub project: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TicketRedeemed {
+    pub project: Pubkey,
+    pub recipient: Pubkey,
+    pub ticket_mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct NftClaimed {
+    pub project: Pubkey,
+    pub recipient: Pubkey,
+    pub nft_mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CompressedNftClaimed {
+    pub project: Pubkey,
+    pub recipient: Pubkey,
+    pub asset_id: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ClaimAlreadyProcessed {
+    pub project: Pubkey,
+    pub recipient: Pubkey,
+    pub nonce: u64,
+}
+
+#[event]
+pub struct ClaimDelegateApproved {
+    pub project: Pubkey,
+    pub recipient: Pubkey,
+    pub token_delegate: Pubkey,
+    pub nonce: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ClaimDelegated {
+    pub project: Pubkey,
+    pub recipient: Pubkey,
+    pub delegate: Pubkey,
+    pub nonce: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct StreamCreated {
+    pub project: Pubkey,
+    pub recipient: Pubkey,
+    pub nonce: u64,
+    pub total: u64,
+    pub rate_per_second: u64,
+    pub start_ts: i64,
+    pub end_ts: i64,
+}
+
+#[event]
+pub struct DistributorAdded {
+    pub distributor: Pubkey,
+    pub weight: u16,
+}
+
+#[event]
+pub struct DistributorRemoved {
+    pub distributor: Pubkey,
+}
+
+#[event]
+pub struct DistributorReplaced {
+    pub old_distributor: Pubkey,
+    pub new_distributor: Pubkey,
+}
+
+#[event]
+pub struct DistributorActiveUpdated {
+    pub distributor: Pubkey,
+    pub active: bool,
+}
+
+#[event]
+pub struct ThresholdUpdated {
+    pub threshold: u8,
+}
+
+#[event]
+pub struct DistributorWeightUpdated {
+    pub distributor: Pubkey,
+    pub weight: u16,
+}
+
+#[event]
+pub struct RequiredWeightUpdated {
+    pub required_weight: u16,
+}
+
+#[event]
+pub struct DistributorCapacityExpanded {
+    pub distributor_capacity: u16,
+}
+
+#[event]
+pub struct DistributorsRotated {
+    pub distributors: Vec<Distributor>,
+    pub threshold: u8,
+    pub required_weight: u16,
+    pub previous_distributors_expiry_slot: u64,
+}
+
+#[event]
+pub struct ProjectDistributorsUpdated {
+    pub project: Pubkey,
+    pub distributors: Vec<Distributor>,
+}
+
+#[event]
+pub struct AuthorityTransferStarted {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+#[event]
+pub struct ProjectVaultMigrated {
+    pub project: Pubkey,
+    pub legacy_vault: Pubkey,
+    pub token_vault: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct FeeConfigUpdated {
+    pub fee_bps: u16,
+    pub fee_treasury: Pubkey,
+}
+
+#[event]
+pub struct FeesWithdrawn {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub fee_treasury: Pubkey,
+}
+
+#[event]
+pub struct RecipientAddedToDenylist {
+    pub recipient: Pubkey,
+}
+
+#[event]
+pub struct RecipientRemovedFromDenylist {
+    pub recipient: Pubkey,
+}
+
+#[event]
+pub struct GuardianUpdated {
+    pub guardian: Pubkey,
+}
+
+#[event]
+pub struct OperatorAdded {
+    pub operator: Pubkey,
+}
+
+#[event]
+pub struct OperatorRemoved {
+    pub operator: Pubkey,
+}
+
+#[event]
+pub struct CreatorAllowlisted {
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct CreatorRemovedFromAllowlist {
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct ProjectCreationModeUpdated {
+    pub project_creation_mode: ProjectCreationMode,
+}
+
+#[event]
+pub struct AllowRiskyMintsUpdated {
+    pub allow_risky_mints: bool,
+}
+
+#[event]
+pub struct DeadlineGraceUpdated {
+    pub deadline_grace_secs: u32,
+}
+
+#[event]
+pub struct GenesisHashUpdated {
+    pub genesis_hash: [u8; 32],
+}
+
+#[event]
+pub struct MaxClaimAmountUpdated {
+    pub max_claim_amount: Option<u64>,
+}
+
+#[event]
+pub struct RateLimitUpdated {
+    pub max_claims_per_window: u64,
+    pub rate_limit_window_slots: u64,
+}
+
+#[event]
+pub struct Paused {
+    pub signer: Pubkey,
+}
+
+#[event]
+pub struct Unpaused {
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct GlobalConfigClosed {
+    pub authority: Pubkey,
+    pub project_count: u64,
+}
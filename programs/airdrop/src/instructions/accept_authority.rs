@@ -0,0 +1,36 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    /// The nominated authority accepting control of the configuration
+    pub pending_authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        constraint = global_config.pending_authority == Some(pending_authority.key())
+            @ AirdropError::PendingAuthorityMismatch
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> AcceptAuthority<'info> {
+    pub fn accept(&mut self) -> Result<()> {
+        let previous_authority = self.global_config.authority;
+        let new_authority = self.pending_authority.key();
+
+        self.global_config.authority = new_authority;
+        self.global_config.pending_authority = None;
+
+        emit_event!(self, AuthorityTransferred {
+            previous_authority,
+            new_authority,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(creator: Pubkey)]
+pub struct AddCreatorToAllowlist<'info> {
+    /// The account paying for the allowlist entry rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The authority that manages the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The creator allowlist entry PDA to be created
+    #[account(
+        init,
+        payer = payer,
+        space = CreatorAllowlistEntry::DISCRIMINATOR.len() + CreatorAllowlistEntry::INIT_SPACE,
+        seeds = [CREATOR_ALLOWLIST_SEED_PREFIX, creator.as_ref()],
+        bump
+    )]
+    pub creator_allowlist_entry: Account<'info, CreatorAllowlistEntry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddCreatorToAllowlist<'info> {
+    pub fn add(&mut self, creator: Pubkey) -> Result<()> {
+        self.creator_allowlist_entry.set_inner(CreatorAllowlistEntry { creator });
+
+        emit_event!(self, CreatorAllowlisted { creator });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct AddManager<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA the manager is added to
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> AddManager<'info> {
+    pub fn add(&mut self, _project_nonce: u64, manager: Pubkey) -> Result<()> {
+        let managers = &mut self.project.managers;
+        require!(
+            !managers.contains(&manager),
+            AirdropError::ManagerAlreadyExists
+        );
+        require!(
+            managers.len() < MAX_PROJECT_MANAGERS,
+            AirdropError::TooManyManagers
+        );
+
+        managers.push(manager);
+
+        emit_event!(self, ManagerAdded {
+            project: self.project.key(),
+            manager,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey)]
+pub struct AddToDenylist<'info> {
+    /// The account paying for the denylist entry rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The authority that manages the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The denylist entry PDA to be created
+    #[account(
+        init,
+        payer = payer,
+        space = DenylistEntry::DISCRIMINATOR.len() + DenylistEntry::INIT_SPACE,
+        seeds = [DENYLIST_SEED_PREFIX, recipient.as_ref()],
+        bump
+    )]
+    pub denylist_entry: Account<'info, DenylistEntry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddToDenylist<'info> {
+    pub fn add(&mut self, recipient: Pubkey) -> Result<()> {
+        self.denylist_entry.set_inner(DenylistEntry { recipient });
+
+        emit_event!(self, RecipientAddedToDenylist { recipient });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct BurnUnclaimed<'info> {
+    /// The project authority or the global config authority
+    pub signer: Signer<'info>,
+
+    /// The global config PDA, whose authority may also burn unclaimed tokens
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The project PDA whose unclaimed tokens are burned
+    #[account(
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = mint @ AirdropError::MintMismatch,
+        constraint = signer.key() == project.authority
+            || signer.key() == global_config.authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+
+    /// The mint of the SPL token being distributed
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The project's token vault PDA holding the unclaimed tokens
+    #[account(
+        mut,
+        seeds = [TOKEN_VAULT_SEED_PREFIX, project.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> BurnUnclaimed<'info> {
+    /// Burns the project's remaining vault balance, less the deposits still owed to funders
+    /// and the unreleased vesting allocations and streams, once its claim window has ended
+    pub fn burn_unclaimed(&mut self, project_nonce: u64) -> Result<()> {
+        require!(
+            self.project.has_ended(Clock::get()?.unix_timestamp),
+            AirdropError::ClaimWindowNotEnded
+        );
+
+        // Funders' deposits stay in the vault for `refund_funder`, granted vesting allocations
+        // for `claim_vesting` and open streams for `withdraw_stream`
+        let amount = self.token_vault.amount - self.project.reserved_pool(self.token_vault.amount);
+
+        let nonce_bytes = project_nonce.to_le_bytes();
+        let project_bump = get_project_bump(project_nonce, &crate::ID);
+        let seeds = &[PROJECT_SEED_PREFIX, nonce_bytes.as_ref(), &[project_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        burn_spl(
+            self.token_program.to_account_info(),
+            self.project.to_account_info(),
+            self.token_vault.to_account_info(),
+            self.mint.to_account_info(),
+            amount,
+            Some(signer_seeds),
+        )?;
+
+        emit_event!(self, UnclaimedBurned {
+            project: self.project.key(),
+            signer: self.signer.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+}
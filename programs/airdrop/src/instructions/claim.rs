@@ -1,58 +1,76 @@
-use crate::{constants::*, errors::*, state::*, utils::*};
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{sysvar::instructions as ix_sysvar, sysvar::SysvarId};
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
+    memo::{self, BuildMemo, Memo},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 use borsh::BorshDeserialize;
 
 //////////////////////////////// MESSAGE ////////////////////////////////
 
-/// Domain-specific fields for airdrop claims
-#[derive(BorshDeserialize)]
-pub struct AirdropMessageData {
-    pub recipient: Pubkey,
-    pub mint: Pubkey,
-    pub project_nonce: u64,
-    pub amount: u64,
-}
+pub use airdrop_message::{AirdropMessage, AirdropMessageData, MerkleRootMessage};
 
-/// Complete airdrop message with domain data and metadata
-#[derive(BorshDeserialize)]
-pub struct AirdropMessage {
-    pub data: AirdropMessageData,
-    pub domain: MessageDomain,
+/// A recipient's allocation under a Merkle root signed by the distributors, with the proof
+/// of its inclusion
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MerkleClaim {
+    pub amount: u64,
+    pub proof: Vec<[u8; 32]>,
 }
 
 //////////////////////////////// INSTRUCTIONS ////////////////////////////////
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 #[instruction(project_nonce: u64, nonce: u64)]
 pub struct Claim<'info> {
-    /// The recipient of the airdrop (must match the recipient in the signed message)
+    /// The account paying for the nullifier, the claim accounting and any token accounts
+    /// created, so a relayer can cover the costs of a recipient without SOL
     #[account(mut)]
-    pub recipient: Signer<'info>,
+    pub payer: Signer<'info>,
 
-    /// The global config PDA containing the distributor public key
+    /// The recipient of the airdrop (must match the recipient in the signed message), writable
+    /// so an unwrapped wrapped SOL claim can be delivered as native SOL
+    /// CHECK: Needs no signature; the distributors' signed message authorizes the claim
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The global config PDA containing the distributor public keys
     #[account(
+        mut,
         seeds = [GLOBAL_CONFIG_SEED],
-        bump
+        bump,
+        constraint = !global_config.paused @ AirdropError::ProgramPaused
     )]
     pub global_config: Account<'info, GlobalConfig>,
 
+    /// The protocol-wide statistics PDA
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
     /// The project PDA from which tokens will be claimed
     #[account(
+        mut,
         seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
-        bump
+        bump,
+        constraint = !project.frozen_by_admin @ AirdropError::ProjectFrozen,
+        constraint = !project.paused @ AirdropError::ProjectPaused,
+        constraint = !project.recipient_must_pay || payer.key() == recipient.key() @ AirdropError::RecipientMustPay
     )]
     pub project: Account<'info, Project>,
 
-    /// Nullifier account to prevent nonce reuse (acts as a nullifier)
+    /// Nullifier account to prevent nonce reuse (acts as a nullifier), required unless the
+    /// project uses bitmap nullifiers
     /// If this account already exists, the transaction will fail, preventing replay attacks
     #[account(
         init,
-        payer = recipient,
+        payer = payer,
         space = ClaimNullifier::DISCRIMINATOR.len() + ClaimNullifier::INIT_SPACE,
         seeds = [
             CLAIM_NULLIFIER_SEED_PREFIX,
@@ -61,113 +79,1104 @@ pub struct Claim<'info> {
         ],
         bump
     )]
-    pub nullifier: Account<'info, ClaimNullifier>,
+    pub nullifier: Option<Account<'info, ClaimNullifier>>,
 
-    /// The mint of the SPL token being distributed
-    pub mint: Account<'info, Mint>,
+    /// The bitmap shard covering the nonce, required when the project uses bitmap nullifiers
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = NullifierShard::DISCRIMINATOR.len() + NullifierShard::INIT_SPACE,
+        seeds = [
+            NULLIFIER_SHARD_SEED_PREFIX,
+            project.key().as_ref(),
+            NullifierShard::index_for(nonce).to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub nullifier_shard: Option<Box<Account<'info, NullifierShard>>>,
+
+    /// The recipient's nonce counter, required when the project uses sequential nonces
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RecipientNonce::DISCRIMINATOR.len() + RecipientNonce::INIT_SPACE,
+        seeds = [
+            RECIPIENT_NONCE_SEED_PREFIX,
+            project.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump
+    )]
+    pub recipient_nonce: Option<Account<'info, RecipientNonce>>,
 
-    /// The token account owned by the project PDA (source of tokens)
+    /// The recipient's cumulative claim accounting for this project
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RecipientClaimState::DISCRIMINATOR.len() + RecipientClaimState::INIT_SPACE,
+        seeds = [
+            RECIPIENT_CLAIM_STATE_SEED_PREFIX,
+            project.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump
+    )]
+    pub recipient_claim_state: Account<'info, RecipientClaimState>,
+
+    /// An optional receipt proving the recipient claimed from the project, paid by the payer
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ClaimReceipt::DISCRIMINATOR.len() + ClaimReceipt::INIT_SPACE,
+        seeds = [
+            CLAIM_RECEIPT_SEED_PREFIX,
+            project.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump
+    )]
+    pub claim_receipt: Option<Account<'info, ClaimReceipt>>,
+
+    /// The project's rolling claim counter, required when the project throttles claims
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ClaimThrottle::DISCRIMINATOR.len() + ClaimThrottle::INIT_SPACE,
+        seeds = [CLAIM_THROTTLE_SEED_PREFIX, project.key().as_ref()],
+        bump
+    )]
+    pub claim_throttle: Option<Account<'info, ClaimThrottle>>,
+
+    /// Marks the recipient as having claimed in the project's campaign group, required when
+    /// the project belongs to one. Its init fails if the recipient already claimed in the group.
+    #[account(
+        init,
+        payer = payer,
+        space = GroupClaim::DISCRIMINATOR.len() + GroupClaim::INIT_SPACE,
+        seeds = [
+            GROUP_CLAIM_SEED_PREFIX,
+            project.claim_group.unwrap_or_default().to_le_bytes().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump
+    )]
+    pub group_claim: Option<Box<Account<'info, GroupClaim>>>,
+
+    /// The recipient's optional claim history for this project, paid by the payer
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RecipientHistory::DISCRIMINATOR.len() + RecipientHistory::INIT_SPACE,
+        seeds = [
+            RECIPIENT_HISTORY_SEED_PREFIX,
+            project.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump
+    )]
+    pub recipient_history: Option<Account<'info, RecipientHistory>>,
+
+    /// The recipient's denylist entry, which must not exist
+    /// CHECK: Only checked for emptiness; any data at this PDA means the recipient is denylisted
+    #[account(
+        seeds = [DENYLIST_SEED_PREFIX, recipient.key().as_ref()],
+        bump,
+        constraint = !is_program_account(&denylist_entry) @ AirdropError::RecipientDenylisted
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    /// The mint of the SPL token being distributed, written when the project mints on claim
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The project's token vault PDA (source of tokens)
     #[account(
         mut,
+        seeds = [TOKEN_VAULT_SEED_PREFIX, project.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token account owned by the global config PDA collecting protocol fees for this mint
+    #[account(
+        init_if_needed,
+        payer = payer,
         associated_token::mint = mint,
-        associated_token::authority = project
+        associated_token::authority = global_config,
+        associated_token::token_program = token_program
+    )]
+    pub fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The project fee treasury's token account, required when the project has a fee override
+    #[account(mut)]
+    pub fee_override_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The global fee treasury, required when the project charges a lamport claim fee
+    /// CHECK: Validated against the fee treasury stored in the global config
+    #[account(
+        mut,
+        address = global_config.fee_treasury @ AirdropError::InvalidFeeTreasury
     )]
-    pub project_token_account: Account<'info, TokenAccount>,
+    pub fee_treasury: Option<UncheckedAccount<'info>>,
 
-    /// The recipient's token account (destination of tokens)
+    /// The referrer's token account, required when the signed message names a referrer
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program
+    )]
+    pub referrer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The recipient's token account, receiving the tokens unless a destination token account is given
     #[account(
         init_if_needed,
-        payer = recipient,
+        payer = payer,
         associated_token::mint = mint,
-        associated_token::authority = recipient
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program
+    )]
+    pub recipient_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Another token account to receive the tokens, owned by the recipient or named as the
+    /// destination in the signed message (e.g. an exchange deposit address)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program
+    )]
+    pub destination_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// A temporary wrapped SOL account the claim is paid into and then closed, delivering native
+    /// SOL to the recipient instead of wrapped SOL; only for projects distributing wrapped SOL
+    #[account(
+        init,
+        payer = payer,
+        seeds = [WSOL_UNWRAP_SEED_PREFIX, project.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
+    )]
+    pub wsol_unwrap_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The recipient's lockup escrow, required when the project locks claims
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = LockedClaim::DISCRIMINATOR.len() + LockedClaim::INIT_SPACE,
+        seeds = [
+            LOCKED_CLAIM_SEED_PREFIX,
+            project.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump
+    )]
+    pub locked_claim: Option<Account<'info, LockedClaim>>,
+
+    /// The token account holding the recipient's locked tokens, required with `locked_claim`
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [
+            LOCKED_CLAIM_VAULT_SEED_PREFIX,
+            project.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
     )]
-    pub recipient_token_account: Account<'info, TokenAccount>,
+    pub locked_claim_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The recipient's attestation, required when the project gates claims on one
+    /// CHECK: Validated by `is_valid_attestation` against the project's attestation gate
+    pub attestation: Option<UncheckedAccount<'info>>,
 
     /// The sysvar containing the full transaction's instructions
     /// CHECK: Validated by requiring its well-known address
     #[account(address = ix_sysvar::Instructions::id())]
     pub instruction_sysvar: AccountInfo<'info>,
 
+    /// The SPL Memo program, required when the signed message carries a memo
+    pub memo_program: Option<Program<'info, Memo>>,
+
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 //////////////////////////////// HANDLERS ////////////////////////////////
 
 impl<'info> Claim<'info> {
-    pub fn claim(&mut self, project_nonce: u64, nonce: u64) -> Result<()> {
+    /// `ed25519_ix_index` is the position of the distributors' signature verification in the
+    /// transaction, which may be followed by compute-budget or other instructions before this one.
+    ///
+    /// With a `merkle_claim`, the distributors signed a `MerkleRootMessage` over the whole
+    /// campaign instead of this grant, and the claim is authorized by the proof that the
+    /// recipient's allocation is included under the signed root.
+    ///
+    /// `remaining_accounts` carries any extra accounts a Token-2022 transfer hook needs
+    pub fn claim(
+        &mut self,
+        project_nonce: u64,
+        nonce: u64,
+        ed25519_ix_index: u8,
+        merkle_claim: Option<MerkleClaim>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
         // Load the instruction sysvar account (holds all tx instructions)
         let ix_sysvar_account = self.instruction_sysvar.to_account_info();
 
-        // Verify the Ed25519 signature and extract the signed message
-        let (distributor_pubkey, message) = verify_ed25519_signature(&ix_sysvar_account)?;
+        // Verify the distributor signatures and extract the signed message
+        let (key_type, signers, message) =
+            verify_distributor_signature_at(&ix_sysvar_account, ed25519_ix_index as usize)?;
 
-        // Validate the distributor's public key against global config
-        require!(
-            distributor_pubkey == self.global_config.distributor,
-            AirdropError::DistributorMismatch
-        );
+        // Validate the signers against the applicable distributor set
+        validate_claim_signers(key_type, &signers, &self.global_config, &self.project)?;
+
+        let data = match merkle_claim {
+            Some(merkle_claim) => self.merkle_grant(&message, nonce, merkle_claim)?,
+            None => {
+                // Deserialize the message using Borsh
+                let airdrop_msg = decode_airdrop_message(&message)?;
 
-        // Deserialize the message using Borsh
-        let airdrop_msg =
-            AirdropMessage::try_from_slice(&message).map_err(|_| AirdropError::InvalidMessage)?;
+                // Validate generic signed message fields (program_id, version, deadline)
+                validate_message_domain(
+                    &airdrop_msg.domain,
+                    MessageKind::Claim,
+                    nonce,
+                    &self.global_config.genesis_hash,
+                    self.project.deadline_grace_secs(&self.global_config),
+                )?;
+                airdrop_msg.data
+            }
+        };
 
-        // Validate generic signed message fields (program_id, version, deadline)
-        validate_message_domain(&airdrop_msg.domain, nonce)?;
+        // Enforce the project's claim window independently of the message deadline
+        let clock = Clock::get()?;
+        self.project.check_claim_window(clock.unix_timestamp)?;
+        self.throttle(clock.slot)?;
+        self.check_attestation(clock.unix_timestamp)?;
 
-        // Initialize the nullifier to mark this nonce as used
-        // If this nonce was already used, the init constraint above would have failed
-        self.nullifier.set_inner(ClaimNullifier { nonce });
+        self.charge_claim_fee()?;
+        self.reimburse_rent()?;
+
+        // Mark this nonce as used
+        self.consume_nonce(nonce)?;
+        self.record_group_claim()?;
 
         // Validate data
+        validate_grant_target(
+            &data,
+            project_nonce,
+            &self.recipient.key(),
+            &self.project,
+            &self.mint,
+        )?;
+        require!(!data.is_vesting(), AirdropError::VestingRequired);
+        let referrer_token_account = self.referrer_token_account(&data)?;
+
+        let destination = self.destination(data.destination)?;
+        let mut settlement = ClaimSettlement {
+            global_config: &mut self.global_config,
+            protocol_stats: &mut self.protocol_stats,
+            project: &mut self.project,
+            recipient_claim_state: &mut self.recipient_claim_state,
+            recipient: self.recipient.key(),
+            mint: &self.mint,
+            token_vault: &mut self.token_vault,
+            fee_vault: &self.fee_vault,
+            fee_override_token_account: self.fee_override_token_account.as_deref(),
+            destination,
+            token_program: self.token_program.to_account_info(),
+            #[cfg(feature = "event-cpi")]
+            event_authority: self.event_authority.to_account_info(),
+        };
+        let amount =
+            settlement.record_grant(data.phase, data.tier, data.amount, &clock)?;
+
+        // Carve the referrer's share out of the grant before paying the recipient
+        let referral_amount = match referrer_token_account {
+            Some(referrer_token_account) => {
+                let referral_amount = calculate_fee(amount, data.referral_bps)?;
+                settlement.pay_referral(
+                    project_nonce,
+                    referral_amount,
+                    referrer_token_account,
+                    remaining_accounts,
+                )?;
+                referral_amount
+            }
+            None => 0,
+        };
+        settlement.pay_out(project_nonce, amount - referral_amount, 1, remaining_accounts)?;
+        self.lock_claim(clock.unix_timestamp)?;
+
+        if let Some(memo) = &data.memo {
+            self.write_memo(memo)?;
+        }
+
+        if let Some(claim_receipt) = &mut self.claim_receipt {
+            claim_receipt.set_inner(ClaimReceipt {
+                project: self.project.key(),
+                recipient: self.recipient.key(),
+                amount,
+                slot: clock.slot,
+                nonce,
+            });
+        }
+
+        if let Some(recipient_history) = &mut self.recipient_history {
+            recipient_history.record(self.project.key(), self.recipient.key(), clock.unix_timestamp);
+        }
+
+        emit_event!(self, ClaimEvent {
+            project: self.project.key(),
+            recipient: self.recipient.key(),
+            mint: self.mint.key(),
+            amount,
+            nonce,
+            timestamp: clock.unix_timestamp,
+        });
+
+        self.unwrap_sol(project_nonce)
+    }
+}
+
+impl<'info> Claim<'info> {
+    /// Returns the grant of the recipient's allocation under the distributors' signed Merkle
+    /// root, once the root message is valid and `merkle_claim` proves the allocation is
+    /// included under it with claim nonce `nonce`
+    fn merkle_grant(
+        &self,
+        message: &[u8],
+        nonce: u64,
+        merkle_claim: MerkleClaim,
+    ) -> Result<AirdropMessageData> {
+        let root_msg = MerkleRootMessage::try_from_slice(message)
+            .map_err(|_| AirdropError::InvalidMessage)?;
+        validate_untagged_version(&root_msg.domain)?;
+        validate_message_envelope(
+            &root_msg.domain,
+            MessageKind::MerkleRoot,
+            &self.global_config.genesis_hash,
+            self.project.deadline_grace_secs(&self.global_config),
+        )?;
+
+        require!(
+            merkle_claim.proof.len() <= MAX_MERKLE_PROOF_LEN,
+            AirdropError::InvalidMerkleProof
+        );
+        let leaf = merkle_leaf(&self.recipient.key(), merkle_claim.amount, nonce);
+        require!(
+            merkle_root_from_proof(leaf, &merkle_claim.proof) == root_msg.root,
+            AirdropError::InvalidMerkleProof
+        );
+
+        Ok(root_msg.grant(self.recipient.key(), merkle_claim.amount))
+    }
 
+    /// Returns the token account the claim pays into: the lockup escrow when the project locks
+    /// claims, the destination token account when one is given and authorized, otherwise the
+    /// recipient's associated token account
+    fn destination(&self, authorized_destination: Option<Pubkey>) -> Result<AccountInfo<'info>> {
+        if self.project.lock_duration > 0 {
+            require!(
+                self.destination_token_account.is_none()
+                    && self.wsol_unwrap_account.is_none()
+                    && self.locked_claim.is_some(),
+                AirdropError::InvalidLockup
+            );
+            let locked_claim_vault = self
+                .locked_claim_vault
+                .as_ref()
+                .ok_or(AirdropError::InvalidLockup)?;
+            return Ok(locked_claim_vault.to_account_info());
+        }
         require!(
-            airdrop_msg.data.project_nonce == project_nonce,
-            AirdropError::ProjectMismatch
+            self.locked_claim.is_none() && self.locked_claim_vault.is_none(),
+            AirdropError::InvalidLockup
         );
+
+        if let Some(wsol_unwrap_account) = &self.wsol_unwrap_account {
+            require!(
+                self.destination_token_account.is_none() && is_native_mint(&self.mint.key()),
+                AirdropError::InvalidUnwrap
+            );
+            return Ok(wsol_unwrap_account.to_account_info());
+        }
+
+        if let Some(destination) = &self.destination_token_account {
+            require!(
+                destination.owner == self.recipient.key()
+                    || authorized_destination == Some(destination.key()),
+                AirdropError::InvalidDestination
+            );
+            return Ok(destination.to_account_info());
+        }
+
+        let recipient_token_account = self
+            .recipient_token_account
+            .as_ref()
+            .ok_or(AirdropError::InvalidDestination)?;
+        Ok(recipient_token_account.to_account_info())
+    }
+
+    /// Requires a valid attestation about the recipient when the project gates claims on one
+    fn check_attestation(&self, now: i64) -> Result<()> {
+        let Some(gate) = self.project.attestation_gate else {
+            return Ok(());
+        };
+
+        let attestation = self
+            .attestation
+            .as_ref()
+            .ok_or(AirdropError::AttestationRequired)?;
         require!(
-            airdrop_msg.data.recipient == self.recipient.key(),
-            AirdropError::RecipientMismatch
+            is_valid_attestation(
+                attestation,
+                &gate.program,
+                &gate.credential,
+                &gate.schema,
+                &self.recipient.key(),
+                now,
+            ),
+            AirdropError::AttestationRequired
         );
+
+        Ok(())
+    }
+
+    /// Returns the referrer's token account when the grant pays a referral, checking it
+    /// belongs to the signed referrer
+    fn referrer_token_account(&self, data: &AirdropMessageData) -> Result<Option<AccountInfo<'info>>> {
+        if !data.has_referral() {
+            return Ok(None);
+        }
+
+        let referrer = data.referrer.ok_or(AirdropError::InvalidReferral)?;
+        let referrer_token_account = self
+            .referrer_token_account
+            .as_ref()
+            .ok_or(AirdropError::InvalidReferral)?;
         require!(
-            airdrop_msg.data.mint == self.mint.key(),
-            AirdropError::MintMismatch
+            referrer_token_account.owner == referrer
+                && data.referral_bps as u64 <= BPS_DENOMINATOR,
+            AirdropError::InvalidReferral
         );
+
+        Ok(Some(referrer_token_account.to_account_info()))
+    }
+
+    /// Marks the nonce as used in the project's nullifier mode: a new nullifier PDA, the
+    /// nonce's bit in its bitmap shard, or the recipient's sequential nonce counter
+    fn consume_nonce(&mut self, nonce: u64) -> Result<()> {
+        let project = self.project.key();
+        if self.project.nullifier_mode != NullifierMode::Pda {
+            require!(self.nullifier.is_none(), AirdropError::InvalidNullifier);
+        }
+
+        match self.project.nullifier_mode {
+            NullifierMode::Pda => {
+                // If this nonce was already used, the nullifier's init constraint would have failed
+                let nullifier = self
+                    .nullifier
+                    .as_mut()
+                    .ok_or(AirdropError::InvalidNullifier)?;
+                nullifier.set_inner(ClaimNullifier { nonce });
+                Ok(())
+            }
+            NullifierMode::Bitmap => self
+                .nullifier_shard
+                .as_mut()
+                .ok_or(AirdropError::InvalidNullifier)?
+                .consume(project, nonce),
+            NullifierMode::Sequential => self
+                .recipient_nonce
+                .as_mut()
+                .ok_or(AirdropError::InvalidNullifier)?
+                .consume(project, self.recipient.key(), nonce),
+        }
+    }
+
+    /// Reimburses the payer from the project's rent pool, as far as it reaches, for the
+    /// nullifier, a new nullifier shard or nonce counter and, on the recipient's first claim,
+    /// their claim accounting. Token accounts created with `init_if_needed` stay with the
+    /// payer, as their creation is not observable.
+    fn reimburse_rent(&mut self) -> Result<()> {
+        if self.project.rent_pool_lamports == 0 {
+            return Ok(());
+        }
+
+        let rent = Rent::get()?;
+        let mut rent_paid = 0;
+        if self.nullifier.is_some() {
+            rent_paid +=
+                rent.minimum_balance(ClaimNullifier::DISCRIMINATOR.len() + ClaimNullifier::INIT_SPACE);
+        }
+        if self
+            .nullifier_shard
+            .as_ref()
+            .is_some_and(|shard| shard.project == Pubkey::default())
+        {
+            rent_paid +=
+                rent.minimum_balance(NullifierShard::DISCRIMINATOR.len() + NullifierShard::INIT_SPACE);
+        }
+        if self
+            .recipient_nonce
+            .as_ref()
+            .is_some_and(|recipient_nonce| recipient_nonce.project == Pubkey::default())
+        {
+            rent_paid +=
+                rent.minimum_balance(RecipientNonce::DISCRIMINATOR.len() + RecipientNonce::INIT_SPACE);
+        }
+        if self.recipient_claim_state.project == Pubkey::default() {
+            rent_paid += rent.minimum_balance(
+                RecipientClaimState::DISCRIMINATOR.len() + RecipientClaimState::INIT_SPACE,
+            );
+        }
+
+        let reimbursement = rent_paid.min(self.project.rent_pool_lamports);
+        self.project.rent_pool_lamports -= reimbursement;
+        transfer_native(
+            &self.project.to_account_info(),
+            &self.payer.to_account_info(),
+            reimbursement,
+            None,
+        )
+    }
+
+    /// Writes the signed memo through the SPL Memo program
+    fn write_memo(&self, memo: &str) -> Result<()> {
+        require!(memo.len() <= MAX_MEMO_LEN, AirdropError::InvalidMemo);
+        let memo_program = self
+            .memo_program
+            .as_ref()
+            .ok_or(AirdropError::InvalidMemo)?;
+
+        memo::build_memo(
+            CpiContext::new(memo_program.to_account_info(), BuildMemo {}),
+            memo.as_bytes(),
+        )
+    }
+
+    /// Charges the payer the project's flat lamport claim fee, if any, sending it to the fee treasury
+    fn charge_claim_fee(&self) -> Result<()> {
+        if self.project.claim_fee_lamports == 0 {
+            return Ok(());
+        }
+        let fee_treasury = self
+            .fee_treasury
+            .as_ref()
+            .ok_or(AirdropError::InvalidFeeTreasury)?;
+
+        transfer_native(
+            &self.payer.to_account_info(),
+            &fee_treasury.to_account_info(),
+            self.project.claim_fee_lamports,
+            None,
+        )
+    }
+
+    /// Counts the claim against the project's throttle, if it has one
+    fn throttle(&mut self, slot: u64) -> Result<()> {
+        if self.project.max_claims_per_window == 0 {
+            return Ok(());
+        }
+
+        let claim_throttle = self
+            .claim_throttle
+            .as_mut()
+            .ok_or(AirdropError::InvalidThrottle)?;
+        claim_throttle.record_claim(
+            self.project.key(),
+            self.project.max_claims_per_window,
+            self.project.throttle_window_slots,
+            slot,
+        )
+    }
+
+    /// Marks the recipient as having claimed in the project's campaign group, if it has one
+    fn record_group_claim(&mut self) -> Result<()> {
+        let Some(group_id) = self.project.claim_group else {
+            require!(self.group_claim.is_none(), AirdropError::InvalidGroupClaim);
+            return Ok(());
+        };
+
+        // If the recipient already claimed in the group, the record's init would have failed
+        let group_claim = self
+            .group_claim
+            .as_mut()
+            .ok_or(AirdropError::InvalidGroupClaim)?;
+        group_claim.set_inner(GroupClaim {
+            group_id,
+            recipient: self.recipient.key(),
+            project: self.project.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Records the tokens just paid into the recipient's lockup escrow, if the project locks
+    /// claims, restarting the lockup from `now`
+    fn lock_claim(&mut self, now: i64) -> Result<()> {
+        let (Some(locked_claim), Some(locked_claim_vault)) =
+            (&mut self.locked_claim, &mut self.locked_claim_vault)
+        else {
+            return Ok(());
+        };
+        locked_claim_vault.reload()?;
+        let received = locked_claim_vault.amount.saturating_sub(locked_claim.amount);
+        let unlock_ts = now
+            .checked_add(self.project.lock_duration)
+            .ok_or(AirdropError::MathOverflow)?;
+        locked_claim.lock(self.project.key(), self.recipient.key(), received, unlock_ts)?;
+        self.project.outstanding_locked = self
+            .project
+            .outstanding_locked
+            .checked_add(received)
+            .ok_or(AirdropError::MathOverflow)?;
+
+        emit_event!(self, ClaimLocked {
+            project: self.project.key(),
+            recipient: self.recipient.key(),
+            amount: received,
+            unlock_ts: locked_claim.unlock_ts,
+        });
+
+        Ok(())
+    }
+
+    /// Closes the temporary wrapped SOL account, if any, sending the claimed lamports to the
+    /// recipient and refunding its rent to the payer
+    fn unwrap_sol(&mut self, project_nonce: u64) -> Result<()> {
+        let Some(wsol_unwrap_account) = &mut self.wsol_unwrap_account else {
+            return Ok(());
+        };
+        let project_info = self.project.to_account_info();
+
+        // Close into the project, then pay the lamports out of it
+        let (amount, rent) = close_wsol_into_project(
+            self.token_program.to_account_info(),
+            project_info.clone(),
+            wsol_unwrap_account,
+            project_nonce,
+        )?;
+        transfer_native(&project_info, &self.recipient.to_account_info(), amount, None)?;
+        transfer_native(&project_info, &self.payer.to_account_info(), rent, None)?;
+
+        msg!("Unwrapped {} lamports to recipient", amount);
+
+        Ok(())
+    }
+}
+
+//////////////////////////////// HELPERS ////////////////////////////////
+
+/// Closes a temporary wrapped SOL account held by the project into the project account,
+/// returning the unwrapped amount and the rent the account held
+pub(crate) fn close_wsol_into_project<'info>(
+    token_program: AccountInfo<'info>,
+    project: AccountInfo<'info>,
+    wsol_account: &mut InterfaceAccount<'info, TokenAccount>,
+    project_nonce: u64,
+) -> Result<(u64, u64)> {
+    wsol_account.reload()?;
+    let amount = wsol_account.amount;
+    let wsol_info = wsol_account.to_account_info();
+    let rent = wsol_info.lamports() - amount;
+
+    let nonce_bytes = project_nonce.to_le_bytes();
+    let project_bump = get_project_bump(project_nonce, &crate::ID);
+    let seeds = &[PROJECT_SEED_PREFIX, nonce_bytes.as_ref(), &[project_bump]];
+
+    close_spl_account(
+        token_program,
+        project.clone(),
+        wsol_info,
+        project,
+        Some(&[&seeds[..]]),
+    )?;
+
+    Ok((amount, rent))
+}
+
+/// Checks the signed claim data against the instruction's project, recipient and mint,
+/// rejecting vesting and referral grants
+pub(crate) fn validate_claim_data(
+    data: &AirdropMessageData,
+    project_nonce: u64,
+    recipient: &Pubkey,
+    project: &Project,
+    mint: &InterfaceAccount<Mint>,
+) -> Result<()> {
+    validate_grant_fields(data, project_nonce, recipient, project, mint)?;
+
+    // Vesting grants may only be released through `claim_vesting`
+    require!(!data.is_vesting(), AirdropError::VestingRequired);
+
+    Ok(())
+}
+
+/// Checks the signed grant fields shared by every claim variant other than `claim`,
+/// vesting included, rejecting referral grants and projects with claim-only restrictions
+pub(crate) fn validate_grant_fields(
+    data: &AirdropMessageData,
+    project_nonce: u64,
+    recipient: &Pubkey,
+    project: &Project,
+    mint: &InterfaceAccount<Mint>,
+) -> Result<()> {
+    validate_grant_target(data, project_nonce, recipient, project, mint)?;
+
+    // Referral rewards are only split out by `claim`
+    require!(!data.has_referral(), AirdropError::ReferralRequiresClaim);
+
+    reject_claim_only_settings(project)?;
+
+    // Other claim instructions use nullifier PDAs, which would not see nonces used otherwise
+    require!(
+        project.nullifier_mode == NullifierMode::Pda,
+        AirdropError::NullifierModeRequiresClaim
+    );
+
+    Ok(())
+}
+
+/// Rejects projects whose settings only `claim` enforces: it alone pays into the lockup
+/// escrow, counts claims against the throttle, checks who pays, checks attestations and
+/// records campaign group claims
+pub(crate) fn reject_claim_only_settings(project: &Project) -> Result<()> {
+    require!(project.lock_duration == 0, AirdropError::LockupRequiresClaim);
+    require!(
+        project.max_claims_per_window == 0,
+        AirdropError::ThrottleRequiresClaim
+    );
+    require!(
+        !project.recipient_must_pay,
+        AirdropError::RecipientPayerRequiresClaim
+    );
+    require!(
+        project.attestation_gate.is_none(),
+        AirdropError::AttestationRequiresClaim
+    );
+    require!(project.claim_group.is_none(), AirdropError::GroupRequiresClaim);
+
+    Ok(())
+}
+
+/// Checks the signed grant targets the instruction's project, recipient and mint, with an
+/// amount within the project's bounds
+fn validate_grant_target(
+    data: &AirdropMessageData,
+    project_nonce: u64,
+    recipient: &Pubkey,
+    project: &Project,
+    mint: &InterfaceAccount<Mint>,
+) -> Result<()> {
+    require!(
+        data.project_nonce == project_nonce,
+        AirdropError::ProjectMismatch
+    );
+    require!(data.recipient == *recipient, AirdropError::RecipientMismatch);
+    require!(data.mint == mint.key(), AirdropError::MintMismatch);
+    require!(project.mint == mint.key(), AirdropError::MintMismatch);
+    require!(
+        project.decimals == mint.decimals,
+        AirdropError::DecimalsMismatch
+    );
+
+    // Reject grants that would only waste a nonce, and absurd signed amounts
+    require!(data.amount > 0, AirdropError::ZeroClaimAmount);
+    if let Some(max_claim_amount) = project.max_claim_amount {
         require!(
-            self.project.mint == self.mint.key(),
-            AirdropError::MintMismatch
+            data.amount <= max_claim_amount,
+            AirdropError::ClaimAmountExceedsProjectMax
         );
+    }
+
+    Ok(())
+}
+
+/// The accounts a claim instruction settles against, shared by every claim variant so
+/// grants are counted and paid out the same way
+pub(crate) struct ClaimSettlement<'a, 'info> {
+    pub global_config: &'a mut GlobalConfig,
+    pub protocol_stats: &'a mut ProtocolStats,
+    pub project: &'a mut Account<'info, Project>,
+    pub recipient_claim_state: &'a mut RecipientClaimState,
+    pub recipient: Pubkey,
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub token_vault: &'a mut InterfaceAccount<'info, TokenAccount>,
+    pub fee_vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub fee_override_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    /// The token account the claimed tokens are sent to
+    pub destination: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    #[cfg(feature = "event-cpi")]
+    pub event_authority: AccountInfo<'info>,
+}
+
+impl<'info> ClaimSettlement<'_, 'info> {
+    /// Counts a claimed amount against the signed phase and tier, the per-claim maximum, the
+    /// project budget, the recipient's cap and the global rate limit. Returns the
+    /// amount to pay out, phase and tier bonuses included.
+    pub fn record_grant(&mut self, phase: u8, tier: u8, amount: u64, clock: &Clock) -> Result<u64> {
+        // Apply the active phase's claim limit and bonus, then the tier's opening time and bonus
+        let amount = self
+            .project
+            .apply_phase(phase, clock.unix_timestamp, amount)?;
+        let amount = self
+            .project
+            .apply_tier(tier, clock.unix_timestamp, amount)?;
+
+        self.record_amount(amount, clock.slot)?;
+
+        Ok(amount)
+    }
+
+    /// Counts an amount against the per-claim maximum, the project budget, the recipient's
+    /// cap and the global rate limit, without applying a phase
+    pub fn record_amount(&mut self, amount: u64, slot: u64) -> Result<()> {
+        if let Some(max_claim_amount) = self.global_config.max_claim_amount {
+            require!(
+                amount <= max_claim_amount,
+                AirdropError::ClaimAmountExceedsMax
+            );
+        }
+
+        // Count the claim against the project budget
+        self.project.record_claim(amount)?;
+
+        // Count the claim against the recipient's cumulative cap
+        self.recipient_claim_state.record(
+            self.project.key(),
+            self.recipient,
+            amount,
+            self.project.max_per_recipient,
+        )?;
+
+        // Count the claim against the global rate limit
+        self.global_config.record_claim(slot)?;
+
+        Ok(())
+    }
+
+    /// Pays a referrer's share of a grant, free of the protocol fee
+    pub fn pay_referral(
+        &mut self,
+        project_nonce: u64,
+        amount: u64,
+        referrer_token_account: AccountInfo<'info>,
+        hook_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let nonce_bytes = project_nonce.to_le_bytes();
+        let project_bump = get_project_bump(project_nonce, &crate::ID);
+        let seeds = &[PROJECT_SEED_PREFIX, nonce_bytes.as_ref(), &[project_bump]];
 
-        // Log all fields
-        msg!("Airdrop Message Fields:");
-        msg!("  Recipient: {}", airdrop_msg.data.recipient);
-        msg!("  Amount: {}", airdrop_msg.data.amount);
-        msg!("  Mint: {}", airdrop_msg.data.mint);
-        msg!("  Deadline: {}", airdrop_msg.domain.deadline);
-        msg!("  Nonce: {}", airdrop_msg.domain.nonce);
-        msg!("  Project Nonce: {}", airdrop_msg.data.project_nonce);
+        self.send(referrer_token_account.clone(), hook_accounts, amount, &[&seeds[..]])?;
 
-        // Transfer tokens from project to recipient
+        self.protocol_stats.total_distributed = self
+            .protocol_stats
+            .total_distributed
+            .saturating_add(amount as u128);
+
+        emit_event!(self, ReferralPaid {
+            project: self.project.key(),
+            recipient: self.recipient,
+            referrer_token_account: referrer_token_account.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Pays `amount` (covering `claims` grants) to the destination, splitting off the protocol
+    /// fee. `hook_accounts` carries any extra accounts a
+    /// Token-2022 transfer hook needs.
+    pub fn pay_out(
+        &mut self,
+        project_nonce: u64,
+        amount: u64,
+        claims: u64,
+        hook_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        // Split the protocol fee off the claimed amount, using the project's fee tier if it has one
+        let fee_bps = match self.project.fee_override {
+            Some(fee_override) => fee_override.fee_bps,
+            None => self.global_config.fee_bps,
+        };
+        let fee = calculate_fee(amount, fee_bps)?;
+        let net_amount = amount - fee;
+
+        // Account for a Token-2022 transfer fee on the recipient's transfer: either send enough
+        // extra for the recipient to net the full amount, or let the fee come out in transit
+        let mint_info = self.mint.to_account_info();
+        let (send_amount, received_amount) = if self.project.mint_on_claim {
+            // Minted tokens are not transferred, so no transfer fee applies
+            (net_amount, net_amount)
+        } else if self.project.gross_up_transfer_fees {
+            let gross_amount = get_gross_amount_for_net(&mint_info, net_amount)?;
+            // The extra tokens leave the project vault too, so they count against its budget
+            self.project.record_outflow(gross_amount - net_amount)?;
+            (gross_amount, net_amount)
+        } else {
+            (net_amount, net_amount - get_transfer_fee(&mint_info, net_amount)?)
+        };
+
+        // Send tokens from project to recipient
         let nonce_bytes = project_nonce.to_le_bytes();
         let project_bump = get_project_bump(project_nonce, &crate::ID);
         let seeds = &[PROJECT_SEED_PREFIX, nonce_bytes.as_ref(), &[project_bump]];
         let signer_seeds = &[&seeds[..]];
 
+        self.send(self.destination.clone(), hook_accounts, send_amount, signer_seeds)?;
+
+        let fee_destination = self.fee_destination()?;
+        if fee > 0 {
+            self.send(fee_destination.clone(), hook_accounts, fee, signer_seeds)?;
+        }
+
+        emit_event!(self, Claimed {
+            project: self.project.key(),
+            recipient: self.recipient,
+            destination: self.destination.key(),
+            amount,
+            net_amount: send_amount,
+            received_amount,
+            fee,
+            fee_destination: fee_destination.key(),
+        });
+
+        // Warn off-chain keepers before the vault runs dry
+        self.token_vault.reload()?;
+        if !self.project.mint_on_claim && self.project.is_low_balance(self.token_vault.amount) {
+            emit_event!(self, VaultLowBalance {
+                project: self.project.key(),
+                vault_balance: self.token_vault.amount,
+                low_balance_threshold: self.project.low_balance_threshold,
+                remaining_budget: self.project.budget.saturating_sub(self.project.total_claimed),
+            });
+        }
+
+        let stats: &mut ProtocolStats = self.protocol_stats;
+        stats.total_claims = stats.total_claims.saturating_add(claims);
+        stats.total_distributed = stats
+            .total_distributed
+            .saturating_add(amount as u128);
+
+        msg!(
+            "Successfully transferred {} tokens to recipient ({} received, {} protocol fee)",
+            send_amount,
+            received_amount,
+            fee
+        );
+
+        Ok(())
+    }
+
+    /// Sends `amount` to `destination`, minting it when the project mints on claim and
+    /// transferring it out of the project vault otherwise
+    fn send(
+        &self,
+        destination: AccountInfo<'info>,
+        hook_accounts: &[AccountInfo<'info>],
+        amount: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        if self.project.mint_on_claim {
+            return mint_spl(
+                self.token_program.clone(),
+                self.project.to_account_info(),
+                self.mint.to_account_info(),
+                destination,
+                amount,
+                Some(signer_seeds),
+            );
+        }
+
         transfer_spl(
-            self.token_program.to_account_info(),
+            self.token_program.clone(),
             self.project.to_account_info(),
-            self.project_token_account.to_account_info(),
-            self.recipient_token_account.to_account_info(),
-            airdrop_msg.data.amount,
+            self.token_vault.to_account_info(),
+            self.mint.to_account_info(),
+            destination,
+            hook_accounts,
+            amount,
+            self.project.decimals,
             Some(signer_seeds),
-        )?;
+        )
+    }
 
-        msg!(
-            "Successfully transferred {} tokens to recipient",
-            airdrop_msg.data.amount
+    /// Returns the token account that receives the protocol fee: the override treasury's
+    /// account when the project has a fee override, otherwise the global fee vault
+    fn fee_destination(&self) -> Result<AccountInfo<'info>> {
+        let Some(fee_override) = self.project.fee_override else {
+            return Ok(self.fee_vault.to_account_info());
+        };
+
+        let token_account = self
+            .fee_override_token_account
+            .ok_or(AirdropError::InvalidFeeOverrideAccount)?;
+        require!(
+            token_account.owner == fee_override.fee_treasury
+                && token_account.mint == self.mint.key(),
+            AirdropError::InvalidFeeOverrideAccount
         );
 
-        Ok(())
+        Ok(token_account.to_account_info())
+    }
+}
+
+/// Validates the signers against the project's distributor set when it has one,
+/// otherwise against the global set (or the previous global set while the
+/// rotation grace period is still open). A project threshold replaces the
+/// threshold of whichever set is used.
+pub(crate) fn validate_claim_signers(
+    key_type: DistributorKeyType,
+    signers: &[Pubkey],
+    config: &GlobalConfig,
+    project: &Project,
+) -> Result<()> {
+    // Only the threshold applies to a project's own distributors, not their weights
+    if !project.distributors.is_empty() {
+        return validate_distributor_signatures(
+            key_type,
+            signers,
+            &project.distributors,
+            project.threshold_or(config.threshold),
+            0,
+        );
+    }
+
+    let current_slot = Clock::get()?.slot;
+    let current_set_result = validate_distributor_signatures(
+        key_type,
+        signers,
+        &config.distributors,
+        project.threshold_or(config.threshold),
+        config.required_weight,
+    );
+    if current_set_result.is_err()
+        && !config.previous_distributors.is_empty()
+        && current_slot < config.previous_distributors_expiry_slot
+    {
+        validate_distributor_signatures(
+            key_type,
+            signers,
+            &config.previous_distributors,
+            project.threshold_or(config.previous_threshold),
+            config.previous_required_weight,
+        )
+    } else {
+        current_set_result
     }
 }
@@ -0,0 +1,248 @@
+use super::claim::{validate_claim_data, validate_claim_signers, AirdropMessageData, ClaimSettlement};
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{sysvar::instructions as ix_sysvar, sysvar::SysvarId};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use borsh::BorshDeserialize;
+
+//////////////////////////////// MESSAGE ////////////////////////////////
+
+/// A grant whose claimed tokens are approved to a token delegate in the same instruction
+#[derive(BorshDeserialize)]
+pub struct DelegatedClaimMessage {
+    pub data: AirdropMessageData,
+    /// The delegate approved over the claimed tokens
+    pub token_delegate: Pubkey,
+    pub domain: MessageDomain,
+}
+
+//////////////////////////////// INSTRUCTIONS ////////////////////////////////
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64, nonce: u64)]
+pub struct ClaimAndDelegate<'info> {
+    /// The account paying for the nullifier, the claim accounting and any token accounts
+    /// created, so a relayer can cover the costs of a recipient without SOL
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The recipient of the airdrop (must match the recipient in the signed message), who
+    /// signs to approve the token delegate on its token account
+    pub recipient: Signer<'info>,
+
+    /// The global config PDA containing the distributor public keys
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        constraint = !global_config.paused @ AirdropError::ProgramPaused
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The protocol-wide statistics PDA
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// The project PDA from which tokens will be claimed
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        constraint = !project.frozen_by_admin @ AirdropError::ProjectFrozen,
+        constraint = !project.paused @ AirdropError::ProjectPaused
+    )]
+    pub project: Account<'info, Project>,
+
+    /// Nullifier account to prevent reuse of the nonce
+    /// If this account already exists, the transaction will fail, preventing replay attacks
+    #[account(
+        init,
+        payer = payer,
+        space = ClaimNullifier::DISCRIMINATOR.len() + ClaimNullifier::INIT_SPACE,
+        seeds = [
+            CLAIM_NULLIFIER_SEED_PREFIX,
+            project.key().as_ref(),
+            nonce.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub nullifier: Account<'info, ClaimNullifier>,
+
+    /// The recipient's cumulative claim accounting for this project
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RecipientClaimState::DISCRIMINATOR.len() + RecipientClaimState::INIT_SPACE,
+        seeds = [
+            RECIPIENT_CLAIM_STATE_SEED_PREFIX,
+            project.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump
+    )]
+    pub recipient_claim_state: Account<'info, RecipientClaimState>,
+
+    /// The recipient's denylist entry, which must not exist
+    /// CHECK: Only checked for emptiness; any data at this PDA means the recipient is denylisted
+    #[account(
+        seeds = [DENYLIST_SEED_PREFIX, recipient.key().as_ref()],
+        bump,
+        constraint = !is_program_account(&denylist_entry) @ AirdropError::RecipientDenylisted
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    /// The mint of the SPL token being distributed, written when the project mints on claim
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The project's token vault PDA (source of tokens)
+    #[account(
+        mut,
+        seeds = [TOKEN_VAULT_SEED_PREFIX, project.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token account owned by the global config PDA collecting protocol fees for this mint
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = global_config,
+        associated_token::token_program = token_program
+    )]
+    pub fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The project fee treasury's token account, required when the project has a fee override
+    #[account(mut)]
+    pub fee_override_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The recipient's token account (destination of tokens)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token delegate approved over the claimed tokens, e.g. a staking or governance program
+    /// CHECK: Must be the delegate named in the signed message
+    pub token_delegate: UncheckedAccount<'info>,
+
+    /// The sysvar containing the full transaction's instructions
+    /// CHECK: Validated by requiring its well-known address
+    #[account(address = ix_sysvar::Instructions::id())]
+    pub instruction_sysvar: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+//////////////////////////////// HANDLERS ////////////////////////////////
+
+impl<'info> ClaimAndDelegate<'info> {
+    /// Claims a grant signed by the distributors (immediately preceding this instruction) and
+    /// approves the signed token delegate over the tokens received, so a staking or governance
+    /// program can pull them in the same transaction.
+    ///
+    /// `remaining_accounts` carries any extra accounts a Token-2022 transfer hook needs.
+    pub fn claim_and_delegate(
+        &mut self,
+        project_nonce: u64,
+        nonce: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let (key_type, signers, message) = verify_distributor_signature(&self.instruction_sysvar)?;
+        validate_claim_signers(key_type, &signers, &self.global_config, &self.project)?;
+
+        let delegated_claim_msg = DelegatedClaimMessage::try_from_slice(&message)
+            .map_err(|_| AirdropError::InvalidMessage)?;
+        validate_untagged_version(&delegated_claim_msg.domain)?;
+        validate_message_domain(
+            &delegated_claim_msg.domain,
+            MessageKind::Claim,
+            nonce,
+            &self.global_config.genesis_hash,
+            self.project.deadline_grace_secs(&self.global_config),
+        )?;
+        require!(
+            delegated_claim_msg.token_delegate == self.token_delegate.key(),
+            AirdropError::InvalidTokenDelegate
+        );
+
+        let clock = Clock::get()?;
+        self.project.check_claim_window(clock.unix_timestamp)?;
+
+        let data = &delegated_claim_msg.data;
+        validate_claim_data(
+            data,
+            project_nonce,
+            &self.recipient.key(),
+            &self.project,
+            &self.mint,
+        )?;
+
+        // If this nonce was already used, the nullifier's init constraint would have failed
+        self.nullifier.set_inner(ClaimNullifier { nonce });
+
+        let balance_before = self.recipient_token_account.amount;
+        let mut settlement = ClaimSettlement {
+            global_config: &mut self.global_config,
+            protocol_stats: &mut self.protocol_stats,
+            project: &mut self.project,
+            recipient_claim_state: &mut self.recipient_claim_state,
+            recipient: self.recipient.key(),
+            mint: &self.mint,
+            token_vault: &mut self.token_vault,
+            fee_vault: &self.fee_vault,
+            fee_override_token_account: self.fee_override_token_account.as_deref(),
+            destination: self.recipient_token_account.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            #[cfg(feature = "event-cpi")]
+            event_authority: self.event_authority.to_account_info(),
+        };
+        let amount = settlement.record_grant(data.phase, data.tier, data.amount, &clock)?;
+        settlement.pay_out(project_nonce, amount, 1, remaining_accounts)?;
+
+        // Approve exactly what arrived, net of protocol and transfer fees
+        self.recipient_token_account.reload()?;
+        let received = self
+            .recipient_token_account
+            .amount
+            .checked_sub(balance_before)
+            .ok_or(AirdropError::MathOverflow)?;
+        approve_spl(
+            self.token_program.to_account_info(),
+            self.recipient.to_account_info(),
+            self.recipient_token_account.to_account_info(),
+            self.mint.to_account_info(),
+            self.token_delegate.to_account_info(),
+            received,
+            self.mint.decimals,
+        )?;
+
+        emit_event!(self, ClaimDelegateApproved {
+            project: self.project.key(),
+            recipient: self.recipient.key(),
+            token_delegate: self.token_delegate.key(),
+            nonce,
+            amount: received,
+        });
+
+        Ok(())
+    }
+}
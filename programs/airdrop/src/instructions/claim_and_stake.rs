@@ -0,0 +1,346 @@
+use super::claim::{
+    close_wsol_into_project, validate_claim_data, validate_claim_signers, ClaimSettlement,
+};
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    program::{invoke, invoke_signed},
+    sysvar::{instructions as ix_sysvar, stake_history, SysvarId},
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use borsh::BorshDeserialize;
+use solana_stake_interface::{
+    instruction as stake_instruction,
+    program as stake_program,
+    state::{Authorized, Lockup, StakeAuthorize, StakeStateV2},
+};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64, nonce: u64)]
+pub struct ClaimAndStake<'info> {
+    /// The account paying for the nullifier, the claim accounting and the stake account's
+    /// rent, so a relayer can cover the costs of a recipient without SOL
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The recipient of the airdrop, who becomes the stake account's staker and withdrawer
+    /// CHECK: Needs no signature; the distributors' signed message authorizes the claim
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The global config PDA containing the distributor public keys
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        constraint = !global_config.paused @ AirdropError::ProgramPaused
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The protocol-wide statistics PDA
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// The project PDA from which SOL will be claimed
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        constraint = !project.frozen_by_admin @ AirdropError::ProjectFrozen,
+        constraint = !project.paused @ AirdropError::ProjectPaused
+    )]
+    pub project: Account<'info, Project>,
+
+    /// Nullifier account to prevent reuse of the nonce
+    /// If this account already exists, the transaction will fail, preventing replay attacks
+    #[account(
+        init,
+        payer = payer,
+        space = ClaimNullifier::DISCRIMINATOR.len() + ClaimNullifier::INIT_SPACE,
+        seeds = [
+            CLAIM_NULLIFIER_SEED_PREFIX,
+            project.key().as_ref(),
+            nonce.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub nullifier: Account<'info, ClaimNullifier>,
+
+    /// The recipient's cumulative claim accounting for this project
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RecipientClaimState::DISCRIMINATOR.len() + RecipientClaimState::INIT_SPACE,
+        seeds = [
+            RECIPIENT_CLAIM_STATE_SEED_PREFIX,
+            project.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump
+    )]
+    pub recipient_claim_state: Account<'info, RecipientClaimState>,
+
+    /// The recipient's denylist entry, which must not exist
+    /// CHECK: Only checked for emptiness; any data at this PDA means the recipient is denylisted
+    #[account(
+        seeds = [DENYLIST_SEED_PREFIX, recipient.key().as_ref()],
+        bump,
+        constraint = !is_program_account(&denylist_entry) @ AirdropError::RecipientDenylisted
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    /// The wrapped SOL mint the project distributes
+    #[account(constraint = is_native_mint(&mint.key()) @ AirdropError::InvalidStake)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The project's token vault PDA (source of tokens)
+    #[account(
+        mut,
+        seeds = [TOKEN_VAULT_SEED_PREFIX, project.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token account owned by the global config PDA collecting protocol fees for this mint
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = global_config,
+        associated_token::token_program = token_program
+    )]
+    pub fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The project fee treasury's token account, required when the project has a fee override
+    #[account(mut)]
+    pub fee_override_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// A temporary wrapped SOL account the claim is paid into and then closed, releasing the
+    /// claimed lamports to stake
+    #[account(
+        init,
+        payer = payer,
+        seeds = [WSOL_UNWRAP_SEED_PREFIX, project.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
+    )]
+    pub wsol_unwrap_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The stake account created for the recipient, one per claimed nonce
+    /// CHECK: Created and initialized here through the System and Stake programs
+    #[account(
+        mut,
+        seeds = [
+            STAKE_ACCOUNT_SEED_PREFIX,
+            project.key().as_ref(),
+            nonce.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub stake_account: UncheckedAccount<'info>,
+
+    /// The vote account the project stakes claims to
+    /// CHECK: Must be the project's configured vote account; the Stake program validates it
+    #[account(
+        constraint = project.stake_vote_account == Some(vote_account.key()) @ AirdropError::InvalidStake
+    )]
+    pub vote_account: UncheckedAccount<'info>,
+
+    /// The sysvar containing the full transaction's instructions
+    /// CHECK: Validated by requiring its well-known address
+    #[account(address = ix_sysvar::Instructions::id())]
+    pub instruction_sysvar: AccountInfo<'info>,
+
+    /// The stake history sysvar, required to delegate stake
+    /// CHECK: Validated by requiring its well-known address
+    #[account(address = stake_history::ID)]
+    pub stake_history: AccountInfo<'info>,
+
+    /// The retired stake config account, required to delegate stake
+    /// CHECK: Validated by requiring its well-known address
+    #[account(address = STAKE_CONFIG_ID)]
+    pub stake_config: AccountInfo<'info>,
+
+    /// The Stake program
+    /// CHECK: Validated by requiring its well-known address
+    #[account(address = stake_program::ID)]
+    pub stake_program: AccountInfo<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> ClaimAndStake<'info> {
+    /// Claims a wrapped SOL grant signed by the distributors (immediately preceding this
+    /// instruction) as native SOL delegated to the project's vote account, in a new stake
+    /// account whose staker and withdrawer is the recipient
+    pub fn claim_and_stake(&mut self, project_nonce: u64, nonce: u64) -> Result<()> {
+        let (key_type, signers, message) = verify_distributor_signature(&self.instruction_sysvar)?;
+        validate_claim_signers(key_type, &signers, &self.global_config, &self.project)?;
+
+        let airdrop_msg = decode_airdrop_message(&message)?;
+        validate_message_domain(
+            &airdrop_msg.domain,
+            MessageKind::Claim,
+            nonce,
+            &self.global_config.genesis_hash,
+            self.project.deadline_grace_secs(&self.global_config),
+        )?;
+
+        let clock = Clock::get()?;
+        self.project.check_claim_window(clock.unix_timestamp)?;
+
+        validate_claim_data(
+            &airdrop_msg.data,
+            project_nonce,
+            &self.recipient.key(),
+            &self.project,
+            &self.mint,
+        )?;
+
+        // If this nonce was already used, the nullifier's init constraint would have failed
+        self.nullifier.set_inner(ClaimNullifier { nonce });
+
+        let mut settlement = ClaimSettlement {
+            global_config: &mut self.global_config,
+            protocol_stats: &mut self.protocol_stats,
+            project: &mut self.project,
+            recipient_claim_state: &mut self.recipient_claim_state,
+            recipient: self.recipient.key(),
+            mint: &self.mint,
+            token_vault: &mut self.token_vault,
+            fee_vault: &self.fee_vault,
+            fee_override_token_account: self.fee_override_token_account.as_deref(),
+            destination: self.wsol_unwrap_account.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            #[cfg(feature = "event-cpi")]
+            event_authority: self.event_authority.to_account_info(),
+        };
+        let amount = settlement.record_grant(
+            airdrop_msg.data.phase,
+            airdrop_msg.data.tier,
+            airdrop_msg.data.amount,
+            &clock,
+        )?;
+        settlement.pay_out(project_nonce, amount, 1, &[])?;
+
+        // Unwrap the claim into the project, refunding the temporary account's rent
+        let project_info = self.project.to_account_info();
+        let (staked, rent) = close_wsol_into_project(
+            self.token_program.to_account_info(),
+            project_info.clone(),
+            &mut self.wsol_unwrap_account,
+            project_nonce,
+        )?;
+        transfer_native(&project_info, &self.payer.to_account_info(), rent, None)?;
+
+        self.stake(project_nonce, nonce, staked)?;
+
+        emit_event!(self, ClaimStaked {
+            project: self.project.key(),
+            recipient: self.recipient.key(),
+            stake_account: self.stake_account.key(),
+            vote_account: self.vote_account.key(),
+            nonce,
+            amount: staked,
+        });
+
+        Ok(())
+    }
+
+    /// Creates the stake account, funds it with `lamports` from the project and delegates it
+    /// with the project as staker, then hands the staker authority to the recipient
+    fn stake(&self, project_nonce: u64, nonce: u64, lamports: u64) -> Result<()> {
+        let project_key = self.project.key();
+        let recipient_key = self.recipient.key();
+        let stake_key = self.stake_account.key();
+        let project_info = self.project.to_account_info();
+        let stake_info = self.stake_account.to_account_info();
+
+        let nonce_bytes = nonce.to_le_bytes();
+        let (_, stake_bump) = Pubkey::find_program_address(
+            &[
+                STAKE_ACCOUNT_SEED_PREFIX,
+                project_key.as_ref(),
+                nonce_bytes.as_ref(),
+            ],
+            &crate::ID,
+        );
+        create_pda_account(
+            &self.payer.to_account_info(),
+            &stake_info,
+            &self.system_program.to_account_info(),
+            StakeStateV2::size_of(),
+            &stake_program::ID,
+            &[
+                STAKE_ACCOUNT_SEED_PREFIX,
+                project_key.as_ref(),
+                nonce_bytes.as_ref(),
+                &[stake_bump],
+            ],
+        )?;
+        transfer_native(&project_info, &stake_info, lamports, None)?;
+
+        invoke(
+            &stake_instruction::initialize(
+                &stake_key,
+                &Authorized {
+                    staker: project_key,
+                    withdrawer: recipient_key,
+                },
+                &Lockup::default(),
+            ),
+            &[stake_info.clone(), self.rent.to_account_info()],
+        )?;
+
+        let project_nonce_bytes = project_nonce.to_le_bytes();
+        let project_bump = get_project_bump(project_nonce, &crate::ID);
+        let project_seeds = &[
+            PROJECT_SEED_PREFIX,
+            project_nonce_bytes.as_ref(),
+            &[project_bump],
+        ];
+
+        invoke_signed(
+            &stake_instruction::delegate_stake(&stake_key, &project_key, &self.vote_account.key()),
+            &[
+                stake_info.clone(),
+                self.vote_account.to_account_info(),
+                self.clock.to_account_info(),
+                self.stake_history.to_account_info(),
+                self.stake_config.to_account_info(),
+                project_info.clone(),
+            ],
+            &[&project_seeds[..]],
+        )?;
+
+        invoke_signed(
+            &stake_instruction::authorize(
+                &stake_key,
+                &project_key,
+                &recipient_key,
+                StakeAuthorize::Staker,
+                None,
+            ),
+            &[stake_info, self.clock.to_account_info(), project_info],
+            &[&project_seeds[..]],
+        )?;
+
+        Ok(())
+    }
+}
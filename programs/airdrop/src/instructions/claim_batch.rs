@@ -0,0 +1,236 @@
+use super::claim::{validate_claim_data, validate_claim_signers, ClaimSettlement};
+use crate::{constants::*, errors::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{sysvar::instructions as ix_sysvar, sysvar::SysvarId};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use borsh::BorshDeserialize;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct ClaimBatch<'info> {
+    /// The account paying for the nullifier, the claim accounting and any token accounts
+    /// created, so a relayer can cover the costs of a recipient without SOL
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The recipient of the airdrop (must match the recipient in every signed message)
+    /// CHECK: Needs no signature; the distributors' signed message authorizes the claim
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The global config PDA containing the distributor public keys
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        constraint = !global_config.paused @ AirdropError::ProgramPaused
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The protocol-wide statistics PDA
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// The project PDA from which tokens will be claimed
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        constraint = !project.frozen_by_admin @ AirdropError::ProjectFrozen,
+        constraint = !project.paused @ AirdropError::ProjectPaused
+    )]
+    pub project: Account<'info, Project>,
+
+    /// The recipient's cumulative claim accounting for this project
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RecipientClaimState::DISCRIMINATOR.len() + RecipientClaimState::INIT_SPACE,
+        seeds = [
+            RECIPIENT_CLAIM_STATE_SEED_PREFIX,
+            project.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump
+    )]
+    pub recipient_claim_state: Account<'info, RecipientClaimState>,
+
+    /// The recipient's denylist entry, which must not exist
+    /// CHECK: Only checked for emptiness; any data at this PDA means the recipient is denylisted
+    #[account(
+        seeds = [DENYLIST_SEED_PREFIX, recipient.key().as_ref()],
+        bump,
+        constraint = !is_program_account(&denylist_entry) @ AirdropError::RecipientDenylisted
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    /// The mint of the SPL token being distributed, written when the project mints on claim
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The project's token vault PDA (source of tokens)
+    #[account(
+        mut,
+        seeds = [TOKEN_VAULT_SEED_PREFIX, project.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token account owned by the global config PDA collecting protocol fees for this mint
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = global_config,
+        associated_token::token_program = token_program
+    )]
+    pub fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The project fee treasury's token account, required when the project has a fee override
+    #[account(mut)]
+    pub fee_override_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The recipient's token account (destination of tokens)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The sysvar containing the full transaction's instructions
+    /// CHECK: Validated by requiring its well-known address
+    #[account(address = ix_sysvar::Instructions::id())]
+    pub instruction_sysvar: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> ClaimBatch<'info> {
+    /// Claims several grants in one transaction. The distributors sign a single Borsh-encoded
+    /// `Vec<VersionedAirdropMessage>` with one message per nonce, in order.
+    ///
+    /// `remaining_accounts` starts with the nullifier PDA of each nonce, in order, followed by
+    /// any extra accounts a Token-2022 transfer hook needs. The grants are paid out in a
+    /// single aggregated transfer.
+    pub fn claim_batch(
+        &mut self,
+        project_nonce: u64,
+        nonces: Vec<u64>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(
+            !nonces.is_empty()
+                && nonces.len() <= MAX_BATCH_CLAIMS
+                && remaining_accounts.len() >= nonces.len(),
+            AirdropError::InvalidBatch
+        );
+        let (nullifiers, hook_accounts) = remaining_accounts.split_at(nonces.len());
+
+        // Verify the Ed25519 signatures over the batch message
+        let (key_type, signers, message) = verify_distributor_signature(&self.instruction_sysvar)?;
+        validate_claim_signers(key_type, &signers, &self.global_config, &self.project)?;
+
+        let messages = decode_airdrop_batch(&message)?;
+        require!(messages.len() == nonces.len(), AirdropError::InvalidBatch);
+
+        // Enforce the project's claim window independently of the message deadlines
+        let clock = Clock::get()?;
+        self.project.check_claim_window(clock.unix_timestamp)?;
+
+        for ((airdrop_msg, nonce), nullifier) in messages.iter().zip(&nonces).zip(nullifiers) {
+            validate_message_domain(
+                &airdrop_msg.domain,
+                MessageKind::Claim,
+                *nonce,
+                &self.global_config.genesis_hash,
+                self.project.deadline_grace_secs(&self.global_config),
+            )?;
+            validate_claim_data(
+                &airdrop_msg.data,
+                project_nonce,
+                &self.recipient.key(),
+                &self.project,
+                &self.mint,
+            )?;
+            self.init_nullifier(nullifier, *nonce)?;
+        }
+
+        let mut settlement = ClaimSettlement {
+            global_config: &mut self.global_config,
+            protocol_stats: &mut self.protocol_stats,
+            project: &mut self.project,
+            recipient_claim_state: &mut self.recipient_claim_state,
+            recipient: self.recipient.key(),
+            mint: &self.mint,
+            token_vault: &mut self.token_vault,
+            fee_vault: &self.fee_vault,
+            fee_override_token_account: self.fee_override_token_account.as_deref(),
+            destination: self.recipient_token_account.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            #[cfg(feature = "event-cpi")]
+            event_authority: self.event_authority.to_account_info(),
+        };
+
+        // Count every grant, then pay them out in a single aggregated transfer
+        let mut amount: u64 = 0;
+        for airdrop_msg in &messages {
+            let grant_amount =
+                settlement.record_grant(airdrop_msg.data.phase, airdrop_msg.data.tier, airdrop_msg.data.amount, &clock)?;
+            amount = amount
+                .checked_add(grant_amount)
+                .ok_or(AirdropError::MathOverflow)?;
+        }
+        settlement.pay_out(project_nonce, amount, nonces.len() as u64, hook_accounts)?;
+
+        Ok(())
+    }
+
+    /// Creates the nullifier PDA for a nonce, failing if it is the wrong address or already used
+    fn init_nullifier(&self, nullifier: &AccountInfo<'info>, nonce: u64) -> Result<()> {
+        let project_key = self.project.key();
+        let nonce_bytes = nonce.to_le_bytes();
+        let (expected, bump) = Pubkey::find_program_address(
+            &[
+                CLAIM_NULLIFIER_SEED_PREFIX,
+                project_key.as_ref(),
+                nonce_bytes.as_ref(),
+            ],
+            &crate::ID,
+        );
+        require!(
+            nullifier.key() == expected && !is_program_account(nullifier),
+            AirdropError::InvalidNullifier
+        );
+
+        create_pda_account(
+            &self.payer.to_account_info(),
+            nullifier,
+            &self.system_program.to_account_info(),
+            ClaimNullifier::DISCRIMINATOR.len() + ClaimNullifier::INIT_SPACE,
+            &crate::ID,
+            &[
+                CLAIM_NULLIFIER_SEED_PREFIX,
+                project_key.as_ref(),
+                nonce_bytes.as_ref(),
+                &[bump],
+            ],
+        )?;
+
+        ClaimNullifier { nonce }.try_serialize(&mut &mut nullifier.try_borrow_mut_data()?[..])
+    }
+}
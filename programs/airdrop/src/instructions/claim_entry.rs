@@ -0,0 +1,249 @@
+use super::claim::{validate_claim_data, validate_claim_signers, AirdropMessageData, ClaimSettlement};
+use crate::{constants::*, errors::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{sysvar::instructions as ix_sysvar, sysvar::SysvarId};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use borsh::BorshDeserialize;
+
+//////////////////////////////// MESSAGE ////////////////////////////////
+
+/// One grant in a multi-grant message
+#[derive(BorshDeserialize)]
+pub struct GrantEntry {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub nonce: u64,
+}
+
+/// Grants for several recipients of one project signed together, each redeemed separately
+/// by `claim_entry`
+#[derive(BorshDeserialize)]
+pub struct MultiGrantMessage {
+    pub mint: Pubkey,
+    pub project_nonce: u64,
+    pub phase: u8,
+    pub tier: u8,
+    pub entries: Vec<GrantEntry>,
+    pub domain: MessageDomain,
+}
+
+impl MultiGrantMessage {
+    /// The claim data of the entry at `index`, as if it had been signed on its own
+    fn entry_data(&self, index: usize) -> Result<(AirdropMessageData, u64)> {
+        let entry = self
+            .entries
+            .get(index)
+            .ok_or(AirdropError::InvalidGrantEntry)?;
+
+        let data = AirdropMessageData {
+            recipient: entry.recipient,
+            mint: self.mint,
+            project_nonce: self.project_nonce,
+            amount: entry.amount,
+            phase: self.phase,
+            destination: None,
+            cliff_ts: None,
+            vest_end_ts: None,
+            referrer: None,
+            referral_bps: 0,
+            tier: self.tier,
+            memo: None,
+        };
+        Ok((data, entry.nonce))
+    }
+}
+
+//////////////////////////////// INSTRUCTIONS ////////////////////////////////
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64, nonce: u64)]
+pub struct ClaimEntry<'info> {
+    /// The account paying for the nullifier, the claim accounting and any token accounts
+    /// created, so a relayer can cover the costs of a recipient without SOL
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The recipient of the airdrop (must match the recipient of the redeemed entry)
+    /// CHECK: Needs no signature; the distributors' signed message authorizes the claim
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The global config PDA containing the distributor public keys
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        constraint = !global_config.paused @ AirdropError::ProgramPaused
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The protocol-wide statistics PDA
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// The project PDA from which tokens will be claimed
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        constraint = !project.frozen_by_admin @ AirdropError::ProjectFrozen,
+        constraint = !project.paused @ AirdropError::ProjectPaused
+    )]
+    pub project: Account<'info, Project>,
+
+    /// Nullifier account to prevent reuse of the entry's nonce
+    /// If this account already exists, the transaction will fail, preventing replay attacks
+    #[account(
+        init,
+        payer = payer,
+        space = ClaimNullifier::DISCRIMINATOR.len() + ClaimNullifier::INIT_SPACE,
+        seeds = [
+            CLAIM_NULLIFIER_SEED_PREFIX,
+            project.key().as_ref(),
+            nonce.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub nullifier: Account<'info, ClaimNullifier>,
+
+    /// The recipient's cumulative claim accounting for this project
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RecipientClaimState::DISCRIMINATOR.len() + RecipientClaimState::INIT_SPACE,
+        seeds = [
+            RECIPIENT_CLAIM_STATE_SEED_PREFIX,
+            project.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump
+    )]
+    pub recipient_claim_state: Account<'info, RecipientClaimState>,
+
+    /// The recipient's denylist entry, which must not exist
+    /// CHECK: Only checked for emptiness; any data at this PDA means the recipient is denylisted
+    #[account(
+        seeds = [DENYLIST_SEED_PREFIX, recipient.key().as_ref()],
+        bump,
+        constraint = !is_program_account(&denylist_entry) @ AirdropError::RecipientDenylisted
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    /// The mint of the SPL token being distributed, written when the project mints on claim
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The project's token vault PDA (source of tokens)
+    #[account(
+        mut,
+        seeds = [TOKEN_VAULT_SEED_PREFIX, project.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token account owned by the global config PDA collecting protocol fees for this mint
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = global_config,
+        associated_token::token_program = token_program
+    )]
+    pub fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The project fee treasury's token account, required when the project has a fee override
+    #[account(mut)]
+    pub fee_override_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The recipient's token account (destination of tokens)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The sysvar containing the full transaction's instructions
+    /// CHECK: Validated by requiring its well-known address
+    #[account(address = ix_sysvar::Instructions::id())]
+    pub instruction_sysvar: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+//////////////////////////////// HANDLERS ////////////////////////////////
+
+impl<'info> ClaimEntry<'info> {
+    /// Redeems the entry at `index` of a multi-grant message signed by the distributors
+    /// (immediately preceding this instruction), nullifying only that entry's nonce.
+    ///
+    /// `remaining_accounts` carries any extra accounts a Token-2022 transfer hook needs.
+    pub fn claim_entry(
+        &mut self,
+        project_nonce: u64,
+        nonce: u64,
+        index: u16,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let (key_type, signers, message) = verify_distributor_signature(&self.instruction_sysvar)?;
+        validate_claim_signers(key_type, &signers, &self.global_config, &self.project)?;
+
+        let multi_grant_msg = MultiGrantMessage::try_from_slice(&message)
+            .map_err(|_| AirdropError::InvalidMessage)?;
+        validate_untagged_version(&multi_grant_msg.domain)?;
+        validate_message_envelope(
+            &multi_grant_msg.domain,
+            MessageKind::Claim,
+            &self.global_config.genesis_hash,
+            self.project.deadline_grace_secs(&self.global_config),
+        )?;
+
+        let clock = Clock::get()?;
+        self.project.check_claim_window(clock.unix_timestamp)?;
+
+        let (data, entry_nonce) = multi_grant_msg.entry_data(index as usize)?;
+        require!(entry_nonce == nonce, AirdropError::NonceMismatch);
+        validate_claim_data(
+            &data,
+            project_nonce,
+            &self.recipient.key(),
+            &self.project,
+            &self.mint,
+        )?;
+
+        // If this nonce was already used, the nullifier's init constraint would have failed
+        self.nullifier.set_inner(ClaimNullifier { nonce });
+
+        let mut settlement = ClaimSettlement {
+            global_config: &mut self.global_config,
+            protocol_stats: &mut self.protocol_stats,
+            project: &mut self.project,
+            recipient_claim_state: &mut self.recipient_claim_state,
+            recipient: self.recipient.key(),
+            mint: &self.mint,
+            token_vault: &mut self.token_vault,
+            fee_vault: &self.fee_vault,
+            fee_override_token_account: self.fee_override_token_account.as_deref(),
+            destination: self.recipient_token_account.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            #[cfg(feature = "event-cpi")]
+            event_authority: self.event_authority.to_account_info(),
+        };
+        let amount = settlement.record_grant(data.phase, data.tier, data.amount, &clock)?;
+        settlement.pay_out(project_nonce, amount, 1, remaining_accounts)
+    }
+}
@@ -0,0 +1,250 @@
+use super::claim::{validate_claim_data, validate_claim_signers, ClaimSettlement};
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{sysvar::instructions as ix_sysvar, sysvar::SysvarId};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use borsh::BorshDeserialize;
+
+//////////////////////////////// MESSAGE ////////////////////////////////
+
+/// The recipient's consent for a delegate to claim one of its grants
+#[derive(BorshDeserialize)]
+pub struct DelegationMessage {
+    pub recipient: Pubkey,
+    pub delegate: Pubkey,
+    pub project_nonce: u64,
+    pub domain: MessageDomain,
+}
+
+//////////////////////////////// INSTRUCTIONS ////////////////////////////////
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64, nonce: u64)]
+pub struct ClaimFor<'info> {
+    /// The party claiming on the recipient's behalf, which pays for the claim and receives the tokens
+    #[account(mut)]
+    pub delegate: Signer<'info>,
+
+    /// The recipient of the airdrop (must match the recipient in both signed messages)
+    /// CHECK: Needs no signature here; the recipient's signed delegation authorizes the claim
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The global config PDA containing the distributor public keys
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        constraint = !global_config.paused @ AirdropError::ProgramPaused
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The protocol-wide statistics PDA
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// The project PDA from which tokens will be claimed
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        constraint = !project.frozen_by_admin @ AirdropError::ProjectFrozen,
+        constraint = !project.paused @ AirdropError::ProjectPaused
+    )]
+    pub project: Account<'info, Project>,
+
+    /// Nullifier account to prevent nonce reuse (acts as a nullifier)
+    /// If this account already exists, the transaction will fail, preventing replay attacks
+    #[account(
+        init,
+        payer = delegate,
+        space = ClaimNullifier::DISCRIMINATOR.len() + ClaimNullifier::INIT_SPACE,
+        seeds = [
+            CLAIM_NULLIFIER_SEED_PREFIX,
+            project.key().as_ref(),
+            nonce.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub nullifier: Account<'info, ClaimNullifier>,
+
+    /// The recipient's cumulative claim accounting for this project
+    #[account(
+        init_if_needed,
+        payer = delegate,
+        space = RecipientClaimState::DISCRIMINATOR.len() + RecipientClaimState::INIT_SPACE,
+        seeds = [
+            RECIPIENT_CLAIM_STATE_SEED_PREFIX,
+            project.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump
+    )]
+    pub recipient_claim_state: Account<'info, RecipientClaimState>,
+
+    /// The recipient's denylist entry, which must not exist
+    /// CHECK: Only checked for emptiness; any data at this PDA means the recipient is denylisted
+    #[account(
+        seeds = [DENYLIST_SEED_PREFIX, recipient.key().as_ref()],
+        bump,
+        constraint = !is_program_account(&denylist_entry) @ AirdropError::RecipientDenylisted
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    /// The mint of the SPL token being distributed, written when the project mints on claim
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The project's token vault PDA (source of tokens)
+    #[account(
+        mut,
+        seeds = [TOKEN_VAULT_SEED_PREFIX, project.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token account owned by the global config PDA collecting protocol fees for this mint
+    #[account(
+        init_if_needed,
+        payer = delegate,
+        associated_token::mint = mint,
+        associated_token::authority = global_config,
+        associated_token::token_program = token_program
+    )]
+    pub fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The project fee treasury's token account, required when the project has a fee override
+    #[account(mut)]
+    pub fee_override_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The delegate's token account (destination of tokens)
+    #[account(
+        init_if_needed,
+        payer = delegate,
+        associated_token::mint = mint,
+        associated_token::authority = delegate,
+        associated_token::token_program = token_program
+    )]
+    pub delegate_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The sysvar containing the full transaction's instructions
+    /// CHECK: Validated by requiring its well-known address
+    #[account(address = ix_sysvar::Instructions::id())]
+    pub instruction_sysvar: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+//////////////////////////////// HANDLERS ////////////////////////////////
+
+impl<'info> ClaimFor<'info> {
+    /// Claims a grant on the recipient's behalf, sending the tokens to the delegate.
+    ///
+    /// The distributors' Ed25519 instruction must immediately precede this one, and the
+    /// recipient's signature over a `DelegationMessage` for the same nonce must precede that.
+    /// `remaining_accounts` carries any extra accounts a Token-2022 transfer hook needs.
+    pub fn claim_for(
+        &mut self,
+        project_nonce: u64,
+        nonce: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        // Verify the distributors' signatures over the grant
+        let (key_type, signers, message) = verify_distributor_signature(&self.instruction_sysvar)?;
+        validate_claim_signers(key_type, &signers, &self.global_config, &self.project)?;
+
+        let airdrop_msg = decode_airdrop_message(&message)?;
+        validate_message_domain(
+            &airdrop_msg.domain,
+            MessageKind::Claim,
+            nonce,
+            &self.global_config.genesis_hash,
+            self.project.deadline_grace_secs(&self.global_config),
+        )?;
+
+        // Verify the recipient's consent to this delegate claiming this grant
+        self.validate_delegation(project_nonce, nonce)?;
+
+        let clock = Clock::get()?;
+        self.project.check_claim_window(clock.unix_timestamp)?;
+
+        self.nullifier.set_inner(ClaimNullifier { nonce });
+
+        validate_claim_data(
+            &airdrop_msg.data,
+            project_nonce,
+            &self.recipient.key(),
+            &self.project,
+            &self.mint,
+        )?;
+
+        let mut settlement = ClaimSettlement {
+            global_config: &mut self.global_config,
+            protocol_stats: &mut self.protocol_stats,
+            project: &mut self.project,
+            recipient_claim_state: &mut self.recipient_claim_state,
+            recipient: self.recipient.key(),
+            mint: &self.mint,
+            token_vault: &mut self.token_vault,
+            fee_vault: &self.fee_vault,
+            fee_override_token_account: self.fee_override_token_account.as_deref(),
+            destination: self.delegate_token_account.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            #[cfg(feature = "event-cpi")]
+            event_authority: self.event_authority.to_account_info(),
+        };
+        let amount =
+            settlement.record_grant(airdrop_msg.data.phase, airdrop_msg.data.tier, airdrop_msg.data.amount, &clock)?;
+        settlement.pay_out(project_nonce, amount, 1, remaining_accounts)?;
+
+        emit_event!(self, ClaimDelegated {
+            project: self.project.key(),
+            recipient: self.recipient.key(),
+            delegate: self.delegate.key(),
+            nonce,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Checks that the recipient alone signed a delegation naming this delegate, project and nonce
+    fn validate_delegation(&self, project_nonce: u64, nonce: u64) -> Result<()> {
+        let (signers, message) = verify_ed25519_signature_at(&self.instruction_sysvar, 2)?;
+        require!(
+            signers == [self.recipient.key()],
+            AirdropError::InvalidDelegation
+        );
+
+        let delegation = DelegationMessage::try_from_slice(&message)
+            .map_err(|_| AirdropError::InvalidMessage)?;
+        validate_untagged_version(&delegation.domain)?;
+        validate_message_domain(
+            &delegation.domain,
+            MessageKind::Delegation,
+            nonce,
+            &self.global_config.genesis_hash,
+            self.project.deadline_grace_secs(&self.global_config),
+        )?;
+        require!(
+            delegation.recipient == self.recipient.key()
+                && delegation.delegate == self.delegate.key()
+                && delegation.project_nonce == project_nonce,
+            AirdropError::InvalidDelegation
+        );
+
+        Ok(())
+    }
+}
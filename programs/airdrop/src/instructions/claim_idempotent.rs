@@ -0,0 +1,236 @@
+use super::claim::{validate_claim_data, validate_claim_signers, ClaimSettlement};
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{sysvar::instructions as ix_sysvar, sysvar::SysvarId};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use borsh::BorshDeserialize;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64, nonce: u64)]
+pub struct ClaimIdempotent<'info> {
+    /// The account paying for the nullifier, the claim accounting and any token accounts
+    /// created, so a relayer can cover the costs of a recipient without SOL
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The recipient of the airdrop (must match the recipient in the signed message)
+    /// CHECK: Needs no signature; the distributors' signed message authorizes the claim
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The global config PDA containing the distributor public keys
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        constraint = !global_config.paused @ AirdropError::ProgramPaused
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The protocol-wide statistics PDA
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// The project PDA from which tokens will be claimed
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        constraint = !project.frozen_by_admin @ AirdropError::ProjectFrozen,
+        constraint = !project.paused @ AirdropError::ProjectPaused
+    )]
+    pub project: Account<'info, Project>,
+
+    /// Nullifier account of the nonce, created by the first claim; if it already exists the
+    /// claim is a no-op
+    /// CHECK: Created here when empty, otherwise checked to be the nonce's nullifier
+    #[account(
+        mut,
+        seeds = [
+            CLAIM_NULLIFIER_SEED_PREFIX,
+            project.key().as_ref(),
+            nonce.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub nullifier: UncheckedAccount<'info>,
+
+    /// The recipient's cumulative claim accounting for this project
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RecipientClaimState::DISCRIMINATOR.len() + RecipientClaimState::INIT_SPACE,
+        seeds = [
+            RECIPIENT_CLAIM_STATE_SEED_PREFIX,
+            project.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump
+    )]
+    pub recipient_claim_state: Account<'info, RecipientClaimState>,
+
+    /// The recipient's denylist entry, which must not exist
+    /// CHECK: Only checked for emptiness; any data at this PDA means the recipient is denylisted
+    #[account(
+        seeds = [DENYLIST_SEED_PREFIX, recipient.key().as_ref()],
+        bump,
+        constraint = !is_program_account(&denylist_entry) @ AirdropError::RecipientDenylisted
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    /// The mint of the SPL token being distributed, written when the project mints on claim
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The project's token vault PDA (source of tokens)
+    #[account(
+        mut,
+        seeds = [TOKEN_VAULT_SEED_PREFIX, project.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token account owned by the global config PDA collecting protocol fees for this mint
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = global_config,
+        associated_token::token_program = token_program
+    )]
+    pub fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The project fee treasury's token account, required when the project has a fee override
+    #[account(mut)]
+    pub fee_override_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The recipient's token account (destination of tokens)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The sysvar containing the full transaction's instructions
+    /// CHECK: Validated by requiring its well-known address
+    #[account(address = ix_sysvar::Instructions::id())]
+    pub instruction_sysvar: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> ClaimIdempotent<'info> {
+    /// Claims a grant signed by the distributors (immediately preceding this instruction) like
+    /// `claim`, except that a grant whose nonce was already claimed succeeds as a no-op, so a
+    /// relayer can safely retry a claim that may have landed.
+    ///
+    /// The signed grant is validated either way and, on a retry, matched against the recipient,
+    /// mint and amount recorded by the first claim, so only a retry of the same grant succeeds.
+    /// `remaining_accounts` carries any extra accounts a Token-2022 transfer hook needs.
+    pub fn claim_idempotent(
+        &mut self,
+        project_nonce: u64,
+        nonce: u64,
+        bump: u8,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let (key_type, signers, message) = verify_distributor_signature(&self.instruction_sysvar)?;
+        validate_claim_signers(key_type, &signers, &self.global_config, &self.project)?;
+
+        let airdrop_msg = decode_airdrop_message(&message)?;
+        validate_message_domain(
+            &airdrop_msg.domain,
+            MessageKind::Claim,
+            nonce,
+            &self.global_config.genesis_hash,
+            self.project.deadline_grace_secs(&self.global_config),
+        )?;
+        let data = &airdrop_msg.data;
+        validate_claim_data(data, project_nonce, &self.recipient.key(), &self.project, &self.mint)?;
+
+        if is_program_account(&self.nullifier) {
+            // Only a nonce this instruction claimed carries a record of the grant it paid
+            let claimed = IdempotentClaim::try_deserialize(&mut &self.nullifier.try_borrow_data()?[..])
+                .map_err(|_| AirdropError::InvalidNullifier)?;
+            require!(
+                claimed.matches(nonce, &self.recipient.key(), &self.mint.key(), data.amount),
+                AirdropError::ClaimRecordMismatch
+            );
+
+            emit_event!(self, ClaimAlreadyProcessed {
+                project: self.project.key(),
+                recipient: self.recipient.key(),
+                nonce,
+            });
+            return Ok(());
+        }
+
+        let clock = Clock::get()?;
+        self.project.check_claim_window(clock.unix_timestamp)?;
+
+        self.init_nullifier(nonce, data.amount, bump)?;
+
+        let mut settlement = ClaimSettlement {
+            global_config: &mut self.global_config,
+            protocol_stats: &mut self.protocol_stats,
+            project: &mut self.project,
+            recipient_claim_state: &mut self.recipient_claim_state,
+            recipient: self.recipient.key(),
+            mint: &self.mint,
+            token_vault: &mut self.token_vault,
+            fee_vault: &self.fee_vault,
+            fee_override_token_account: self.fee_override_token_account.as_deref(),
+            destination: self.recipient_token_account.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            #[cfg(feature = "event-cpi")]
+            event_authority: self.event_authority.to_account_info(),
+        };
+        let amount = settlement.record_grant(data.phase, data.tier, data.amount, &clock)?;
+        settlement.pay_out(project_nonce, amount, 1, remaining_accounts)
+    }
+
+    /// Creates the nonce's nullifier PDA, marking the grant as claimed and recording what was
+    /// paid for later retries; `bump` is the canonical bump Anchor derived for the nullifier
+    fn init_nullifier(&self, nonce: u64, amount: u64, bump: u8) -> Result<()> {
+        let project_key = self.project.key();
+        let nonce_bytes = nonce.to_le_bytes();
+
+        create_pda_account(
+            &self.payer.to_account_info(),
+            &self.nullifier.to_account_info(),
+            &self.system_program.to_account_info(),
+            IdempotentClaim::DISCRIMINATOR.len() + IdempotentClaim::INIT_SPACE,
+            &crate::ID,
+            &[
+                CLAIM_NULLIFIER_SEED_PREFIX,
+                project_key.as_ref(),
+                nonce_bytes.as_ref(),
+                &[bump],
+            ],
+        )?;
+
+        let mut data = self.nullifier.try_borrow_mut_data()?;
+        IdempotentClaim {
+            nonce,
+            recipient: self.recipient.key(),
+            mint: self.mint.key(),
+            amount,
+        }
+        .try_serialize(&mut &mut data[..])
+    }
+}
@@ -0,0 +1,307 @@
+use super::claim::{
+    close_wsol_into_project, validate_claim_data, validate_claim_signers, ClaimSettlement,
+};
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{sysvar::instructions as ix_sysvar, sysvar::SysvarId};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use borsh::BorshDeserialize;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64, nonce: u64)]
+pub struct ClaimIntoPool<'info> {
+    /// The account paying for the nullifier and the claim accounting, so a relayer can cover
+    /// the costs of a recipient without SOL
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The recipient of the airdrop, who receives the pool tokens
+    /// CHECK: Needs no signature; the distributors' signed message authorizes the claim
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The global config PDA containing the distributor public keys
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        constraint = !global_config.paused @ AirdropError::ProgramPaused
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The protocol-wide statistics PDA
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// The project PDA from which SOL will be claimed
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        constraint = !project.frozen_by_admin @ AirdropError::ProjectFrozen,
+        constraint = !project.paused @ AirdropError::ProjectPaused
+    )]
+    pub project: Account<'info, Project>,
+
+    /// Nullifier account to prevent reuse of the nonce
+    /// If this account already exists, the transaction will fail, preventing replay attacks
+    #[account(
+        init,
+        payer = payer,
+        space = ClaimNullifier::DISCRIMINATOR.len() + ClaimNullifier::INIT_SPACE,
+        seeds = [
+            CLAIM_NULLIFIER_SEED_PREFIX,
+            project.key().as_ref(),
+            nonce.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub nullifier: Account<'info, ClaimNullifier>,
+
+    /// The recipient's cumulative claim accounting for this project
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RecipientClaimState::DISCRIMINATOR.len() + RecipientClaimState::INIT_SPACE,
+        seeds = [
+            RECIPIENT_CLAIM_STATE_SEED_PREFIX,
+            project.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump
+    )]
+    pub recipient_claim_state: Account<'info, RecipientClaimState>,
+
+    /// The recipient's denylist entry, which must not exist
+    /// CHECK: Only checked for emptiness; any data at this PDA means the recipient is denylisted
+    #[account(
+        seeds = [DENYLIST_SEED_PREFIX, recipient.key().as_ref()],
+        bump,
+        constraint = !is_program_account(&denylist_entry) @ AirdropError::RecipientDenylisted
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    /// The wrapped SOL mint the project distributes
+    #[account(constraint = is_native_mint(&mint.key()) @ AirdropError::InvalidStakePool)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The project's token vault PDA (source of tokens)
+    #[account(
+        mut,
+        seeds = [TOKEN_VAULT_SEED_PREFIX, project.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token account owned by the global config PDA collecting protocol fees for this mint
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = global_config,
+        associated_token::token_program = token_program
+    )]
+    pub fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The project fee treasury's token account, required when the project has a fee override
+    #[account(mut)]
+    pub fee_override_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// A temporary wrapped SOL account the claim is paid into and then closed, releasing the
+    /// claimed lamports to deposit
+    #[account(
+        init,
+        payer = payer,
+        seeds = [WSOL_UNWRAP_SEED_PREFIX, project.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
+    )]
+    pub wsol_unwrap_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The system-owned PDA the claimed lamports are deposited into the pool from
+    /// CHECK: Holds no data; only signs as the source of the deposit
+    #[account(
+        mut,
+        seeds = [POOL_DEPOSIT_SEED_PREFIX, project.key().as_ref()],
+        bump
+    )]
+    pub pool_deposit: UncheckedAccount<'info>,
+
+    /// The project's stake pool
+    /// CHECK: Must be the project's configured pool, owned by the stake pool program
+    #[account(
+        mut,
+        owner = SPL_STAKE_POOL_PROGRAM_ID @ AirdropError::InvalidStakePool,
+        constraint = project.stake_pool == Some(stake_pool.key()) @ AirdropError::InvalidStakePool
+    )]
+    pub stake_pool: UncheckedAccount<'info>,
+
+    /// The stake pool's withdraw authority
+    /// CHECK: Validated by the stake pool program
+    pub stake_pool_withdraw_authority: UncheckedAccount<'info>,
+
+    /// The stake pool's reserve stake account
+    /// CHECK: Validated by the stake pool program
+    #[account(mut)]
+    pub reserve_stake: UncheckedAccount<'info>,
+
+    /// The stake pool manager's fee account
+    /// CHECK: Validated by the stake pool program
+    #[account(mut)]
+    pub manager_fee_account: UncheckedAccount<'info>,
+
+    /// The stake pool's token mint
+    /// CHECK: Validated by the stake pool program
+    #[account(mut)]
+    pub pool_mint: UncheckedAccount<'info>,
+
+    /// The recipient's pool token account, receiving the minted pool tokens
+    #[account(
+        mut,
+        constraint = pool_token_account.owner == recipient.key() @ AirdropError::InvalidStakePool,
+        constraint = pool_token_account.mint == pool_mint.key() @ AirdropError::InvalidStakePool
+    )]
+    pub pool_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The sysvar containing the full transaction's instructions
+    /// CHECK: Validated by requiring its well-known address
+    #[account(address = ix_sysvar::Instructions::id())]
+    pub instruction_sysvar: AccountInfo<'info>,
+
+    /// The SPL stake pool program
+    /// CHECK: Validated by requiring its well-known address
+    #[account(address = SPL_STAKE_POOL_PROGRAM_ID)]
+    pub stake_pool_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    /// The token program of both wrapped SOL and the pool mint
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> ClaimIntoPool<'info> {
+    /// Claims a wrapped SOL grant signed by the distributors (immediately preceding this
+    /// instruction) as a deposit into the project's stake pool, minting the pool tokens to
+    /// the recipient
+    pub fn claim_into_pool(&mut self, project_nonce: u64, nonce: u64) -> Result<()> {
+        let pool_tokens_before = self.pool_token_account.amount;
+
+        let (key_type, signers, message) = verify_distributor_signature(&self.instruction_sysvar)?;
+        validate_claim_signers(key_type, &signers, &self.global_config, &self.project)?;
+
+        let airdrop_msg = decode_airdrop_message(&message)?;
+        validate_message_domain(
+            &airdrop_msg.domain,
+            MessageKind::Claim,
+            nonce,
+            &self.global_config.genesis_hash,
+            self.project.deadline_grace_secs(&self.global_config),
+        )?;
+
+        let clock = Clock::get()?;
+        self.project.check_claim_window(clock.unix_timestamp)?;
+
+        validate_claim_data(
+            &airdrop_msg.data,
+            project_nonce,
+            &self.recipient.key(),
+            &self.project,
+            &self.mint,
+        )?;
+
+        // If this nonce was already used, the nullifier's init constraint would have failed
+        self.nullifier.set_inner(ClaimNullifier { nonce });
+
+        let mut settlement = ClaimSettlement {
+            global_config: &mut self.global_config,
+            protocol_stats: &mut self.protocol_stats,
+            project: &mut self.project,
+            recipient_claim_state: &mut self.recipient_claim_state,
+            recipient: self.recipient.key(),
+            mint: &self.mint,
+            token_vault: &mut self.token_vault,
+            fee_vault: &self.fee_vault,
+            fee_override_token_account: self.fee_override_token_account.as_deref(),
+            destination: self.wsol_unwrap_account.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            #[cfg(feature = "event-cpi")]
+            event_authority: self.event_authority.to_account_info(),
+        };
+        let amount = settlement.record_grant(
+            airdrop_msg.data.phase,
+            airdrop_msg.data.tier,
+            airdrop_msg.data.amount,
+            &clock,
+        )?;
+        settlement.pay_out(project_nonce, amount, 1, &[])?;
+
+        // Unwrap the claim into the project, refunding the temporary account's rent
+        let project_info = self.project.to_account_info();
+        let (deposited, rent) = close_wsol_into_project(
+            self.token_program.to_account_info(),
+            project_info.clone(),
+            &mut self.wsol_unwrap_account,
+            project_nonce,
+        )?;
+        transfer_native(&project_info, &self.payer.to_account_info(), rent, None)?;
+
+        self.deposit(deposited)?;
+
+        emit_event!(self, ClaimPooled {
+            project: self.project.key(),
+            recipient: self.recipient.key(),
+            stake_pool: self.stake_pool.key(),
+            pool_token_account: self.pool_token_account.key(),
+            nonce,
+            amount: deposited,
+            pool_tokens: self.pool_token_account.amount - pool_tokens_before,
+        });
+
+        Ok(())
+    }
+
+    /// Moves `lamports` from the project to the pool deposit PDA, which deposits them into the
+    /// stake pool on the recipient's behalf
+    fn deposit(&mut self, lamports: u64) -> Result<()> {
+        let project_key = self.project.key();
+        let pool_deposit_info = self.pool_deposit.to_account_info();
+        transfer_native(&self.project.to_account_info(), &pool_deposit_info, lamports, None)?;
+
+        let (_, pool_deposit_bump) = Pubkey::find_program_address(
+            &[POOL_DEPOSIT_SEED_PREFIX, project_key.as_ref()],
+            &crate::ID,
+        );
+        deposit_sol(
+            self.stake_pool_program.to_account_info(),
+            self.stake_pool.to_account_info(),
+            self.stake_pool_withdraw_authority.to_account_info(),
+            self.reserve_stake.to_account_info(),
+            pool_deposit_info,
+            self.pool_token_account.to_account_info(),
+            self.manager_fee_account.to_account_info(),
+            self.pool_mint.to_account_info(),
+            self.system_program.to_account_info(),
+            self.token_program.to_account_info(),
+            lamports,
+            &[&[
+                POOL_DEPOSIT_SEED_PREFIX,
+                project_key.as_ref(),
+                &[pool_deposit_bump],
+            ]],
+        )?;
+
+        self.pool_token_account.reload()
+    }
+}
@@ -0,0 +1,236 @@
+use super::claim::{validate_claim_signers, validate_grant_fields, ClaimSettlement};
+use crate::{constants::*, errors::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{sysvar::instructions as ix_sysvar, sysvar::SysvarId};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use borsh::BorshDeserialize;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64, nonce: u64)]
+pub struct ClaimVesting<'info> {
+    /// The account paying for the nullifier, the claim accounting and any token accounts
+    /// created, so a relayer can cover the costs of a recipient without SOL
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The recipient of the airdrop (must match the recipient in the signed message)
+    /// CHECK: Needs no signature; the distributors' signed message authorizes the claim
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The global config PDA containing the distributor public keys
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        constraint = !global_config.paused @ AirdropError::ProgramPaused
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The protocol-wide statistics PDA
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// The project PDA from which tokens will be claimed
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        constraint = !project.frozen_by_admin @ AirdropError::ProjectFrozen,
+        constraint = !project.paused @ AirdropError::ProjectPaused
+    )]
+    pub project: Account<'info, Project>,
+
+    /// The vesting allocation, created on the first claim at the grant's nullifier address
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = Allocation::DISCRIMINATOR.len() + Allocation::INIT_SPACE,
+        seeds = [
+            CLAIM_NULLIFIER_SEED_PREFIX,
+            project.key().as_ref(),
+            nonce.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub allocation: Account<'info, Allocation>,
+
+    /// The recipient's cumulative claim accounting for this project
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RecipientClaimState::DISCRIMINATOR.len() + RecipientClaimState::INIT_SPACE,
+        seeds = [
+            RECIPIENT_CLAIM_STATE_SEED_PREFIX,
+            project.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump
+    )]
+    pub recipient_claim_state: Account<'info, RecipientClaimState>,
+
+    /// The recipient's denylist entry, which must not exist
+    /// CHECK: Only checked for emptiness; any data at this PDA means the recipient is denylisted
+    #[account(
+        seeds = [DENYLIST_SEED_PREFIX, recipient.key().as_ref()],
+        bump,
+        constraint = !is_program_account(&denylist_entry) @ AirdropError::RecipientDenylisted
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    /// The mint of the SPL token being distributed, written when the project mints on claim
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The project's token vault PDA (source of tokens)
+    #[account(
+        mut,
+        seeds = [TOKEN_VAULT_SEED_PREFIX, project.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token account owned by the global config PDA collecting protocol fees for this mint
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = global_config,
+        associated_token::token_program = token_program
+    )]
+    pub fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The project fee treasury's token account, required when the project has a fee override
+    #[account(mut)]
+    pub fee_override_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The recipient's token account (destination of tokens)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The sysvar containing the full transaction's instructions
+    /// CHECK: Validated by requiring its well-known address
+    #[account(address = ix_sysvar::Instructions::id())]
+    pub instruction_sysvar: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> ClaimVesting<'info> {
+    /// Releases the vested portion of a vesting grant to the recipient.
+    ///
+    /// The first claim verifies the distributors' signed message (immediately preceding this
+    /// instruction) and records the allocation and its schedule, phase bonus included. Later
+    /// claims need no signature and release whatever has vested since, even after the
+    /// project's claim window or the message deadline has passed.
+    ///
+    /// `remaining_accounts` carries any extra accounts a Token-2022 transfer hook needs.
+    pub fn claim_vesting(
+        &mut self,
+        project_nonce: u64,
+        nonce: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        if self.allocation.total == 0 {
+            self.record_allocation(project_nonce, nonce, &clock)?;
+        }
+
+        require!(
+            self.allocation.is_vesting() && self.allocation.recipient == self.recipient.key(),
+            AirdropError::AllocationMismatch
+        );
+        let vested = self.allocation.vested_amount(clock.unix_timestamp);
+        let release = vested.saturating_sub(self.allocation.claimed_so_far);
+        require!(release > 0, AirdropError::NothingVested);
+        self.allocation.claimed_so_far = vested;
+        self.project.outstanding_vesting = self.project.outstanding_vesting.saturating_sub(release);
+
+        let mut settlement = ClaimSettlement {
+            global_config: &mut self.global_config,
+            protocol_stats: &mut self.protocol_stats,
+            project: &mut self.project,
+            recipient_claim_state: &mut self.recipient_claim_state,
+            recipient: self.recipient.key(),
+            mint: &self.mint,
+            token_vault: &mut self.token_vault,
+            fee_vault: &self.fee_vault,
+            fee_override_token_account: self.fee_override_token_account.as_deref(),
+            destination: self.recipient_token_account.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            #[cfg(feature = "event-cpi")]
+            event_authority: self.event_authority.to_account_info(),
+        };
+        settlement.record_amount(release, clock.slot)?;
+        settlement.pay_out(project_nonce, release, 1, remaining_accounts)
+    }
+
+    /// Verifies the signed vesting grant and records it as the allocation
+    fn record_allocation(&mut self, project_nonce: u64, nonce: u64, clock: &Clock) -> Result<()> {
+        let (key_type, signers, message) = verify_distributor_signature(&self.instruction_sysvar)?;
+        validate_claim_signers(key_type, &signers, &self.global_config, &self.project)?;
+
+        let airdrop_msg = decode_airdrop_message(&message)?;
+        validate_message_domain(
+            &airdrop_msg.domain,
+            MessageKind::Claim,
+            nonce,
+            &self.global_config.genesis_hash,
+            self.project.deadline_grace_secs(&self.global_config),
+        )?;
+        self.project.check_claim_window(clock.unix_timestamp)?;
+
+        let data = &airdrop_msg.data;
+        validate_grant_fields(data, project_nonce, &self.recipient.key(), &self.project, &self.mint)?;
+        let (Some(cliff_ts), Some(vest_end_ts)) = (data.cliff_ts, data.vest_end_ts) else {
+            return err!(AirdropError::InvalidVestingSchedule);
+        };
+        Allocation::validate_schedule(cliff_ts, vest_end_ts)?;
+
+        // The phase and tier are fixed when the grant is accepted, so their bonuses are folded
+        // into the total
+        let total = self
+            .project
+            .apply_phase(data.phase, clock.unix_timestamp, data.amount)?;
+        let total = self
+            .project
+            .apply_tier(data.tier, clock.unix_timestamp, total)?;
+        require!(total > 0, AirdropError::ClaimExceedsAllocation);
+
+        self.allocation.set_inner(Allocation {
+            project: self.project.key(),
+            recipient: self.recipient.key(),
+            nonce,
+            total,
+            claimed_so_far: 0,
+            cliff_ts,
+            vest_end_ts,
+        });
+        self.project.outstanding_vesting = self
+            .project
+            .outstanding_vesting
+            .checked_add(total)
+            .ok_or(AirdropError::MathOverflow)?;
+
+        Ok(())
+    }
+}
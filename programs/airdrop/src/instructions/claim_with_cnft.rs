@@ -0,0 +1,247 @@
+use super::claim::{reject_claim_only_settings, ClaimSettlement};
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+/// A Bubblegum leaf as read from an indexer, claimed with by its owner
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedNftLeaf {
+    /// The tree root the proof was built against
+    pub root: [u8; 32],
+
+    /// The hash of the compressed NFT's metadata
+    pub data_hash: [u8; 32],
+
+    /// The hash of the compressed NFT's creators
+    pub creator_hash: [u8; 32],
+
+    /// The leaf's delegate, which is the owner when none is set
+    pub delegate: Pubkey,
+
+    /// The leaf nonce the asset ID is derived from
+    pub nonce: u64,
+
+    /// The leaf's index in the tree
+    pub index: u32,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64, leaf: CompressedNftLeaf)]
+pub struct ClaimWithCnft<'info> {
+    /// The account paying for the NFT claim record, the claim accounting and any token
+    /// accounts created
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The compressed NFT's owner, who receives the claim
+    /// CHECK: Needs no signature; owning the leaf is what makes the claim eligible
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The global config PDA
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        constraint = !global_config.paused @ AirdropError::ProgramPaused
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The protocol-wide statistics PDA
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// The project PDA from which tokens will be claimed
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        constraint = !project.frozen_by_admin @ AirdropError::ProjectFrozen,
+        constraint = !project.paused @ AirdropError::ProjectPaused
+    )]
+    pub project: Account<'info, Project>,
+
+    /// Records the compressed NFT as claimed; its init fails if it was already claimed with
+    #[account(
+        init,
+        payer = payer,
+        space = NftClaim::DISCRIMINATOR.len() + NftClaim::INIT_SPACE,
+        seeds = [
+            NFT_CLAIM_SEED_PREFIX,
+            project.key().as_ref(),
+            compressed_asset_id(&merkle_tree.key(), leaf.nonce).as_ref(),
+        ],
+        bump
+    )]
+    pub nft_claim: Account<'info, NftClaim>,
+
+    /// The recipient's cumulative claim accounting for this project
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RecipientClaimState::DISCRIMINATOR.len() + RecipientClaimState::INIT_SPACE,
+        seeds = [
+            RECIPIENT_CLAIM_STATE_SEED_PREFIX,
+            project.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump
+    )]
+    pub recipient_claim_state: Account<'info, RecipientClaimState>,
+
+    /// The recipient's denylist entry, which must not exist
+    /// CHECK: Only checked for emptiness; any data at this PDA means the recipient is denylisted
+    #[account(
+        seeds = [DENYLIST_SEED_PREFIX, recipient.key().as_ref()],
+        bump,
+        constraint = !is_program_account(&denylist_entry) @ AirdropError::RecipientDenylisted
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    /// The mint of the SPL token being distributed, written when the project mints on claim
+    #[account(mut, address = project.mint @ AirdropError::MintMismatch)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The project's token vault PDA (source of tokens)
+    #[account(
+        mut,
+        seeds = [TOKEN_VAULT_SEED_PREFIX, project.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token account owned by the global config PDA collecting protocol fees for this mint
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = global_config,
+        associated_token::token_program = token_program
+    )]
+    pub fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The project fee treasury's token account, required when the project has a fee override
+    #[account(mut)]
+    pub fee_override_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The recipient's token account (destination of tokens)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The project's Bubblegum merkle tree
+    /// CHECK: Must be the project's gating tree; the account compression program validates it
+    #[account(
+        constraint = project.compressed_nft_gate.is_some_and(|gate| gate.merkle_tree == merkle_tree.key()) @ AirdropError::InvalidCompressedNft
+    )]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// The SPL account compression program
+    /// CHECK: Validated by requiring its well-known address
+    #[account(address = SPL_ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> ClaimWithCnft<'info> {
+    /// Pays the project's fixed amount to the owner of a compressed NFT in the project's
+    /// Bubblegum tree, proven by its leaf and a merkle proof verified through the account
+    /// compression program. Each compressed NFT claims once.
+    ///
+    /// `remaining_accounts` starts with the `proof_len` proof nodes of the leaf, followed by
+    /// any extra accounts a Token-2022 transfer hook needs.
+    pub fn claim_with_cnft(
+        &mut self,
+        project_nonce: u64,
+        leaf: CompressedNftLeaf,
+        proof_len: u8,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let gate = self
+            .project
+            .compressed_nft_gate
+            .ok_or(AirdropError::InvalidCompressedNft)?;
+        reject_claim_only_settings(&self.project)?;
+
+        require!(
+            remaining_accounts.len() >= proof_len as usize,
+            AirdropError::InvalidCompressedNft
+        );
+        let (proof, hook_accounts) = remaining_accounts.split_at(proof_len as usize);
+
+        // The leaf only hashes to a node of the tree if the recipient owns the compressed NFT
+        let asset_id = compressed_asset_id(&self.merkle_tree.key(), leaf.nonce);
+        let leaf_hash = compressed_leaf_hash(
+            &asset_id,
+            &self.recipient.key(),
+            &leaf.delegate,
+            leaf.nonce,
+            &leaf.data_hash,
+            &leaf.creator_hash,
+        );
+        verify_leaf(
+            self.compression_program.to_account_info(),
+            self.merkle_tree.to_account_info(),
+            proof,
+            leaf.root,
+            leaf_hash,
+            leaf.index,
+        )?;
+
+        let clock = Clock::get()?;
+        self.project.check_claim_window(clock.unix_timestamp)?;
+
+        // If this compressed NFT was already claimed with, the record's init constraint would
+        // have failed
+        self.nft_claim.set_inner(NftClaim {
+            project: self.project.key(),
+            asset: asset_id,
+            recipient: self.recipient.key(),
+        });
+
+        let mut settlement = ClaimSettlement {
+            global_config: &mut self.global_config,
+            protocol_stats: &mut self.protocol_stats,
+            project: &mut self.project,
+            recipient_claim_state: &mut self.recipient_claim_state,
+            recipient: self.recipient.key(),
+            mint: &self.mint,
+            token_vault: &mut self.token_vault,
+            fee_vault: &self.fee_vault,
+            fee_override_token_account: self.fee_override_token_account.as_deref(),
+            destination: self.recipient_token_account.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            #[cfg(feature = "event-cpi")]
+            event_authority: self.event_authority.to_account_info(),
+        };
+        settlement.record_amount(gate.amount, clock.slot)?;
+        settlement.pay_out(project_nonce, gate.amount, 1, hook_accounts)?;
+
+        emit_event!(self, CompressedNftClaimed {
+            project: self.project.key(),
+            recipient: self.recipient.key(),
+            asset_id,
+            amount: gate.amount,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,207 @@
+use super::claim::{reject_claim_only_settings, ClaimSettlement};
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct ClaimWithNft<'info> {
+    /// The account paying for the NFT claim record, the claim accounting and any token
+    /// accounts created
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The NFT holder, who receives the claim
+    /// CHECK: Needs no signature; holding the NFT is what makes the claim eligible
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The global config PDA
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        constraint = !global_config.paused @ AirdropError::ProgramPaused
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The protocol-wide statistics PDA
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// The project PDA from which tokens will be claimed
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        constraint = !project.frozen_by_admin @ AirdropError::ProjectFrozen,
+        constraint = !project.paused @ AirdropError::ProjectPaused
+    )]
+    pub project: Account<'info, Project>,
+
+    /// Records the NFT as claimed; its init fails if the NFT was already claimed with
+    #[account(
+        init,
+        payer = payer,
+        space = NftClaim::DISCRIMINATOR.len() + NftClaim::INIT_SPACE,
+        seeds = [
+            NFT_CLAIM_SEED_PREFIX,
+            project.key().as_ref(),
+            nft_mint.key().as_ref(),
+        ],
+        bump
+    )]
+    pub nft_claim: Account<'info, NftClaim>,
+
+    /// The recipient's cumulative claim accounting for this project
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RecipientClaimState::DISCRIMINATOR.len() + RecipientClaimState::INIT_SPACE,
+        seeds = [
+            RECIPIENT_CLAIM_STATE_SEED_PREFIX,
+            project.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump
+    )]
+    pub recipient_claim_state: Account<'info, RecipientClaimState>,
+
+    /// The recipient's denylist entry, which must not exist
+    /// CHECK: Only checked for emptiness; any data at this PDA means the recipient is denylisted
+    #[account(
+        seeds = [DENYLIST_SEED_PREFIX, recipient.key().as_ref()],
+        bump,
+        constraint = !is_program_account(&denylist_entry) @ AirdropError::RecipientDenylisted
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    /// The mint of the SPL token being distributed, written when the project mints on claim
+    #[account(mut, address = project.mint @ AirdropError::MintMismatch)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The project's token vault PDA (source of tokens)
+    #[account(
+        mut,
+        seeds = [TOKEN_VAULT_SEED_PREFIX, project.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token account owned by the global config PDA collecting protocol fees for this mint
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = global_config,
+        associated_token::token_program = token_program
+    )]
+    pub fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The project fee treasury's token account, required when the project has a fee override
+    #[account(mut)]
+    pub fee_override_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The recipient's token account (destination of tokens)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of the recipient's NFT
+    #[account(
+        constraint = nft_mint.decimals == 0 && nft_mint.supply == 1 @ AirdropError::InvalidCollectionNft
+    )]
+    pub nft_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The recipient's token account holding the NFT
+    #[account(
+        token::mint = nft_mint,
+        token::authority = recipient,
+        constraint = nft_token_account.amount == 1 @ AirdropError::InvalidCollectionNft
+    )]
+    pub nft_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The NFT's Token Metadata account, proving its collection membership
+    /// CHECK: Validated by `verified_collection` against the Token Metadata program and the NFT mint
+    pub nft_metadata: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> ClaimWithNft<'info> {
+    /// Pays the project's fixed amount to the holder of an NFT of the project's verified
+    /// collection. Each NFT claims once, so holder airdrops need no distributor signatures.
+    ///
+    /// `remaining_accounts` carries any extra accounts a Token-2022 transfer hook needs.
+    pub fn claim_with_nft(
+        &mut self,
+        project_nonce: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let collection_gate = self
+            .project
+            .collection_gate
+            .ok_or(AirdropError::InvalidCollectionNft)?;
+        reject_claim_only_settings(&self.project)?;
+
+        require!(
+            verified_collection(&self.nft_metadata, &self.nft_mint.key())
+                == Some(collection_gate.collection),
+            AirdropError::InvalidCollectionNft
+        );
+
+        let clock = Clock::get()?;
+        self.project.check_claim_window(clock.unix_timestamp)?;
+
+        // If this NFT was already claimed with, the record's init constraint would have failed
+        self.nft_claim.set_inner(NftClaim {
+            project: self.project.key(),
+            asset: self.nft_mint.key(),
+            recipient: self.recipient.key(),
+        });
+
+        let mut settlement = ClaimSettlement {
+            global_config: &mut self.global_config,
+            protocol_stats: &mut self.protocol_stats,
+            project: &mut self.project,
+            recipient_claim_state: &mut self.recipient_claim_state,
+            recipient: self.recipient.key(),
+            mint: &self.mint,
+            token_vault: &mut self.token_vault,
+            fee_vault: &self.fee_vault,
+            fee_override_token_account: self.fee_override_token_account.as_deref(),
+            destination: self.recipient_token_account.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            #[cfg(feature = "event-cpi")]
+            event_authority: self.event_authority.to_account_info(),
+        };
+        settlement.record_amount(collection_gate.amount, clock.slot)?;
+        settlement.pay_out(project_nonce, collection_gate.amount, 1, remaining_accounts)?;
+
+        emit_event!(self, NftClaimed {
+            project: self.project.key(),
+            recipient: self.recipient.key(),
+            nft_mint: self.nft_mint.key(),
+            amount: collection_gate.amount,
+        });
+
+        Ok(())
+    }
+}
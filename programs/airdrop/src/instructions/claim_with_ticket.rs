@@ -0,0 +1,187 @@
+use super::claim::{reject_claim_only_settings, ClaimSettlement};
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct ClaimWithTicket<'info> {
+    /// The account paying for the claim accounting and any token accounts created
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The ticket holder, who signs to burn the ticket and receives the claim
+    pub recipient: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        constraint = !global_config.paused @ AirdropError::ProgramPaused
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The protocol-wide statistics PDA
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// The project PDA from which tokens will be claimed
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        constraint = !project.frozen_by_admin @ AirdropError::ProjectFrozen,
+        constraint = !project.paused @ AirdropError::ProjectPaused
+    )]
+    pub project: Account<'info, Project>,
+
+    /// The recipient's cumulative claim accounting for this project
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RecipientClaimState::DISCRIMINATOR.len() + RecipientClaimState::INIT_SPACE,
+        seeds = [
+            RECIPIENT_CLAIM_STATE_SEED_PREFIX,
+            project.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump
+    )]
+    pub recipient_claim_state: Account<'info, RecipientClaimState>,
+
+    /// The recipient's denylist entry, which must not exist
+    /// CHECK: Only checked for emptiness; any data at this PDA means the recipient is denylisted
+    #[account(
+        seeds = [DENYLIST_SEED_PREFIX, recipient.key().as_ref()],
+        bump,
+        constraint = !is_program_account(&denylist_entry) @ AirdropError::RecipientDenylisted
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    /// The mint of the SPL token being distributed, written when the project mints on claim
+    #[account(mut, address = project.mint @ AirdropError::MintMismatch)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The project's token vault PDA (source of tokens)
+    #[account(
+        mut,
+        seeds = [TOKEN_VAULT_SEED_PREFIX, project.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token account owned by the global config PDA collecting protocol fees for this mint
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = global_config,
+        associated_token::token_program = token_program
+    )]
+    pub fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The project fee treasury's token account, required when the project has a fee override
+    #[account(mut)]
+    pub fee_override_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The recipient's token account (destination of tokens)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The project's ticket mint
+    #[account(
+        mut,
+        constraint = project.ticket.is_some_and(|ticket| ticket.mint == ticket_mint.key()) @ AirdropError::InvalidTicket
+    )]
+    pub ticket_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The recipient's ticket account, from which one ticket is burned
+    #[account(
+        mut,
+        token::mint = ticket_mint,
+        token::authority = recipient,
+        token::token_program = ticket_token_program
+    )]
+    pub ticket_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub ticket_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> ClaimWithTicket<'info> {
+    /// Redeems one ticket of the project's ticket mint for the project's fixed ticket amount.
+    /// The burned ticket is the proof of eligibility, so no distributor signature is needed.
+    ///
+    /// `remaining_accounts` carries any extra accounts a Token-2022 transfer hook needs.
+    pub fn claim_with_ticket(
+        &mut self,
+        project_nonce: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let ticket = self.project.ticket.ok_or(AirdropError::InvalidTicket)?;
+        reject_claim_only_settings(&self.project)?;
+
+        let clock = Clock::get()?;
+        self.project.check_claim_window(clock.unix_timestamp)?;
+
+        // Burn one whole ticket, which is what makes the claim single-use
+        let one_ticket = 10u64
+            .checked_pow(self.ticket_mint.decimals as u32)
+            .ok_or(AirdropError::MathOverflow)?;
+        burn_spl(
+            self.ticket_token_program.to_account_info(),
+            self.recipient.to_account_info(),
+            self.ticket_token_account.to_account_info(),
+            self.ticket_mint.to_account_info(),
+            one_ticket,
+            None,
+        )?;
+
+        let mut settlement = ClaimSettlement {
+            global_config: &mut self.global_config,
+            protocol_stats: &mut self.protocol_stats,
+            project: &mut self.project,
+            recipient_claim_state: &mut self.recipient_claim_state,
+            recipient: self.recipient.key(),
+            mint: &self.mint,
+            token_vault: &mut self.token_vault,
+            fee_vault: &self.fee_vault,
+            fee_override_token_account: self.fee_override_token_account.as_deref(),
+            destination: self.recipient_token_account.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            #[cfg(feature = "event-cpi")]
+            event_authority: self.event_authority.to_account_info(),
+        };
+        settlement.record_amount(ticket.amount, clock.slot)?;
+        settlement.pay_out(project_nonce, ticket.amount, 1, remaining_accounts)?;
+
+        emit_event!(self, TicketRedeemed {
+            project: self.project.key(),
+            recipient: self.recipient.key(),
+            ticket_mint: self.ticket_mint.key(),
+            amount: ticket.amount,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CloseGlobalConfig<'info> {
+    /// The authority that manages the configuration and receives the rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The global config PDA to be closed
+    #[account(
+        mut,
+        close = authority,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The protocol-wide statistics PDA, closed together with the global config
+    #[account(
+        mut,
+        close = authority,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+}
+
+impl<'info> CloseGlobalConfig<'info> {
+    /// Closes the global config, refusing while projects are still active unless `force` is set
+    pub fn close(&mut self, force: bool) -> Result<()> {
+        let project_count = self.global_config.project_count;
+        require!(
+            project_count == 0 || force,
+            AirdropError::ActiveProjectsRemain
+        );
+
+        emit_event!(self, GlobalConfigClosed {
+            authority: self.authority.key(),
+            project_count,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64, nonce: u64)]
+pub struct CloseNullifier<'info> {
+    /// The authority that manages the project, receiving the nullifier's rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The project PDA the nullifier belongs to
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+
+    /// The nullifier to close; allocations and streams at the same address are never closed here
+    /// CHECK: Seeds and ownership are checked here; the handler only closes a `ClaimNullifier`
+    /// or an `IdempotentClaim`
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [
+            CLAIM_NULLIFIER_SEED_PREFIX,
+            project.key().as_ref(),
+            nonce.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub nullifier: UncheckedAccount<'info>,
+}
+
+impl<'info> CloseNullifier<'info> {
+    /// Closes a claim nullifier once the project's claim window has ended, refunding its rent.
+    ///
+    /// A closed nullifier no longer blocks its signed message, so the first close seals the
+    /// claim window for good and the project can never accept claims again.
+    pub fn close_nullifier(&mut self, _project_nonce: u64, nonce: u64) -> Result<()> {
+        require!(
+            self.project.has_ended(Clock::get()?.unix_timestamp),
+            AirdropError::ClaimWindowNotEnded
+        );
+
+        let nullifier = self.nullifier.to_account_info();
+        {
+            let data = nullifier.try_borrow_data()?;
+            require!(
+                data.starts_with(ClaimNullifier::DISCRIMINATOR)
+                    || data.starts_with(IdempotentClaim::DISCRIMINATOR),
+                AirdropError::InvalidNullifier
+            );
+        }
+        close_program_account(&nullifier, &self.authority.to_account_info())?;
+
+        self.project.claim_window_sealed = true;
+
+        emit_event!(self, NullifierClosed {
+            project: self.project.key(),
+            nonce,
+        });
+
+        Ok(())
+    }
+}
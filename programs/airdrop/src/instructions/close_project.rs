@@ -0,0 +1,185 @@
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::spl_token_2022::instruction::AuthorityType,
+    token_interface::{self, Mint, SetAuthority, TokenAccount, TokenInterface},
+};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct CloseProject<'info> {
+    /// The authority that manages the project and receives the tokens and rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The global config PDA tracking the number of active projects
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The project PDA to be closed or archived
+    /// CHECK: Deserialized as a `Project` and checked in the handler; a typed account would
+    /// re-serialize the full layout on exit and overwrite the archived record
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub project: UncheckedAccount<'info>,
+
+    /// The project's metadata PDA, closed together with the project
+    #[account(
+        mut,
+        close = authority,
+        seeds = [PROJECT_METADATA_SEED_PREFIX, project.key().as_ref()],
+        bump
+    )]
+    pub project_metadata: Account<'info, ProjectMetadata>,
+
+    /// The mint of the SPL token being distributed, whose mint authority is handed back
+    /// to the project authority if the project was minting claims
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The project's token vault PDA, swept and closed
+    #[account(
+        mut,
+        seeds = [TOKEN_VAULT_SEED_PREFIX, project.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The authority's token account (destination of the remaining tokens)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = authority,
+        associated_token::token_program = token_program
+    )]
+    pub authority_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> CloseProject<'info> {
+    /// Sweeps the remaining tokens to the authority once no funder deposits are left to
+    /// refund, then closes the token vault and the project metadata, returning their rent to
+    /// the authority. A project that mints claims hands the mint authority back to its
+    /// authority. The project PDA is either closed too or, with `archive`, shrunk into an
+    /// `ArchivedProject` record.
+    ///
+    /// `remaining_accounts` carries any extra accounts a Token-2022 transfer hook needs
+    pub fn close(
+        &mut self,
+        project_nonce: u64,
+        archive: bool,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let project = Project::try_deserialize(&mut &self.project.try_borrow_data()?[..])?;
+        require!(
+            project.authority == self.authority.key(),
+            AirdropError::Unauthorized
+        );
+        require!(project.mint == self.mint.key(), AirdropError::MintMismatch);
+        require!(!project.frozen_by_admin, AirdropError::ProjectFrozen);
+        // Funders' deposits may only leave the vault through `refund_funder`
+        require!(
+            project.deposited_pool(self.token_vault.amount) == 0,
+            AirdropError::DepositsOutstanding
+        );
+        // Closing would cancel vesting allocations and streams that were already granted
+        require!(project.outstanding_vesting == 0, AirdropError::VestingOutstanding);
+        require!(project.outstanding_streamed == 0, AirdropError::StreamsOutstanding);
+        // Lockup vaults can only be released while the project exists to sign for them
+        require!(project.outstanding_locked == 0, AirdropError::LockupsOutstanding);
+
+        let remaining = self.token_vault.amount;
+
+        let nonce_bytes = project_nonce.to_le_bytes();
+        let project_bump = get_project_bump(project_nonce, &crate::ID);
+        let seeds = &[PROJECT_SEED_PREFIX, nonce_bytes.as_ref(), &[project_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        if remaining > 0 {
+            transfer_spl(
+                self.token_program.to_account_info(),
+                self.project.to_account_info(),
+                self.token_vault.to_account_info(),
+                self.mint.to_account_info(),
+                self.authority_token_account.to_account_info(),
+                remaining_accounts,
+                remaining,
+                self.mint.decimals,
+                Some(signer_seeds),
+            )?;
+        }
+
+        if project.mint_on_claim {
+            token_interface::set_authority(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    SetAuthority {
+                        current_authority: self.project.to_account_info(),
+                        account_or_mint: self.mint.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                AuthorityType::MintTokens,
+                Some(self.authority.key()),
+            )?;
+        }
+
+        close_spl_account(
+            self.token_program.to_account_info(),
+            self.project.to_account_info(),
+            self.token_vault.to_account_info(),
+            self.authority.to_account_info(),
+            Some(signer_seeds),
+        )?;
+
+        let project_info = self.project.to_account_info();
+        let authority_info = self.authority.to_account_info();
+        if archive {
+            let archived = ArchivedProject {
+                nonce: project.nonce,
+                mint: project.mint,
+                authority: project.authority,
+                total_funded: project.total_funded,
+                total_claimed: project.total_claimed,
+                claim_count: project.claim_count,
+                closed_slot: Clock::get()?.slot,
+            };
+            shrink_account(
+                &project_info,
+                &authority_info,
+                ArchivedProject::DISCRIMINATOR.len() + ArchivedProject::INIT_SPACE,
+            )?;
+            archived.try_serialize(&mut &mut project_info.try_borrow_mut_data()?[..])?;
+        } else {
+            close_program_account(&project_info, &authority_info)?;
+        }
+
+        self.global_config.project_count = self.global_config.project_count.saturating_sub(1);
+
+        emit_event!(self, ProjectClosed {
+            project: self.project.key(),
+            nonce: project_nonce,
+            authority: self.authority.key(),
+            swept_amount: remaining,
+            archived: archive,
+        });
+
+        Ok(())
+    }
+}
@@ -1,30 +1,90 @@
-use crate::{constants::*, state::*};
+use crate::{constants::*, events::*, state::*, utils::*};
 use anchor_lang::prelude::*;
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 pub struct CreateGlobalConfig<'info> {
-    /// The authority that can manage the configuration
+    /// The account paying for the global config rent
     #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The authority that can manage the configuration.
+    /// Kept separate from the payer so a multisig vault PDA only has to sign.
     pub authority: Signer<'info>,
 
     /// The global config PDA
     #[account(
         init,
-        payer = authority,
+        payer = payer,
         space = GlobalConfig::DISCRIMINATOR.len() + GlobalConfig::INIT_SPACE,
         seeds = [GLOBAL_CONFIG_SEED],
         bump
     )]
     pub global_config: Account<'info, GlobalConfig>,
 
+    /// The protocol-wide statistics PDA, created alongside the global config
+    #[account(
+        init,
+        payer = payer,
+        space = ProtocolStats::DISCRIMINATOR.len() + ProtocolStats::INIT_SPACE,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> CreateGlobalConfig<'info> {
-    pub fn create(&mut self, distributor: Pubkey) -> Result<()> {
+    pub fn create(
+        &mut self,
+        distributors: Vec<Distributor>,
+        threshold: u8,
+        required_weight: u16,
+    ) -> Result<()> {
+        validate_distributor_list(&distributors, MAX_DISTRIBUTORS)?;
+        validate_distributor_set(&distributors, threshold, required_weight)?;
+
         self.global_config.set_inner(GlobalConfig {
             authority: self.authority.key(),
-            distributor,
+            distributors,
+            threshold,
+            paused: false,
+            project_count: 0,
+            previous_distributors: Vec::new(),
+            previous_threshold: 0,
+            previous_distributors_expiry_slot: 0,
+            required_weight,
+            previous_required_weight: 0,
+            pending_authority: None,
+            version: GLOBAL_CONFIG_VERSION,
+            fee_bps: 0,
+            fee_treasury: self.authority.key(),
+            guardian: Pubkey::default(),
+            distributor_capacity: MAX_DISTRIBUTORS as u16,
+            operators: Vec::new(),
+            project_creation_mode: ProjectCreationMode::Open,
+            max_claim_amount: None,
+            max_claims_per_window: 0,
+            rate_limit_window_slots: 0,
+            window_start_slot: 0,
+            claims_this_window: 0,
+            allow_risky_mints: false,
+            deadline_grace_secs: 0,
+            genesis_hash: [0; 32],
+        });
+
+        self.protocol_stats.set_inner(ProtocolStats {
+            total_projects: 0,
+            total_claims: 0,
+            total_distributed: 0,
+        });
+
+        emit_event!(self, GlobalConfigCreated {
+            authority: self.authority.key(),
+            distributors: self.global_config.distributors.clone(),
+            threshold,
+            required_weight,
         });
 
         Ok(())
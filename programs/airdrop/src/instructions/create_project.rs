@@ -1,51 +1,199 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
-};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
 use crate::state::*;
+use crate::utils::*;
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
-#[instruction(nonce: u64)]
 pub struct CreateProject<'info> {
     /// The authority that will manage this project
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    /// The project PDA account to be created
+    /// The global config PDA, checked so projects cannot be created while paused
+    /// or by keys the project creation mode does not admit
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        constraint = !global_config.paused @ AirdropError::ProgramPaused,
+        constraint = global_config.can_create_project(
+            &authority.key(),
+            is_program_account(&creator_allowlist_entry),
+        ) @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The protocol-wide statistics PDA
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// The creator's allowlist entry, which only needs to exist in allowlist-only mode
+    /// CHECK: Only checked for existence; data at this PDA means the creator is allowlisted
+    #[account(
+        seeds = [CREATOR_ALLOWLIST_SEED_PREFIX, authority.key().as_ref()],
+        bump
+    )]
+    pub creator_allowlist_entry: UncheckedAccount<'info>,
+
+    /// The counter PDA that assigns the project's nonce
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ProjectCounter::DISCRIMINATOR.len() + ProjectCounter::INIT_SPACE,
+        seeds = [PROJECT_COUNTER_SEED],
+        bump
+    )]
+    pub project_counter: Account<'info, ProjectCounter>,
+
+    /// The project PDA account to be created, at the counter's next nonce; a nonce an
+    /// older project already holds is moved past with `skip_project_nonce`
     #[account(
         init,
         payer = authority,
         space = Project::DISCRIMINATOR.len() + Project::INIT_SPACE,
-        seeds = [PROJECT_SEED_PREFIX, nonce.to_le_bytes().as_ref()],
+        seeds = [PROJECT_SEED_PREFIX, project_counter.next_nonce().to_le_bytes().as_ref()],
         bump
     )]
     pub project: Account<'info, Project>,
 
+    /// The registry page the project is listed on, created by the page's first project
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ProjectRegistry::DISCRIMINATOR.len() + ProjectRegistry::INIT_SPACE,
+        seeds = [
+            PROJECT_REGISTRY_SEED_PREFIX,
+            ProjectRegistry::page_for(project_counter.next_nonce()).to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub project_registry: Account<'info, ProjectRegistry>,
+
+    /// The project's metadata PDA, created alongside the project
+    #[account(
+        init,
+        payer = authority,
+        space = ProjectMetadata::DISCRIMINATOR.len() + ProjectMetadata::INIT_SPACE,
+        seeds = [PROJECT_METADATA_SEED_PREFIX, project.key().as_ref()],
+        bump
+    )]
+    pub project_metadata: Account<'info, ProjectMetadata>,
+
     /// The mint of the SPL token to be distributed
-    pub mint: Account<'info, Mint>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
 
-    /// The token account owned by the project PDA
+    /// The project's token vault, a PDA owned by the project rather than a publicly derivable ATA
     #[account(
         init,
         payer = authority,
-        associated_token::mint = mint,
-        associated_token::authority = project
+        seeds = [TOKEN_VAULT_SEED_PREFIX, project.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
     )]
-    pub project_token_account: Account<'info, TokenAccount>,
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 impl<'info> CreateProject<'info> {
-    pub fn create_project(&mut self, nonce: u64) -> Result<()> {
+    /// Creates a project at the counter's next nonce, which is announced in `ProjectCreated`
+    pub fn create_project(
+        &mut self,
+        start_time: i64,
+        end_time: i64,
+        metadata: ProjectMetadataArgs,
+    ) -> Result<()> {
+        require!(
+            self.global_config.allow_risky_mints || !is_risky_mint(&self.mint.to_account_info())?,
+            AirdropError::RiskyMint
+        );
+        Project::validate_claim_window(start_time, end_time)?;
+        metadata.validate()?;
+
+        let nonce = self.project_counter.next_nonce();
+        self.project_counter.count = nonce;
+
         self.project.set_inner(Project {
             nonce,
             mint: self.mint.key(),
             authority: self.authority.key(),
+            distributors: Vec::new(),
+            version: PROJECT_VERSION,
+            total_funded: 0,
+            start_time,
+            end_time,
+            budget: 0,
+            total_claimed: 0,
+            max_per_recipient: None,
+            gross_up_transfer_fees: false,
+            frozen_by_admin: false,
+            fee_override: None,
+            managers: Vec::new(),
+            paused: false,
+            phases: Vec::new(),
+            outstanding_deposits: 0,
+            threshold: None,
+            low_balance_threshold: 0,
+            claim_count: 0,
+            decimals: self.mint.decimals,
+            claim_fee_lamports: 0,
+            claim_window_sealed: false,
+            rent_pool_lamports: 0,
+            nullifier_mode: NullifierMode::Pda,
+            tiers: Vec::new(),
+            stake_vote_account: None,
+            stake_pool: None,
+            lock_duration: 0,
+            deadline_grace_secs: None,
+            max_claim_amount: None,
+            max_claims_per_window: 0,
+            throttle_window_slots: 0,
+            recipient_must_pay: false,
+            mint_on_claim: false,
+            ticket: None,
+            collection_gate: None,
+            compressed_nft_gate: None,
+            attestation_gate: None,
+            claim_group: None,
+            outstanding_vesting: 0,
+            outstanding_streamed: 0,
+            outstanding_locked: 0,
+        });
+
+        self.project_metadata.set_inner(ProjectMetadata {
+            project: self.project.key(),
+            name: metadata.name,
+            symbol: metadata.symbol,
+            uri: metadata.uri,
+            description: metadata.description,
+        });
+
+        let registry: &mut ProjectRegistry = &mut self.project_registry;
+        registry.page = ProjectRegistry::page_for(nonce);
+        registry.entries.push(ProjectRegistryEntry {
+            project: self.project.key(),
+            nonce,
+        });
+
+        self.global_config.project_count = self.global_config.project_count.saturating_add(1);
+        self.protocol_stats.total_projects = self.protocol_stats.total_projects.saturating_add(1);
+
+        emit_event!(self, ProjectCreated {
+            project: self.project.key(),
+            nonce,
+            mint: self.mint.key(),
+            authority: self.authority.key(),
         });
 
         Ok(())
@@ -0,0 +1,139 @@
+use super::claim::{validate_claim_signers, validate_grant_fields};
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{sysvar::instructions as ix_sysvar, sysvar::SysvarId};
+use anchor_spl::token_interface::Mint;
+use borsh::BorshDeserialize;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64, nonce: u64)]
+pub struct CreateStream<'info> {
+    /// The account paying for the stream
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The recipient of the stream (must match the recipient in the signed message)
+    /// CHECK: Needs no signature; the distributors' signed message authorizes the stream
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The global config PDA containing the distributor public keys
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        constraint = !global_config.paused @ AirdropError::ProgramPaused
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The project PDA the stream is paid from
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        constraint = !project.frozen_by_admin @ AirdropError::ProjectFrozen,
+        constraint = !project.paused @ AirdropError::ProjectPaused
+    )]
+    pub project: Account<'info, Project>,
+
+    /// The stream, created at the grant's nullifier address
+    #[account(
+        init,
+        payer = payer,
+        space = Stream::DISCRIMINATOR.len() + Stream::INIT_SPACE,
+        seeds = [
+            CLAIM_NULLIFIER_SEED_PREFIX,
+            project.key().as_ref(),
+            nonce.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub stream: Account<'info, Stream>,
+
+    /// The recipient's denylist entry, which must not exist
+    /// CHECK: Only checked for emptiness; any data at this PDA means the recipient is denylisted
+    #[account(
+        seeds = [DENYLIST_SEED_PREFIX, recipient.key().as_ref()],
+        bump,
+        constraint = !is_program_account(&denylist_entry) @ AirdropError::RecipientDenylisted
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    /// The mint of the SPL token being distributed
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The sysvar containing the full transaction's instructions
+    /// CHECK: Validated by requiring its well-known address
+    #[account(address = ix_sysvar::Instructions::id())]
+    pub instruction_sysvar: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateStream<'info> {
+    /// Turns a signed scheduled grant into a stream that accrues every second from the
+    /// grant's start (`cliff_ts`) to its end (`vest_end_ts`). The distributors' signed message
+    /// must immediately precede this instruction. Nothing is transferred until `withdraw_stream`.
+    pub fn create_stream(&mut self, project_nonce: u64, nonce: u64) -> Result<()> {
+        let (key_type, signers, message) = verify_distributor_signature(&self.instruction_sysvar)?;
+        validate_claim_signers(key_type, &signers, &self.global_config, &self.project)?;
+
+        let airdrop_msg = decode_airdrop_message(&message)?;
+        validate_message_domain(
+            &airdrop_msg.domain,
+            MessageKind::Claim,
+            nonce,
+            &self.global_config.genesis_hash,
+            self.project.deadline_grace_secs(&self.global_config),
+        )?;
+
+        let clock = Clock::get()?;
+        self.project.check_claim_window(clock.unix_timestamp)?;
+
+        let data = &airdrop_msg.data;
+        validate_grant_fields(data, project_nonce, &self.recipient.key(), &self.project, &self.mint)?;
+        let (Some(start_ts), Some(end_ts)) = (data.cliff_ts, data.vest_end_ts) else {
+            return err!(AirdropError::InvalidVestingSchedule);
+        };
+        Allocation::validate_schedule(start_ts, end_ts)?;
+
+        // The phase and tier are fixed when the grant is accepted, so their bonuses are folded
+        // into the total
+        let total = self
+            .project
+            .apply_phase(data.phase, clock.unix_timestamp, data.amount)?;
+        let total = self
+            .project
+            .apply_tier(data.tier, clock.unix_timestamp, total)?;
+        require!(total > 0, AirdropError::ClaimExceedsAllocation);
+        let rate_per_second = Stream::rate_for(total, start_ts, end_ts)?;
+
+        self.stream.set_inner(Stream {
+            project: self.project.key(),
+            recipient: self.recipient.key(),
+            nonce,
+            total,
+            withdrawn: 0,
+            rate_per_second,
+            start_ts,
+            end_ts,
+            last_withdrawn_ts: start_ts,
+        });
+        self.project.outstanding_streamed = self
+            .project
+            .outstanding_streamed
+            .checked_add(total)
+            .ok_or(AirdropError::MathOverflow)?;
+
+        emit_event!(self, StreamCreated {
+            project: self.project.key(),
+            recipient: self.recipient.key(),
+            nonce,
+            total,
+            rate_per_second,
+            start_ts,
+            end_ts,
+        });
+
+        Ok(())
+    }
+}
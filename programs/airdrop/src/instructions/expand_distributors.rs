@@ -0,0 +1,44 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(additional: u16)]
+pub struct ExpandDistributors<'info> {
+    /// The account paying for the additional rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The authority that manages the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA, grown to fit `additional` more distributors per set
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized,
+        realloc = GlobalConfig::space(global_config.max_distributors() + additional as usize),
+        realloc::payer = payer,
+        realloc::zero = false
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExpandDistributors<'info> {
+    pub fn expand(&mut self, additional: u16) -> Result<()> {
+        let distributor_capacity = (self.global_config.max_distributors() as u16)
+            .checked_add(additional)
+            .ok_or(AirdropError::MathOverflow)?;
+
+        self.global_config.distributor_capacity = distributor_capacity;
+
+        emit_event!(self, DistributorCapacityExpanded {
+            distributor_capacity,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct FundProject<'info> {
+    /// The account depositing tokens into the project
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// The funder's token account (source of tokens)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = funder,
+        token::token_program = token_program
+    )]
+    pub funder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The project PDA being funded
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        constraint = project.mint == mint.key() @ AirdropError::MintMismatch
+    )]
+    pub project: Account<'info, Project>,
+
+    /// The funder's deposit accounting for this project
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = Funder::DISCRIMINATOR.len() + Funder::INIT_SPACE,
+        seeds = [FUNDER_SEED_PREFIX, project.key().as_ref(), funder.key().as_ref()],
+        bump
+    )]
+    pub funder_state: Account<'info, Funder>,
+
+    /// The mint of the SPL token being distributed
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The project's token vault PDA (destination of tokens)
+    #[account(
+        mut,
+        seeds = [TOKEN_VAULT_SEED_PREFIX, project.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> FundProject<'info> {
+    /// Deposits tokens into the project and records them in `total_funded` and the funder's account
+    ///
+    /// `remaining_accounts` carries any extra accounts a Token-2022 transfer hook needs
+    pub fn fund(
+        &mut self,
+        _project_nonce: u64,
+        amount: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        transfer_spl(
+            self.token_program.to_account_info(),
+            self.funder.to_account_info(),
+            self.funder_token_account.to_account_info(),
+            self.mint.to_account_info(),
+            self.token_vault.to_account_info(),
+            remaining_accounts,
+            amount,
+            self.mint.decimals,
+            None,
+        )?;
+
+        self.project.total_funded = self
+            .project
+            .total_funded
+            .checked_add(amount)
+            .ok_or(AirdropError::MathOverflow)?;
+
+        self.project.outstanding_deposits = self
+            .project
+            .outstanding_deposits
+            .checked_add(amount)
+            .ok_or(AirdropError::MathOverflow)?;
+
+        let funder_state: &mut Funder = &mut self.funder_state;
+        funder_state.project = self.project.key();
+        funder_state.funder = self.funder.key();
+        funder_state.deposited = funder_state
+            .deposited
+            .checked_add(amount)
+            .ok_or(AirdropError::MathOverflow)?;
+
+        emit_event!(self, ProjectFunded {
+            project: self.project.key(),
+            funder: self.funder.key(),
+            amount,
+            total_funded: self.project.total_funded,
+        });
+
+        // A deposit may still leave the vault short, e.g. a transfer fee took part of it
+        self.token_vault.reload()?;
+        if self.project.is_low_balance(self.token_vault.amount) {
+            emit_event!(self, VaultLowBalance {
+                project: self.project.key(),
+                vault_balance: self.token_vault.amount,
+                low_balance_threshold: self.project.low_balance_threshold,
+                remaining_budget: self.project.budget.saturating_sub(self.project.total_claimed),
+            });
+        }
+
+        Ok(())
+    }
+}
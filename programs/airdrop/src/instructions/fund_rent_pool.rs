@@ -0,0 +1,49 @@
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct FundRentPool<'info> {
+    /// The account depositing lamports into the rent pool
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// The project PDA holding the rent pool
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, Project>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FundRentPool<'info> {
+    /// Deposits lamports into the project's rent pool, from which claims reimburse their
+    /// payer for the accounts they create
+    pub fn fund_rent_pool(&mut self, _project_nonce: u64, amount: u64) -> Result<()> {
+        transfer_native(
+            &self.funder.to_account_info(),
+            &self.project.to_account_info(),
+            amount,
+            None,
+        )?;
+
+        self.project.rent_pool_lamports = self
+            .project
+            .rent_pool_lamports
+            .checked_add(amount)
+            .ok_or(AirdropError::MathOverflow)?;
+
+        emit_event!(self, RentPoolFunded {
+            project: self.project.key(),
+            funder: self.funder.key(),
+            amount,
+            rent_pool_lamports: self.project.rent_pool_lamports,
+        });
+
+        Ok(())
+    }
+}
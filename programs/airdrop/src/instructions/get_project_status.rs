@@ -0,0 +1,68 @@
+use crate::{constants::*, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+/// A summary of a project's claimable state, returned through return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProjectStatus {
+    /// The token balance left in the project's vault
+    pub remaining_balance: u64,
+
+    /// The total amount claimed from the project so far (fees included)
+    pub total_claimed: u64,
+
+    /// The maximum total amount that may be claimed from the project; 0 means uncapped
+    pub budget: u64,
+
+    /// The number of successful claims against the project
+    pub claim_count: u64,
+
+    /// Whether claims are blocked by a pause or an admin freeze
+    pub paused: bool,
+
+    /// Unix timestamp from which claims are accepted
+    pub start_time: i64,
+
+    /// Unix timestamp from which claims are rejected; 0 means the window never closes
+    pub end_time: i64,
+
+    /// Whether the claim window is open at the current clock
+    pub window_open: bool,
+}
+
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct GetProjectStatus<'info> {
+    /// The project PDA being queried
+    #[account(
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, Project>,
+
+    /// The project's token vault PDA
+    #[account(
+        seeds = [TOKEN_VAULT_SEED_PREFIX, project.key().as_ref()],
+        bump
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+}
+
+impl<'info> GetProjectStatus<'info> {
+    /// Summarizes the project in a single read-only call, meant to be simulated
+    pub fn get_status(&self, _project_nonce: u64) -> Result<ProjectStatus> {
+        let project = &self.project;
+        let now = Clock::get()?.unix_timestamp;
+
+        Ok(ProjectStatus {
+            remaining_balance: self.token_vault.amount,
+            total_claimed: project.total_claimed,
+            budget: project.budget,
+            claim_count: project.claim_count,
+            paused: project.paused || project.frozen_by_admin,
+            start_time: project.start_time,
+            end_time: project.end_time,
+            window_open: project.check_claim_window(now).is_ok(),
+        })
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct MigrateGlobalConfig<'info> {
+    /// The account paying for any additional rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The authority that manages the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA, possibly laid out by an older program version
+    /// CHECK: Ownership and seeds are checked here; the layout is checked in the handler
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        owner = crate::ID
+    )]
+    pub global_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateGlobalConfig<'info> {
+    /// Grows the global config to the current layout and bumps its version
+    pub fn migrate(&mut self) -> Result<()> {
+        let account = self.global_config.to_account_info();
+
+        require!(
+            account.try_borrow_data()?.starts_with(GlobalConfig::DISCRIMINATOR),
+            AirdropError::InvalidAccountLayout
+        );
+
+        // Fields missing from an older layout read as zero, so decode from a padded copy;
+        // the size to grow to depends on the distributor capacity stored in the account
+        let mut data = account.try_borrow_data()?.to_vec();
+        data.resize(
+            data.len() + GlobalConfig::DISCRIMINATOR.len() + GlobalConfig::INIT_SPACE,
+            0,
+        );
+        let mut config = GlobalConfig::try_deserialize(&mut &data[..])?;
+
+        require_keys_eq!(
+            config.authority,
+            self.authority.key(),
+            AirdropError::Unauthorized
+        );
+
+        grow_account(
+            &account,
+            &self.payer.to_account_info(),
+            GlobalConfig::space(config.max_distributors()),
+        )?;
+
+        let from_version = config.version;
+        require!(
+            from_version <= GLOBAL_CONFIG_VERSION,
+            AirdropError::UnsupportedAccountVersion
+        );
+
+        config.version = GLOBAL_CONFIG_VERSION;
+        config.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+        emit_event!(self, AccountMigrated {
+            account: account.key(),
+            from_version,
+            to_version: GLOBAL_CONFIG_VERSION,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct MigrateProject<'info> {
+    /// The account paying for any additional rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The authority that manages the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The project PDA, possibly laid out by an older program version
+    /// CHECK: Ownership and seeds are checked here; the layout is checked in the handler
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub project: UncheckedAccount<'info>,
+
+    /// The project's mint, read to backfill its decimals
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateProject<'info> {
+    /// Grows a project to the current layout and bumps its version
+    pub fn migrate(&mut self, _project_nonce: u64) -> Result<()> {
+        let account = self.project.to_account_info();
+
+        require!(
+            account.try_borrow_data()?.starts_with(Project::DISCRIMINATOR),
+            AirdropError::InvalidAccountLayout
+        );
+
+        grow_account(
+            &account,
+            &self.payer.to_account_info(),
+            Project::DISCRIMINATOR.len() + Project::INIT_SPACE,
+        )?;
+
+        let mut project = Project::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+
+        let from_version = project.version;
+        require!(
+            from_version <= PROJECT_VERSION,
+            AirdropError::UnsupportedAccountVersion
+        );
+
+        // Version 2 added the recorded mint decimals
+        if from_version < 2 {
+            require!(project.mint == self.mint.key(), AirdropError::MintMismatch);
+            project.decimals = self.mint.decimals;
+        }
+
+        project.version = PROJECT_VERSION;
+        project.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+        emit_event!(self, AccountMigrated {
+            account: account.key(),
+            from_version,
+            to_version: PROJECT_VERSION,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct MigrateProjectVault<'info> {
+    /// The project authority, paying for the new vault and receiving the old one's rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The project PDA, which owns both the legacy and the new vault
+    #[account(
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized,
+        has_one = mint @ AirdropError::MintMismatch
+    )]
+    pub project: Box<Account<'info, Project>>,
+
+    /// The mint of the SPL token being distributed
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The project's associated token account, where older program versions kept its tokens
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = project,
+        associated_token::token_program = token_program
+    )]
+    pub legacy_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The project's token vault PDA, created here for projects that predate it
+    #[account(
+        init,
+        payer = authority,
+        seeds = [TOKEN_VAULT_SEED_PREFIX, project.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> MigrateProjectVault<'info> {
+    /// Creates the token vault PDA for a project whose tokens still sit in its ATA, moves
+    /// the ATA's balance into it and closes the ATA
+    ///
+    /// `remaining_accounts` carries any extra accounts a Token-2022 transfer hook needs
+    pub fn migrate(
+        &mut self,
+        project_nonce: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let amount = self.legacy_vault.amount;
+
+        let nonce_bytes = project_nonce.to_le_bytes();
+        let project_bump = get_project_bump(project_nonce, &crate::ID);
+        let seeds = &[PROJECT_SEED_PREFIX, nonce_bytes.as_ref(), &[project_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        if amount > 0 {
+            transfer_spl(
+                self.token_program.to_account_info(),
+                self.project.to_account_info(),
+                self.legacy_vault.to_account_info(),
+                self.mint.to_account_info(),
+                self.token_vault.to_account_info(),
+                remaining_accounts,
+                amount,
+                self.mint.decimals,
+                Some(signer_seeds),
+            )?;
+        }
+
+        close_spl_account(
+            self.token_program.to_account_info(),
+            self.project.to_account_info(),
+            self.legacy_vault.to_account_info(),
+            self.authority.to_account_info(),
+            Some(signer_seeds),
+        )?;
+
+        emit_event!(self, ProjectVaultMigrated {
+            project: self.project.key(),
+            legacy_vault: self.legacy_vault.key(),
+            token_vault: self.token_vault.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+}
@@ -1,7 +1,169 @@
+pub mod accept_authority;
+pub mod add_creator_to_allowlist;
+pub mod add_manager;
+pub mod add_to_denylist;
+pub mod burn_unclaimed;
 pub mod claim;
+pub mod claim_and_delegate;
+pub mod claim_and_stake;
+pub mod claim_batch;
+pub mod claim_entry;
+pub mod claim_for;
+pub mod claim_idempotent;
+pub mod claim_into_pool;
+pub mod claim_partial;
+pub mod claim_vesting;
+pub mod claim_with_cnft;
+pub mod claim_with_nft;
+pub mod claim_with_ticket;
+pub mod close_global_config;
+pub mod close_nullifier;
+pub mod close_project;
 pub mod create_global_config;
 pub mod create_project;
+pub mod create_stream;
+pub mod expand_distributors;
+pub mod fund_project;
+pub mod fund_rent_pool;
+pub mod get_project_status;
+pub mod migrate_global_config;
+pub mod migrate_project;
+pub mod migrate_project_vault;
+pub mod pause;
+pub mod refund_funder;
+pub mod remove_creator_from_allowlist;
+pub mod remove_from_denylist;
+pub mod remove_manager;
+pub mod rotate_distributors;
+pub mod set_allow_risky_mints;
+pub mod set_deadline_grace;
+pub mod set_distributor_active;
+pub mod set_fee_config;
+pub mod set_genesis_hash;
+pub mod set_guardian;
+pub mod set_max_claim_amount;
+pub mod set_project_budget;
+pub mod set_paused_by_signature;
+pub mod set_project_attestation_gate;
+pub mod set_project_claim_fee;
+pub mod set_project_claim_group;
+pub mod set_project_claim_window;
+pub mod set_project_collection_gate;
+pub mod set_project_compressed_nft_gate;
+pub mod set_project_creation_mode;
+pub mod set_project_deadline_grace;
+pub mod set_project_distributors;
+pub mod set_project_fee_override;
+pub mod set_project_frozen;
+pub mod set_project_lock_duration;
+pub mod set_project_low_balance_threshold;
+pub mod set_project_max_claim_amount;
+pub mod set_project_max_per_recipient;
+pub mod set_project_mint_on_claim;
+pub mod set_project_nullifier_mode;
+pub mod set_project_paused;
+pub mod set_project_phases;
+pub mod set_project_recipient_must_pay;
+pub mod set_project_stake_pool;
+pub mod set_project_stake_vote_account;
+pub mod set_project_threshold;
+pub mod set_project_ticket;
+pub mod set_project_throttle;
+pub mod set_project_tiers;
+pub mod set_project_transfer_fee_mode;
+pub mod set_rate_limit;
+pub mod skip_project_nonce;
+pub mod transfer_authority;
+pub mod unlock;
+pub mod unpause;
+pub mod update_distributors_by_signature;
+pub mod update_global_config;
+pub mod update_operators;
+pub mod update_project_metadata;
+pub mod withdraw_fees;
+pub mod withdraw_stream;
 
+pub use accept_authority::*;
+pub use add_creator_to_allowlist::*;
+pub use add_manager::*;
+pub use add_to_denylist::*;
+pub use burn_unclaimed::*;
 pub use claim::*;
+pub use claim_and_delegate::*;
+pub use claim_and_stake::*;
+pub use claim_batch::*;
+pub use claim_entry::*;
+pub use claim_for::*;
+pub use claim_idempotent::*;
+pub use claim_into_pool::*;
+pub use claim_partial::*;
+pub use claim_vesting::*;
+pub use claim_with_cnft::*;
+pub use claim_with_nft::*;
+pub use claim_with_ticket::*;
+pub use close_global_config::*;
+pub use close_nullifier::*;
+pub use close_project::*;
 pub use create_global_config::*;
-pub use create_project::*;
\ No newline at end of file
+pub use create_project::*;
+pub use create_stream::*;
+pub use expand_distributors::*;
+pub use fund_project::*;
+pub use fund_rent_pool::*;
+pub use get_project_status::*;
+pub use migrate_global_config::*;
+pub use migrate_project::*;
+pub use migrate_project_vault::*;
+pub use pause::*;
+pub use refund_funder::*;
+pub use remove_creator_from_allowlist::*;
+pub use remove_from_denylist::*;
+pub use remove_manager::*;
+pub use rotate_distributors::*;
+pub use set_allow_risky_mints::*;
+pub use set_deadline_grace::*;
+pub use set_distributor_active::*;
+pub use set_fee_config::*;
+pub use set_genesis_hash::*;
+pub use set_guardian::*;
+pub use set_max_claim_amount::*;
+pub use set_project_budget::*;
+pub use set_paused_by_signature::*;
+pub use set_project_attestation_gate::*;
+pub use set_project_claim_fee::*;
+pub use set_project_claim_group::*;
+pub use set_project_claim_window::*;
+pub use set_project_collection_gate::*;
+pub use set_project_compressed_nft_gate::*;
+pub use set_project_creation_mode::*;
+pub use set_project_deadline_grace::*;
+pub use set_project_distributors::*;
+pub use set_project_fee_override::*;
+pub use set_project_frozen::*;
+pub use set_project_lock_duration::*;
+pub use set_project_low_balance_threshold::*;
+pub use set_project_max_claim_amount::*;
+pub use set_project_max_per_recipient::*;
+pub use set_project_mint_on_claim::*;
+pub use set_project_nullifier_mode::*;
+pub use set_project_paused::*;
+pub use set_project_phases::*;
+pub use set_project_recipient_must_pay::*;
+pub use set_project_stake_pool::*;
+pub use set_project_stake_vote_account::*;
+pub use set_project_threshold::*;
+pub use set_project_ticket::*;
+pub use set_project_throttle::*;
+pub use set_project_tiers::*;
+pub use set_project_transfer_fee_mode::*;
+pub use set_rate_limit::*;
+pub use skip_project_nonce::*;
+pub use transfer_authority::*;
+pub use unlock::*;
+pub use unpause::*;
+pub use update_distributors_by_signature::*;
+pub use update_global_config::*;
+pub use update_operators::*;
+pub use update_project_metadata::*;
+pub use withdraw_fees::*;
+pub use withdraw_stream::*;
\ No newline at end of file
@@ -0,0 +1,31 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    /// The authority or the guardian halting the program
+    pub signer: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        constraint = signer.key() == global_config.authority
+            || signer.key() == global_config.guardian @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> Pause<'info> {
+    pub fn pause(&mut self) -> Result<()> {
+        self.global_config.paused = true;
+
+        emit_event!(self, Paused {
+            signer: self.signer.key(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct RefundFunder<'info> {
+    /// The funder withdrawing their share, who also receives the funder account's rent
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// The funder's token account (destination of the refund)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = funder,
+        token::token_program = token_program
+    )]
+    pub funder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The project PDA being refunded from
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = mint @ AirdropError::MintMismatch,
+        constraint = !project.frozen_by_admin @ AirdropError::ProjectFrozen
+    )]
+    pub project: Account<'info, Project>,
+
+    /// The funder's deposit accounting, closed once refunded
+    #[account(
+        mut,
+        close = funder,
+        seeds = [FUNDER_SEED_PREFIX, project.key().as_ref(), funder.key().as_ref()],
+        bump
+    )]
+    pub funder_state: Account<'info, Funder>,
+
+    /// The mint of the SPL token being distributed
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The project's token vault PDA (source of the refund)
+    #[account(
+        mut,
+        seeds = [TOKEN_VAULT_SEED_PREFIX, project.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> RefundFunder<'info> {
+    /// Pays the funder their pro-rata share of the deposits left in the vault, in proportion
+    /// to their deposits against all deposits not yet refunded. Tokens beyond the outstanding
+    /// deposits belong to the project authority and are not shared out.
+    ///
+    /// `remaining_accounts` carries any extra accounts a Token-2022 transfer hook needs
+    pub fn refund(&mut self, project_nonce: u64, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        require!(
+            self.project.is_refundable(Clock::get()?.unix_timestamp),
+            AirdropError::RefundNotAvailable
+        );
+
+        let deposited = self.funder_state.deposited;
+        let outstanding = self.project.outstanding_deposits;
+        let refunded = if outstanding == 0 {
+            0
+        } else {
+            let pool = self.project.deposited_pool(self.token_vault.amount);
+            (pool as u128 * deposited as u128 / outstanding as u128) as u64
+        };
+
+        if refunded > 0 {
+            let nonce_bytes = project_nonce.to_le_bytes();
+            let project_bump = get_project_bump(project_nonce, &crate::ID);
+            let seeds = &[PROJECT_SEED_PREFIX, nonce_bytes.as_ref(), &[project_bump]];
+            let signer_seeds = &[&seeds[..]];
+
+            transfer_spl(
+                self.token_program.to_account_info(),
+                self.project.to_account_info(),
+                self.token_vault.to_account_info(),
+                self.mint.to_account_info(),
+                self.funder_token_account.to_account_info(),
+                remaining_accounts,
+                refunded,
+                self.mint.decimals,
+                Some(signer_seeds),
+            )?;
+        }
+
+        self.project.outstanding_deposits = outstanding.saturating_sub(deposited);
+
+        emit_event!(self, FunderRefunded {
+            project: self.project.key(),
+            funder: self.funder.key(),
+            deposited,
+            refunded,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(creator: Pubkey)]
+pub struct RemoveCreatorFromAllowlist<'info> {
+    /// The authority that manages the configuration and receives the rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The creator allowlist entry PDA to be closed
+    #[account(
+        mut,
+        close = authority,
+        seeds = [CREATOR_ALLOWLIST_SEED_PREFIX, creator.as_ref()],
+        bump
+    )]
+    pub creator_allowlist_entry: Account<'info, CreatorAllowlistEntry>,
+}
+
+impl<'info> RemoveCreatorFromAllowlist<'info> {
+    pub fn remove(&mut self, creator: Pubkey) -> Result<()> {
+        emit_event!(self, CreatorRemovedFromAllowlist { creator });
+
+        Ok(())
+    }
+}
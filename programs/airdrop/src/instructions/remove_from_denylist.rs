@@ -0,0 +1,36 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey)]
+pub struct RemoveFromDenylist<'info> {
+    /// The authority that manages the configuration and receives the rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The denylist entry PDA to be closed
+    #[account(
+        mut,
+        close = authority,
+        seeds = [DENYLIST_SEED_PREFIX, recipient.as_ref()],
+        bump
+    )]
+    pub denylist_entry: Account<'info, DenylistEntry>,
+}
+
+impl<'info> RemoveFromDenylist<'info> {
+    pub fn remove(&mut self, recipient: Pubkey) -> Result<()> {
+        emit_event!(self, RecipientRemovedFromDenylist { recipient });
+
+        Ok(())
+    }
+}
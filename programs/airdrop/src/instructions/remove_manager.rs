@@ -0,0 +1,38 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct RemoveManager<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA the manager is removed from
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> RemoveManager<'info> {
+    pub fn remove(&mut self, _project_nonce: u64, manager: Pubkey) -> Result<()> {
+        let managers = &mut self.project.managers;
+        let index = managers
+            .iter()
+            .position(|m| *m == manager)
+            .ok_or(AirdropError::ManagerNotFound)?;
+
+        managers.remove(index);
+
+        emit_event!(self, ManagerRemoved {
+            project: self.project.key(),
+            manager,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RotateDistributors<'info> {
+    /// The authority that manages the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> RotateDistributors<'info> {
+    /// Replaces the distributor set, keeping the outgoing set valid for `grace_slots` slots
+    /// so that signatures issued before the rotation can still be claimed.
+    pub fn rotate(
+        &mut self,
+        distributors: Vec<Distributor>,
+        threshold: u8,
+        required_weight: u16,
+        grace_slots: u64,
+    ) -> Result<()> {
+        validate_distributor_list(&distributors, self.global_config.max_distributors())?;
+        validate_distributor_set(&distributors, threshold, required_weight)?;
+
+        let current_slot = Clock::get()?.slot;
+        let expiry_slot = current_slot.saturating_add(grace_slots);
+
+        let config = &mut self.global_config;
+        config.previous_distributors = std::mem::replace(&mut config.distributors, distributors);
+        config.previous_threshold = config.threshold;
+        config.previous_required_weight = config.required_weight;
+        config.previous_distributors_expiry_slot = expiry_slot;
+        config.threshold = threshold;
+        config.required_weight = required_weight;
+
+        emit_event!(self, DistributorsRotated {
+            distributors: config.distributors.clone(),
+            threshold,
+            required_weight,
+            previous_distributors_expiry_slot: expiry_slot,
+        });
+
+        Ok(())
+    }
+}
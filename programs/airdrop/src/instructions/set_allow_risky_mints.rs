@@ -0,0 +1,30 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetAllowRiskyMints<'info> {
+    /// The authority that manages the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> SetAllowRiskyMints<'info> {
+    /// Sets whether projects may be created for mints with a freeze authority,
+    /// a permanent delegate or the non-transferable extension
+    pub fn set_allow_risky_mints(&mut self, allow_risky_mints: bool) -> Result<()> {
+        self.global_config.allow_risky_mints = allow_risky_mints;
+
+        emit_event!(self, AllowRiskyMintsUpdated { allow_risky_mints });
+
+        Ok(())
+    }
+}
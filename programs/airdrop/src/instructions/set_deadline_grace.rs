@@ -0,0 +1,35 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetDeadlineGrace<'info> {
+    /// The authority that manages the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> SetDeadlineGrace<'info> {
+    /// Sets how many seconds signed messages are still accepted after their deadline, for
+    /// projects without their own grace period
+    pub fn set_deadline_grace(&mut self, deadline_grace_secs: u32) -> Result<()> {
+        require!(
+            deadline_grace_secs <= MAX_DEADLINE_GRACE_SECS,
+            AirdropError::InvalidDeadlineGrace
+        );
+
+        self.global_config.deadline_grace_secs = deadline_grace_secs;
+
+        emit_event!(self, DeadlineGraceUpdated { deadline_grace_secs });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetDistributorActive<'info> {
+    /// The authority that manages the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> SetDistributorActive<'info> {
+    /// Activates or deactivates a distributor without changing its position in the set
+    pub fn set_active(&mut self, distributor: Pubkey, active: bool) -> Result<()> {
+        let config: &mut GlobalConfig = &mut self.global_config;
+
+        let index = find_distributor(&config.distributors, &distributor)
+            .ok_or(AirdropError::DistributorNotFound)?;
+        config.distributors[index].active = active;
+
+        // The remaining active distributors must still be able to meet the requirements
+        validate_distributor_set(&config.distributors, config.threshold, config.required_weight)?;
+
+        emit_event!(self, DistributorActiveUpdated {
+            distributor,
+            active,
+        });
+
+        Ok(())
+    }
+}
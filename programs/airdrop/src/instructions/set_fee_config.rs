@@ -0,0 +1,37 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetFeeConfig<'info> {
+    /// The authority that manages the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> SetFeeConfig<'info> {
+    pub fn set_fee_config(&mut self, fee_bps: u16, fee_treasury: Pubkey) -> Result<()> {
+        require!(
+            fee_bps as u64 <= BPS_DENOMINATOR,
+            AirdropError::InvalidFeeBps
+        );
+
+        self.global_config.fee_bps = fee_bps;
+        self.global_config.fee_treasury = fee_treasury;
+
+        emit_event!(self, FeeConfigUpdated {
+            fee_bps,
+            fee_treasury,
+        });
+
+        Ok(())
+    }
+}
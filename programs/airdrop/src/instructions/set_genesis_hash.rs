@@ -0,0 +1,30 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetGenesisHash<'info> {
+    /// The authority that manages the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> SetGenesisHash<'info> {
+    /// Sets the genesis hash of the cluster the program runs on. Signed messages must carry
+    /// the same hash, so messages signed for another cluster are rejected.
+    pub fn set_genesis_hash(&mut self, genesis_hash: [u8; 32]) -> Result<()> {
+        self.global_config.genesis_hash = genesis_hash;
+
+        emit_event!(self, GenesisHashUpdated { genesis_hash });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    /// The authority that manages the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> SetGuardian<'info> {
+    /// Sets the guardian allowed to pause the program. `Pubkey::default()` removes it.
+    pub fn set_guardian(&mut self, guardian: Pubkey) -> Result<()> {
+        self.global_config.guardian = guardian;
+
+        emit_event!(self, GuardianUpdated { guardian });
+
+        Ok(())
+    }
+}
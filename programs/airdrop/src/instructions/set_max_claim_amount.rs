@@ -0,0 +1,29 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetMaxClaimAmount<'info> {
+    /// The authority that manages the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> SetMaxClaimAmount<'info> {
+    /// Sets the per-claim amount cap. `None` removes it.
+    pub fn set_max_claim_amount(&mut self, max_claim_amount: Option<u64>) -> Result<()> {
+        self.global_config.max_claim_amount = max_claim_amount;
+
+        emit_event!(self, MaxClaimAmountUpdated { max_claim_amount });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{sysvar::instructions as ix_sysvar, sysvar::SysvarId};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct SetPausedBySignature<'info> {
+    /// The account submitting the authority's signed message and paying for its nullifier
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Nullifier account to prevent reuse of the admin message's nonce, scoped to the global
+    /// config the way claim nullifiers are scoped to their project
+    #[account(
+        init,
+        payer = payer,
+        space = ClaimNullifier::DISCRIMINATOR.len() + ClaimNullifier::INIT_SPACE,
+        seeds = [
+            CLAIM_NULLIFIER_SEED_PREFIX,
+            global_config.key().as_ref(),
+            nonce.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub nullifier: Account<'info, ClaimNullifier>,
+
+    /// The sysvar containing the full transaction's instructions
+    /// CHECK: Validated by requiring its well-known address
+    #[account(address = ix_sysvar::Instructions::id())]
+    pub instruction_sysvar: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetPausedBySignature<'info> {
+    /// Pauses or unpauses the program as approved by the authority's signature over an admin
+    /// message (immediately preceding this instruction)
+    pub fn set_paused_by_signature(&mut self, nonce: u64) -> Result<()> {
+        let AdminAction::SetPaused { paused } =
+            verify_admin_message(&self.instruction_sysvar, &self.global_config, nonce)?
+        else {
+            return err!(AirdropError::AdminActionMismatch);
+        };
+
+        // If this nonce was already used, the nullifier's init constraint would have failed
+        self.nullifier.set_inner(ClaimNullifier { nonce });
+        self.global_config.paused = paused;
+
+        let authority = self.global_config.authority;
+        if paused {
+            emit_event!(self, Paused { signer: authority });
+        } else {
+            emit_event!(self, Unpaused { authority });
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectAttestationGate<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA whose required attestation is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectAttestationGate<'info> {
+    /// Sets the attestation recipients must hold to claim, or lifts the requirement with `None`
+    pub fn set_attestation_gate(
+        &mut self,
+        _project_nonce: u64,
+        attestation_gate: Option<AttestationGate>,
+    ) -> Result<()> {
+        self.project.attestation_gate = attestation_gate;
+
+        emit_event!(self, ProjectAttestationGateUpdated {
+            project: self.project.key(),
+            attestation_gate,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectBudget<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA whose budget is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectBudget<'info> {
+    /// Sets the total amount that may be claimed from the project. A budget of 0 removes the cap.
+    pub fn set_budget(&mut self, _project_nonce: u64, budget: u64) -> Result<()> {
+        require!(
+            budget == 0 || budget >= self.project.total_claimed,
+            AirdropError::InvalidBudget
+        );
+
+        self.project.budget = budget;
+
+        emit_event!(self, ProjectBudgetUpdated {
+            project: self.project.key(),
+            budget,
+        });
+
+        Ok(())
+    }
+}
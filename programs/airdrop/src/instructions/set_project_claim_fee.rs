@@ -0,0 +1,41 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectClaimFee<'info> {
+    /// The authority that manages the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The project PDA whose claim fee is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectClaimFee<'info> {
+    /// Sets the flat lamport fee charged to the payer of each claim against the project,
+    /// recouping the rent relayers subsidize. 0 disables it.
+    pub fn set_project_claim_fee(&mut self, _project_nonce: u64, claim_fee_lamports: u64) -> Result<()> {
+        self.project.claim_fee_lamports = claim_fee_lamports;
+
+        emit_event!(self, ProjectClaimFeeUpdated {
+            project: self.project.key(),
+            claim_fee_lamports,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectClaimGroup<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA whose campaign group is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectClaimGroup<'info> {
+    /// Puts the project in a campaign group whose wallets may claim from only one of its
+    /// projects, or takes it out of its group with `None`
+    pub fn set_claim_group(&mut self, _project_nonce: u64, claim_group: Option<u64>) -> Result<()> {
+        self.project.claim_group = claim_group;
+
+        emit_event!(self, ProjectClaimGroupUpdated {
+            project: self.project.key(),
+            claim_group,
+        });
+
+        Ok(())
+    }
+}
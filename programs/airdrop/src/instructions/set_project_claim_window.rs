@@ -0,0 +1,41 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectClaimWindow<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA whose claim window is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectClaimWindow<'info> {
+    /// Sets the window in which claims against the project are accepted
+    pub fn set_claim_window(&mut self, _project_nonce: u64, start_time: i64, end_time: i64) -> Result<()> {
+        require!(
+            !self.project.claim_window_sealed,
+            AirdropError::ClaimWindowSealed
+        );
+        Project::validate_claim_window(start_time, end_time)?;
+
+        self.project.start_time = start_time;
+        self.project.end_time = end_time;
+
+        emit_event!(self, ProjectClaimWindowUpdated {
+            project: self.project.key(),
+            start_time,
+            end_time,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectCollectionGate<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA whose gating collection is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectCollectionGate<'info> {
+    /// Sets the verified collection whose holders can claim per NFT. `None` disables NFT claims.
+    pub fn set_collection_gate(
+        &mut self,
+        _project_nonce: u64,
+        collection_gate: Option<CollectionGate>,
+    ) -> Result<()> {
+        if let Some(collection_gate) = collection_gate {
+            require!(collection_gate.amount > 0, AirdropError::InvalidCollectionNft);
+        }
+
+        self.project.collection_gate = collection_gate;
+
+        emit_event!(self, ProjectCollectionGateUpdated {
+            project: self.project.key(),
+            collection_gate,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectCompressedNftGate<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA whose gating merkle tree is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectCompressedNftGate<'info> {
+    /// Sets the Bubblegum tree whose compressed NFT holders can claim per NFT. `None` disables
+    /// compressed NFT claims.
+    pub fn set_compressed_nft_gate(
+        &mut self,
+        _project_nonce: u64,
+        compressed_nft_gate: Option<CompressedNftGate>,
+    ) -> Result<()> {
+        if let Some(compressed_nft_gate) = compressed_nft_gate {
+            require!(compressed_nft_gate.amount > 0, AirdropError::InvalidCompressedNft);
+        }
+
+        self.project.compressed_nft_gate = compressed_nft_gate;
+
+        emit_event!(self, ProjectCompressedNftGateUpdated {
+            project: self.project.key(),
+            compressed_nft_gate,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetProjectCreationMode<'info> {
+    /// The authority that manages the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> SetProjectCreationMode<'info> {
+    pub fn set_mode(&mut self, mode: ProjectCreationMode) -> Result<()> {
+        self.global_config.project_creation_mode = mode;
+
+        emit_event!(self, ProjectCreationModeUpdated {
+            project_creation_mode: mode,
+        });
+
+        Ok(())
+    }
+}
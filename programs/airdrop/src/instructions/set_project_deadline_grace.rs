@@ -0,0 +1,43 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectDeadlineGrace<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA whose deadline grace period is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectDeadlineGrace<'info> {
+    /// Overrides the global deadline grace period for the project's claims. `None` falls back
+    /// to the global grace period.
+    pub fn set_deadline_grace(
+        &mut self,
+        _project_nonce: u64,
+        deadline_grace_secs: Option<u32>,
+    ) -> Result<()> {
+        require!(
+            deadline_grace_secs.unwrap_or(0) <= MAX_DEADLINE_GRACE_SECS,
+            AirdropError::InvalidDeadlineGrace
+        );
+
+        self.project.deadline_grace_secs = deadline_grace_secs;
+
+        emit_event!(self, ProjectDeadlineGraceUpdated {
+            project: self.project.key(),
+            deadline_grace_secs,
+        });
+
+        Ok(())
+    }
+}
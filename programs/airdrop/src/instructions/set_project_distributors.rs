@@ -0,0 +1,50 @@
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectDistributors<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The global config PDA, used to check the set against the global threshold
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The project PDA whose distributor set is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectDistributors<'info> {
+    /// Sets the project-level distributor set. An empty set falls back to the global distributors.
+    /// Only the threshold applies to a project's set; the distributors' weights are not counted.
+    pub fn set_distributors(&mut self, _project_nonce: u64, distributors: Vec<Distributor>) -> Result<()> {
+        if !distributors.is_empty() {
+            validate_distributor_list(&distributors, MAX_DISTRIBUTORS)?;
+            require!(
+                self.project.threshold_or(self.global_config.threshold) as usize
+                    <= active_count(&distributors),
+                AirdropError::InvalidThreshold
+            );
+        }
+
+        self.project.distributors = distributors;
+
+        emit_event!(self, ProjectDistributorsUpdated {
+            project: self.project.key(),
+            distributors: self.project.distributors.clone(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectFeeOverride<'info> {
+    /// The authority that manages the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The project PDA whose fee tier is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectFeeOverride<'info> {
+    /// Sets a project-specific fee tier. `None` falls back to the global fee config.
+    pub fn set_fee_override(&mut self, _project_nonce: u64, fee_override: Option<FeeOverride>) -> Result<()> {
+        if let Some(fee_override) = fee_override {
+            require!(
+                fee_override.fee_bps as u64 <= BPS_DENOMINATOR,
+                AirdropError::InvalidFeeBps
+            );
+        }
+
+        self.project.fee_override = fee_override;
+
+        emit_event!(self, ProjectFeeOverrideUpdated {
+            project: self.project.key(),
+            fee_override,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectFrozen<'info> {
+    /// The authority that manages the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The project PDA being frozen or unfrozen
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectFrozen<'info> {
+    /// Freezes or unfreezes a project. While frozen, claims and authority withdrawals are rejected.
+    pub fn set_frozen(&mut self, _project_nonce: u64, frozen: bool) -> Result<()> {
+        self.project.frozen_by_admin = frozen;
+
+        emit_event!(self, ProjectFrozenUpdated {
+            project: self.project.key(),
+            frozen,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectLockDuration<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA whose lockup is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectLockDuration<'info> {
+    /// Sets how long claimed tokens stay in escrow, or pays claims out directly with 0.
+    /// Only affects later claims; tokens already locked keep their unlock time.
+    pub fn set_lock_duration(&mut self, _project_nonce: u64, lock_duration: i64) -> Result<()> {
+        require!(lock_duration >= 0, AirdropError::InvalidLockup);
+
+        self.project.lock_duration = lock_duration;
+
+        emit_event!(self, ProjectLockDurationUpdated {
+            project: self.project.key(),
+            lock_duration,
+        });
+
+        Ok(())
+    }
+}
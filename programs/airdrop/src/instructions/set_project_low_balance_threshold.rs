@@ -0,0 +1,34 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectLowBalanceThreshold<'info> {
+    /// The project authority or one of its managers
+    pub signer: Signer<'info>,
+
+    /// The project PDA whose low-balance threshold is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        constraint = project.is_manager(&signer.key()) @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectLowBalanceThreshold<'info> {
+    /// Sets the vault balance below which claims and deposits emit `VaultLowBalance`. 0 disables it.
+    pub fn set_low_balance_threshold(&mut self, _project_nonce: u64, low_balance_threshold: u64) -> Result<()> {
+        self.project.low_balance_threshold = low_balance_threshold;
+
+        emit_event!(self, ProjectLowBalanceThresholdUpdated {
+            project: self.project.key(),
+            signer: self.signer.key(),
+            low_balance_threshold,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectMaxClaimAmount<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA whose per-grant cap is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectMaxClaimAmount<'info> {
+    /// Sets the amount any single signed grant may carry. `None` removes the cap.
+    pub fn set_max_claim_amount(&mut self, _project_nonce: u64, max_claim_amount: Option<u64>) -> Result<()> {
+        self.project.max_claim_amount = max_claim_amount;
+
+        emit_event!(self, ProjectMaxClaimAmountUpdated {
+            project: self.project.key(),
+            max_claim_amount,
+        });
+
+        Ok(())
+    }
+}
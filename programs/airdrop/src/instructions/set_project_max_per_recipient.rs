@@ -0,0 +1,33 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectMaxPerRecipient<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA whose per-recipient cap is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectMaxPerRecipient<'info> {
+    /// Sets the cumulative amount any single recipient may claim. `None` removes the cap.
+    pub fn set_max_per_recipient(&mut self, _project_nonce: u64, max_per_recipient: Option<u64>) -> Result<()> {
+        self.project.max_per_recipient = max_per_recipient;
+
+        emit_event!(self, ProjectMaxPerRecipientUpdated {
+            project: self.project.key(),
+            max_per_recipient,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType;
+use anchor_spl::token_interface::{self, Mint, SetAuthority, TokenInterface};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectMintOnClaim<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA whose distribution mode is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized,
+        has_one = mint @ AirdropError::MintMismatch
+    )]
+    pub project: Account<'info, Project>,
+
+    /// The project's mint, whose mint authority is handed back when minting is turned off
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> SetProjectMintOnClaim<'info> {
+    /// Switches the project between minting claims and paying them out of the vault.
+    ///
+    /// Minting requires the project PDA to already hold the mint authority. Turning it off
+    /// hands the mint authority back to the project authority.
+    pub fn set_mint_on_claim(&mut self, project_nonce: u64, mint_on_claim: bool) -> Result<()> {
+        if mint_on_claim {
+            require!(
+                self.mint.mint_authority == Some(self.project.key()).into(),
+                AirdropError::MintAuthorityRequired
+            );
+        } else if self.project.mint_on_claim {
+            let nonce_bytes = project_nonce.to_le_bytes();
+            let project_bump = get_project_bump(project_nonce, &crate::ID);
+            let seeds = &[PROJECT_SEED_PREFIX, nonce_bytes.as_ref(), &[project_bump]];
+            let signer_seeds = &[&seeds[..]];
+
+            token_interface::set_authority(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    SetAuthority {
+                        current_authority: self.project.to_account_info(),
+                        account_or_mint: self.mint.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                AuthorityType::MintTokens,
+                Some(self.authority.key()),
+            )?;
+        }
+
+        self.project.mint_on_claim = mint_on_claim;
+
+        emit_event!(self, ProjectMintOnClaimUpdated {
+            project: self.project.key(),
+            mint_on_claim,
+        });
+
+        Ok(())
+    }
+}
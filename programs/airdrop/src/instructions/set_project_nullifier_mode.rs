@@ -0,0 +1,41 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectNullifierMode<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA whose nullifier mode is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectNullifierMode<'info> {
+    /// Chooses how `claim` records used nonces: one nullifier PDA per nonce, bitmap shards
+    /// covering `NULLIFIER_SHARD_NONCES` nonces each, or a sequential counter per recipient.
+    /// Each records used nonces in different places, so the mode can only change before the
+    /// first claim.
+    pub fn set_project_nullifier_mode(&mut self, _project_nonce: u64, nullifier_mode: NullifierMode) -> Result<()> {
+        require!(
+            self.project.claim_count == 0,
+            AirdropError::NullifierModeLocked
+        );
+
+        self.project.nullifier_mode = nullifier_mode;
+
+        emit_event!(self, ProjectNullifierModeUpdated {
+            project: self.project.key(),
+            nullifier_mode,
+        });
+
+        Ok(())
+    }
+}
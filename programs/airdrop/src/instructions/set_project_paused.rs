@@ -0,0 +1,34 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectPaused<'info> {
+    /// The project authority or one of its managers
+    pub signer: Signer<'info>,
+
+    /// The project PDA being paused or unpaused
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        constraint = project.is_manager(&signer.key()) @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectPaused<'info> {
+    /// Pauses or unpauses claims against the project
+    pub fn set_paused(&mut self, _project_nonce: u64, paused: bool) -> Result<()> {
+        self.project.paused = paused;
+
+        emit_event!(self, ProjectPausedUpdated {
+            project: self.project.key(),
+            signer: self.signer.key(),
+            paused,
+        });
+
+        Ok(())
+    }
+}
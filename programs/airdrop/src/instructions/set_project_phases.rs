@@ -0,0 +1,40 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectPhases<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA whose phases are updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectPhases<'info> {
+    /// Replaces the project's phases. Claim counts are carried over from the phase previously
+    /// at the same position, so editing a phase cannot reset its claim limit.
+    pub fn set_phases(&mut self, _project_nonce: u64, mut phases: Vec<Phase>) -> Result<()> {
+        Project::validate_phases(&phases)?;
+
+        for (index, phase) in phases.iter_mut().enumerate() {
+            phase.claims = self.project.phases.get(index).map_or(0, |p| p.claims);
+        }
+
+        self.project.phases = phases;
+
+        emit_event!(self, ProjectPhasesUpdated {
+            project: self.project.key(),
+            phases: self.project.phases.clone(),
+        });
+
+        Ok(())
+    }
+}
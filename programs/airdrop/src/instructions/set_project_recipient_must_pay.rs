@@ -0,0 +1,34 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectRecipientMustPay<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA whose anti-bot mode is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectRecipientMustPay<'info> {
+    /// Sets whether claims must be paid for by the recipient. The program cannot see the
+    /// transaction fee payer, so this requires the recipient to sign as the claim's payer.
+    pub fn set_recipient_must_pay(&mut self, _project_nonce: u64, recipient_must_pay: bool) -> Result<()> {
+        self.project.recipient_must_pay = recipient_must_pay;
+
+        emit_event!(self, ProjectRecipientMustPayUpdated {
+            project: self.project.key(),
+            recipient_must_pay,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectStakePool<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA whose stake pool is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectStakePool<'info> {
+    /// Sets the stake pool `claim_into_pool` deposits into, or disables pool claims with `None`
+    pub fn set_stake_pool(
+        &mut self,
+        _project_nonce: u64,
+        stake_pool: Option<Pubkey>,
+    ) -> Result<()> {
+        self.project.stake_pool = stake_pool;
+
+        emit_event!(self, ProjectStakePoolUpdated {
+            project: self.project.key(),
+            stake_pool,
+        });
+
+        Ok(())
+    }
+}
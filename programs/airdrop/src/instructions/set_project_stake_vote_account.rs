@@ -0,0 +1,37 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectStakeVoteAccount<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA whose staking vote account is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectStakeVoteAccount<'info> {
+    /// Sets the vote account `claim_and_stake` delegates to, or disables staking claims with `None`
+    pub fn set_stake_vote_account(
+        &mut self,
+        _project_nonce: u64,
+        stake_vote_account: Option<Pubkey>,
+    ) -> Result<()> {
+        self.project.stake_vote_account = stake_vote_account;
+
+        emit_event!(self, ProjectStakeVoteAccountUpdated {
+            project: self.project.key(),
+            stake_vote_account,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectThreshold<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The global config PDA, used to check the threshold against the global distributor set
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The project PDA whose signature threshold is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectThreshold<'info> {
+    /// Sets the number of distributor signatures the project's claims require.
+    /// `None` falls back to the global threshold.
+    pub fn set_threshold(&mut self, _project_nonce: u64, threshold: Option<u8>) -> Result<()> {
+        if let Some(threshold) = threshold {
+            // The threshold must be reachable by whichever distributor set signs for the project
+            let available = if self.project.distributors.is_empty() {
+                active_count(&self.global_config.distributors)
+            } else {
+                active_count(&self.project.distributors)
+            };
+            require!(
+                threshold >= 1 && threshold as usize <= available,
+                AirdropError::InvalidThreshold
+            );
+        }
+
+        self.project.threshold = threshold;
+
+        emit_event!(self, ProjectThresholdUpdated {
+            project: self.project.key(),
+            threshold,
+        });
+
+        Ok(())
+    }
+}
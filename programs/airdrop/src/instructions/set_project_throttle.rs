@@ -0,0 +1,46 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectThrottle<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA whose claim throttle is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectThrottle<'info> {
+    /// Caps the claims the project accepts per window of slots. A `max_claims_per_window` of 0
+    /// disables the throttle.
+    pub fn set_throttle(
+        &mut self,
+        _project_nonce: u64,
+        max_claims_per_window: u64,
+        throttle_window_slots: u64,
+    ) -> Result<()> {
+        require!(
+            max_claims_per_window == 0 || throttle_window_slots > 0,
+            AirdropError::InvalidRateLimitWindow
+        );
+
+        self.project.max_claims_per_window = max_claims_per_window;
+        self.project.throttle_window_slots = throttle_window_slots;
+
+        emit_event!(self, ProjectThrottleUpdated {
+            project: self.project.key(),
+            max_claims_per_window,
+            throttle_window_slots,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectTicket<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA whose ticket mint is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectTicket<'info> {
+    /// Sets the ticket mint redeemable for a fixed amount. `None` disables ticket claims.
+    pub fn set_ticket(&mut self, _project_nonce: u64, ticket: Option<TicketConfig>) -> Result<()> {
+        if let Some(ticket) = ticket {
+            require!(ticket.amount > 0, AirdropError::InvalidTicket);
+        }
+
+        self.project.ticket = ticket;
+
+        emit_event!(self, ProjectTicketUpdated {
+            project: self.project.key(),
+            ticket,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectTiers<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA whose tiers are updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectTiers<'info> {
+    /// Replaces the project's recipient tiers, indexed by the tier in the signed message
+    pub fn set_tiers(&mut self, _project_nonce: u64, tiers: Vec<Tier>) -> Result<()> {
+        Project::validate_tiers(&tiers)?;
+
+        self.project.tiers = tiers;
+
+        emit_event!(self, ProjectTiersUpdated {
+            project: self.project.key(),
+            tiers: self.project.tiers.clone(),
+        });
+
+        Ok(())
+    }
+}
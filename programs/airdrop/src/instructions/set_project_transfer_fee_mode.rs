@@ -0,0 +1,33 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct SetProjectTransferFeeMode<'info> {
+    /// The authority that manages the project
+    pub authority: Signer<'info>,
+
+    /// The project PDA whose transfer fee mode is updated
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+}
+
+impl<'info> SetProjectTransferFeeMode<'info> {
+    /// Sets whether claims gross up for Token-2022 transfer fees so recipients receive the full claimed amount
+    pub fn set_transfer_fee_mode(&mut self, _project_nonce: u64, gross_up_transfer_fees: bool) -> Result<()> {
+        self.project.gross_up_transfer_fees = gross_up_transfer_fees;
+
+        emit_event!(self, ProjectTransferFeeModeUpdated {
+            project: self.project.key(),
+            gross_up_transfer_fees,
+        });
+
+        Ok(())
+    }
+}
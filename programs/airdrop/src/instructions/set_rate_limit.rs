@@ -0,0 +1,47 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetRateLimit<'info> {
+    /// The authority that manages the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> SetRateLimit<'info> {
+    /// Sets the global claim rate limit. A `max_claims_per_window` of 0 disables it.
+    pub fn set_rate_limit(
+        &mut self,
+        max_claims_per_window: u64,
+        rate_limit_window_slots: u64,
+    ) -> Result<()> {
+        require!(
+            max_claims_per_window == 0 || rate_limit_window_slots > 0,
+            AirdropError::InvalidRateLimitWindow
+        );
+
+        let config: &mut GlobalConfig = &mut self.global_config;
+        config.max_claims_per_window = max_claims_per_window;
+        config.rate_limit_window_slots = rate_limit_window_slots;
+
+        // Start a fresh window under the new limit
+        config.window_start_slot = Clock::get()?.slot;
+        config.claims_this_window = 0;
+
+        emit_event!(self, RateLimitUpdated {
+            max_claims_per_window,
+            rate_limit_window_slots,
+        });
+
+        Ok(())
+    }
+}
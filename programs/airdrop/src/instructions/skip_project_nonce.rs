@@ -0,0 +1,52 @@
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SkipProjectNonce<'info> {
+    /// The account paying for the counter if it does not exist yet
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The counter PDA that assigns project nonces
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ProjectCounter::DISCRIMINATOR.len() + ProjectCounter::INIT_SPACE,
+        seeds = [PROJECT_COUNTER_SEED],
+        bump
+    )]
+    pub project_counter: Account<'info, ProjectCounter>,
+
+    /// The project PDA at the counter's next nonce, already taken by a project
+    /// created with a caller-chosen nonce
+    /// CHECK: Only checked for existence; data at this PDA means the nonce is taken
+    #[account(
+        seeds = [PROJECT_SEED_PREFIX, project_counter.next_nonce().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub project: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SkipProjectNonce<'info> {
+    /// Advances the counter past a nonce an existing project already occupies,
+    /// so `create_project` is never stuck behind a project from before the counter
+    pub fn skip(&mut self) -> Result<()> {
+        require!(
+            is_program_account(&self.project),
+            AirdropError::ProjectNonceAvailable
+        );
+
+        let nonce = self.project_counter.next_nonce();
+        self.project_counter.count = nonce;
+
+        emit_event!(self, ProjectNonceSkipped {
+            project: self.project.key(),
+            nonce,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct TransferAuthority<'info> {
+    /// The current authority of the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> TransferAuthority<'info> {
+    /// Nominates a new authority (e.g. an SPL Governance native treasury or a multisig vault).
+    /// The transfer only takes effect once the nominee signs `accept_authority`.
+    pub fn transfer(&mut self, new_authority: Pubkey) -> Result<()> {
+        self.global_config.pending_authority = Some(new_authority);
+
+        emit_event!(self, AuthorityTransferStarted {
+            authority: self.authority.key(),
+            pending_authority: new_authority,
+        });
+
+        Ok(())
+    }
+}
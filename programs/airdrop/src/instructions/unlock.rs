@@ -0,0 +1,126 @@
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct Unlock<'info> {
+    /// Anyone may unlock on the recipient's behalf, paying for any token account created
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The recipient of the locked tokens, who also receives the escrow's rent
+    /// CHECK: Checked against the lockup's recipient
+    #[account(mut, address = locked_claim.recipient @ AirdropError::InvalidLockup)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The project PDA the tokens were claimed from
+    #[account(
+        mut,
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, Project>,
+
+    /// The recipient's lockup escrow, closed once released
+    #[account(
+        mut,
+        close = recipient,
+        seeds = [
+            LOCKED_CLAIM_SEED_PREFIX,
+            project.key().as_ref(),
+            locked_claim.recipient.as_ref(),
+        ],
+        bump,
+        has_one = project @ AirdropError::InvalidLockup
+    )]
+    pub locked_claim: Account<'info, LockedClaim>,
+
+    /// The mint of the locked tokens
+    #[account(address = project.mint @ AirdropError::MintMismatch)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token account holding the locked tokens, closed once released
+    #[account(
+        mut,
+        seeds = [
+            LOCKED_CLAIM_VAULT_SEED_PREFIX,
+            project.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program
+    )]
+    pub locked_claim_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The recipient's token account (destination of tokens)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> Unlock<'info> {
+    /// Releases the recipient's locked tokens once the lockup has ended. Permissionless, and
+    /// unaffected by the project's claim window or an admin freeze, since the tokens were
+    /// already claimed.
+    ///
+    /// `remaining_accounts` carries any extra accounts a Token-2022 transfer hook needs.
+    pub fn unlock(&mut self, project_nonce: u64, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp >= self.locked_claim.unlock_ts,
+            AirdropError::LockupNotExpired
+        );
+
+        let nonce_bytes = project_nonce.to_le_bytes();
+        let project_bump = get_project_bump(project_nonce, &crate::ID);
+        let seeds = &[PROJECT_SEED_PREFIX, nonce_bytes.as_ref(), &[project_bump]];
+        let amount = self.locked_claim_vault.amount;
+
+        transfer_spl(
+            self.token_program.to_account_info(),
+            self.project.to_account_info(),
+            self.locked_claim_vault.to_account_info(),
+            self.mint.to_account_info(),
+            self.recipient_token_account.to_account_info(),
+            remaining_accounts,
+            amount,
+            self.mint.decimals,
+            Some(&[&seeds[..]]),
+        )?;
+        close_spl_account(
+            self.token_program.to_account_info(),
+            self.project.to_account_info(),
+            self.locked_claim_vault.to_account_info(),
+            self.recipient.to_account_info(),
+            Some(&[&seeds[..]]),
+        )?;
+        self.project.outstanding_locked = self
+            .project
+            .outstanding_locked
+            .saturating_sub(self.locked_claim.amount);
+
+        emit_event!(self, ClaimUnlocked {
+            project: self.project.key(),
+            recipient: self.recipient.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+}
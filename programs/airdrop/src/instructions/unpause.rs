@@ -0,0 +1,30 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct Unpause<'info> {
+    /// The authority that manages the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> Unpause<'info> {
+    pub fn unpause(&mut self) -> Result<()> {
+        self.global_config.paused = false;
+
+        emit_event!(self, Unpaused {
+            authority: self.authority.key(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use super::update_global_config::DistributorSetUpdate;
+use crate::{constants::*, errors::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{sysvar::instructions as ix_sysvar, sysvar::SysvarId};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct UpdateDistributorsBySignature<'info> {
+    /// The account submitting the authority's signed message and paying for its nullifier
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Nullifier account to prevent reuse of the admin message's nonce
+    #[account(
+        init,
+        payer = payer,
+        space = ClaimNullifier::DISCRIMINATOR.len() + ClaimNullifier::INIT_SPACE,
+        seeds = [
+            CLAIM_NULLIFIER_SEED_PREFIX,
+            global_config.key().as_ref(),
+            nonce.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub nullifier: Account<'info, ClaimNullifier>,
+
+    /// The sysvar containing the full transaction's instructions
+    /// CHECK: Validated by requiring its well-known address
+    #[account(address = ix_sysvar::Instructions::id())]
+    pub instruction_sysvar: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateDistributorsBySignature<'info> {
+    /// Applies a distributor set change approved by the authority's signature over an admin
+    /// message (immediately preceding this instruction)
+    pub fn update_distributors_by_signature(&mut self, nonce: u64) -> Result<()> {
+        let AdminAction::UpdateDistributors(update) =
+            verify_admin_message(&self.instruction_sysvar, &self.global_config, nonce)?
+        else {
+            return err!(AirdropError::AdminActionMismatch);
+        };
+
+        // If this nonce was already used, the nullifier's init constraint would have failed
+        self.nullifier.set_inner(ClaimNullifier { nonce });
+
+        DistributorSetUpdate {
+            global_config: &mut self.global_config,
+            #[cfg(feature = "event-cpi")]
+            event_authority: self.event_authority.to_account_info(),
+        }
+        .apply(update)
+    }
+}
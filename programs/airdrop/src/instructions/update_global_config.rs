@@ -0,0 +1,140 @@
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+
+/// A single change to the distributor set
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum DistributorUpdate {
+    /// Append a new distributor with the given weight and key type to the set
+    Add {
+        distributor: Pubkey,
+        weight: u16,
+        key_type: DistributorKeyType,
+    },
+    /// Remove an existing distributor from the set
+    Remove { distributor: Pubkey },
+    /// Swap an existing distributor for a new key in place, keeping its weight and key type
+    Replace { old_distributor: Pubkey, new_distributor: Pubkey },
+    /// Change the number of distinct signatures required per claim
+    SetThreshold { threshold: u8 },
+    /// Change the weight of an existing distributor
+    SetWeight { distributor: Pubkey, weight: u16 },
+    /// Change the total signed weight required per claim
+    SetRequiredWeight { required_weight: u16 },
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct UpdateGlobalConfig<'info> {
+    /// The authority that manages the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> UpdateGlobalConfig<'info> {
+    pub fn update(&mut self, update: DistributorUpdate) -> Result<()> {
+        DistributorSetUpdate {
+            global_config: &mut self.global_config,
+            #[cfg(feature = "event-cpi")]
+            event_authority: self.event_authority.to_account_info(),
+        }
+        .apply(update)
+    }
+}
+
+/// Applies a change to the global distributor set and emits its event; shared by
+/// `update_global_config` and `update_distributors_by_signature`
+pub(crate) struct DistributorSetUpdate<'a, 'info> {
+    pub global_config: &'a mut Account<'info, GlobalConfig>,
+    #[cfg(feature = "event-cpi")]
+    pub event_authority: AccountInfo<'info>,
+}
+
+impl DistributorSetUpdate<'_, '_> {
+    pub fn apply(&mut self, update: DistributorUpdate) -> Result<()> {
+        let config: &mut GlobalConfig = &mut *self.global_config;
+        let max_distributors = config.max_distributors();
+        let distributors = &mut config.distributors;
+
+        match update {
+            DistributorUpdate::Add {
+                distributor,
+                weight,
+                key_type,
+            } => {
+                validate_distributor_key(&distributor, key_type)?;
+                require!(
+                    find_distributor(distributors, &distributor).is_none(),
+                    AirdropError::DistributorAlreadyExists
+                );
+                require!(
+                    distributors.len() < max_distributors,
+                    AirdropError::TooManyDistributors
+                );
+
+                distributors.push(Distributor {
+                    pubkey: distributor,
+                    weight,
+                    active: true,
+                    key_type,
+                });
+                emit_event!(self, DistributorAdded { distributor, weight });
+            }
+            DistributorUpdate::Remove { distributor } => {
+                let index = find_distributor(distributors, &distributor)
+                    .ok_or(AirdropError::DistributorNotFound)?;
+
+                distributors.remove(index);
+                validate_distributor_set(distributors, config.threshold, config.required_weight)?;
+                emit_event!(self, DistributorRemoved { distributor });
+            }
+            DistributorUpdate::Replace {
+                old_distributor,
+                new_distributor,
+            } => {
+                require!(
+                    find_distributor(distributors, &new_distributor).is_none(),
+                    AirdropError::DistributorAlreadyExists
+                );
+                let index = find_distributor(distributors, &old_distributor)
+                    .ok_or(AirdropError::DistributorNotFound)?;
+                validate_distributor_key(&new_distributor, distributors[index].key_type)?;
+
+                distributors[index].pubkey = new_distributor;
+                emit_event!(self, DistributorReplaced {
+                    old_distributor,
+                    new_distributor,
+                });
+            }
+            DistributorUpdate::SetThreshold { threshold } => {
+                validate_distributor_set(distributors, threshold, config.required_weight)?;
+
+                config.threshold = threshold;
+                emit_event!(self, ThresholdUpdated { threshold });
+            }
+            DistributorUpdate::SetWeight { distributor, weight } => {
+                let index = find_distributor(distributors, &distributor)
+                    .ok_or(AirdropError::DistributorNotFound)?;
+
+                distributors[index].weight = weight;
+                validate_distributor_set(distributors, config.threshold, config.required_weight)?;
+                emit_event!(self, DistributorWeightUpdated { distributor, weight });
+            }
+            DistributorUpdate::SetRequiredWeight { required_weight } => {
+                validate_distributor_set(distributors, config.threshold, required_weight)?;
+
+                config.required_weight = required_weight;
+                emit_event!(self, RequiredWeightUpdated { required_weight });
+            }
+        }
+
+        Ok(())
+    }
+}
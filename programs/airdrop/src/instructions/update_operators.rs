@@ -0,0 +1,60 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+/// A single change to the operator set
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum OperatorUpdate {
+    /// Grant operator rights to a key
+    Add { operator: Pubkey },
+    /// Revoke operator rights from a key
+    Remove { operator: Pubkey },
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct UpdateOperators<'info> {
+    /// The authority that manages the configuration
+    pub authority: Signer<'info>,
+
+    /// The global config PDA
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> UpdateOperators<'info> {
+    pub fn update(&mut self, update: OperatorUpdate) -> Result<()> {
+        let operators = &mut self.global_config.operators;
+
+        match update {
+            OperatorUpdate::Add { operator } => {
+                require!(
+                    !operators.contains(&operator),
+                    AirdropError::OperatorAlreadyExists
+                );
+                require!(
+                    operators.len() < MAX_OPERATORS,
+                    AirdropError::TooManyOperators
+                );
+
+                operators.push(operator);
+                emit_event!(self, OperatorAdded { operator });
+            }
+            OperatorUpdate::Remove { operator } => {
+                let index = operators
+                    .iter()
+                    .position(|o| *o == operator)
+                    .ok_or(AirdropError::OperatorNotFound)?;
+
+                operators.remove(index);
+                emit_event!(self, OperatorRemoved { operator });
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use crate::{constants::*, errors::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(project_nonce: u64)]
+pub struct UpdateProjectMetadata<'info> {
+    /// The project authority or one of its managers
+    pub signer: Signer<'info>,
+
+    /// The project PDA the metadata belongs to
+    #[account(
+        seeds = [PROJECT_SEED_PREFIX, project_nonce.to_le_bytes().as_ref()],
+        bump,
+        constraint = project.is_manager(&signer.key()) @ AirdropError::Unauthorized
+    )]
+    pub project: Account<'info, Project>,
+
+    /// The project's metadata PDA
+    #[account(
+        mut,
+        seeds = [PROJECT_METADATA_SEED_PREFIX, project.key().as_ref()],
+        bump
+    )]
+    pub project_metadata: Account<'info, ProjectMetadata>,
+}
+
+impl<'info> UpdateProjectMetadata<'info> {
+    /// Replaces the project's name, symbol, uri and description
+    pub fn update(&mut self, _project_nonce: u64, metadata: ProjectMetadataArgs) -> Result<()> {
+        metadata.validate()?;
+
+        self.project_metadata.apply(metadata);
+
+        emit_event!(self, ProjectMetadataUpdated {
+            project: self.project.key(),
+            name: self.project_metadata.name.clone(),
+            symbol: self.project_metadata.symbol.clone(),
+            uri: self.project_metadata.uri.clone(),
+        });
+
+        Ok(())
+    }
+}
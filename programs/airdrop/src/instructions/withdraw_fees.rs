@@ -0,0 +1,86 @@
+use crate::{constants::*, errors::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    /// The authority that manages the configuration
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The global config PDA that owns the fee vaults
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ AirdropError::Unauthorized,
+        has_one = fee_treasury
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// The mint of the collected fees
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The token account holding the collected fees for this mint
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = global_config,
+        associated_token::token_program = token_program
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The wallet receiving the fees
+    /// CHECK: Validated against the fee treasury stored in the global config
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    /// The fee treasury's token account (destination of the fees)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = fee_treasury,
+        associated_token::token_program = token_program
+    )]
+    pub fee_treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> WithdrawFees<'info> {
+    /// Sweeps every collected fee for the mint to the fee treasury
+    ///
+    /// `remaining_accounts` carries any extra accounts a Token-2022 transfer hook needs
+    pub fn withdraw_fees(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let amount = self.fee_vault.amount;
+
+        let global_config_bump = get_global_config_bump(&crate::ID);
+        let seeds = &[GLOBAL_CONFIG_SEED, &[global_config_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        transfer_spl(
+            self.token_program.to_account_info(),
+            self.global_config.to_account_info(),
+            self.fee_vault.to_account_info(),
+            self.mint.to_account_info(),
+            self.fee_treasury_token_account.to_account_info(),
+            remaining_accounts,
+            amount,
+            self.mint.decimals,
+            Some(signer_seeds),
+        )?;
+
+        emit_event!(self, FeesWithdrawn {
+            mint: self.mint.key(),
+            amount,
+            fee_treasury: self.fee_treasury.key(),
+        });
+
+        Ok(())
+    }
+}
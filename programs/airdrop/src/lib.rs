@@ -1,12 +1,15 @@
 use anchor_lang::prelude::*;
 
 pub mod errors;
+#[macro_use]
+pub mod events;
 pub mod instructions;
 pub mod state;
 pub mod utils;
 pub mod constants;
 
 use instructions::*;
+use state::*;
 
 declare_id!("H3eYcELNCrf1iTxVukbkfxu1uzuzSbgeZqjAPjhZWQbe");
 
@@ -14,15 +17,339 @@ declare_id!("H3eYcELNCrf1iTxVukbkfxu1uzuzSbgeZqjAPjhZWQbe");
 pub mod airdrop {
     use super::*;
 
-    pub fn create_global_config(ctx: Context<CreateGlobalConfig>, distributor: Pubkey) -> Result<()> {
-        ctx.accounts.create(distributor)
+    pub fn create_global_config(ctx: Context<CreateGlobalConfig>, distributors: Vec<Distributor>, threshold: u8, required_weight: u16) -> Result<()> {
+        ctx.accounts.create(distributors, threshold, required_weight)
     }
 
-    pub fn create_project(ctx: Context<CreateProject>, nonce: u64) -> Result<()> {
-        ctx.accounts.create_project(nonce)
+    pub fn update_global_config(ctx: Context<UpdateGlobalConfig>, update: DistributorUpdate) -> Result<()> {
+        ctx.accounts.update(update)
     }
 
-    pub fn claim(ctx: Context<Claim>, project_nonce: u64, nonce: u64) -> Result<()> {
-        ctx.accounts.claim(project_nonce, nonce)
+    pub fn set_distributor_active(ctx: Context<SetDistributorActive>, distributor: Pubkey, active: bool) -> Result<()> {
+        ctx.accounts.set_active(distributor, active)
+    }
+
+    pub fn expand_distributors(ctx: Context<ExpandDistributors>, additional: u16) -> Result<()> {
+        ctx.accounts.expand(additional)
+    }
+
+    pub fn rotate_distributors(ctx: Context<RotateDistributors>, distributors: Vec<Distributor>, threshold: u8, required_weight: u16, grace_slots: u64) -> Result<()> {
+        ctx.accounts.rotate(distributors, threshold, required_weight, grace_slots)
+    }
+
+    pub fn transfer_authority(ctx: Context<TransferAuthority>, new_authority: Pubkey) -> Result<()> {
+        ctx.accounts.transfer(new_authority)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        ctx.accounts.accept()
+    }
+
+    pub fn close_global_config(ctx: Context<CloseGlobalConfig>, force: bool) -> Result<()> {
+        ctx.accounts.close(force)
+    }
+
+    pub fn migrate_global_config(ctx: Context<MigrateGlobalConfig>) -> Result<()> {
+        ctx.accounts.migrate()
+    }
+
+    pub fn migrate_project(ctx: Context<MigrateProject>, project_nonce: u64) -> Result<()> {
+        ctx.accounts.migrate(project_nonce)
+    }
+
+    pub fn migrate_project_vault<'info>(ctx: Context<'_, '_, 'info, 'info, MigrateProjectVault<'info>>, project_nonce: u64) -> Result<()> {
+        ctx.accounts.migrate(project_nonce, ctx.remaining_accounts)
+    }
+
+    pub fn set_fee_config(ctx: Context<SetFeeConfig>, fee_bps: u16, fee_treasury: Pubkey) -> Result<()> {
+        ctx.accounts.set_fee_config(fee_bps, fee_treasury)
+    }
+
+    pub fn withdraw_fees<'info>(ctx: Context<'_, '_, 'info, 'info, WithdrawFees<'info>>) -> Result<()> {
+        ctx.accounts.withdraw_fees(ctx.remaining_accounts)
+    }
+
+    pub fn add_to_denylist(ctx: Context<AddToDenylist>, recipient: Pubkey) -> Result<()> {
+        ctx.accounts.add(recipient)
+    }
+
+    pub fn remove_from_denylist(ctx: Context<RemoveFromDenylist>, recipient: Pubkey) -> Result<()> {
+        ctx.accounts.remove(recipient)
+    }
+
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+        ctx.accounts.set_guardian(guardian)
+    }
+
+    pub fn update_operators(ctx: Context<UpdateOperators>, update: OperatorUpdate) -> Result<()> {
+        ctx.accounts.update(update)
+    }
+
+    pub fn set_project_creation_mode(ctx: Context<SetProjectCreationMode>, mode: ProjectCreationMode) -> Result<()> {
+        ctx.accounts.set_mode(mode)
+    }
+
+    pub fn add_creator_to_allowlist(ctx: Context<AddCreatorToAllowlist>, creator: Pubkey) -> Result<()> {
+        ctx.accounts.add(creator)
+    }
+
+    pub fn remove_creator_from_allowlist(ctx: Context<RemoveCreatorFromAllowlist>, creator: Pubkey) -> Result<()> {
+        ctx.accounts.remove(creator)
+    }
+
+    pub fn set_deadline_grace(ctx: Context<SetDeadlineGrace>, deadline_grace_secs: u32) -> Result<()> {
+        ctx.accounts.set_deadline_grace(deadline_grace_secs)
+    }
+
+    pub fn set_genesis_hash(ctx: Context<SetGenesisHash>, genesis_hash: [u8; 32]) -> Result<()> {
+        ctx.accounts.set_genesis_hash(genesis_hash)
+    }
+
+    pub fn set_allow_risky_mints(ctx: Context<SetAllowRiskyMints>, allow_risky_mints: bool) -> Result<()> {
+        ctx.accounts.set_allow_risky_mints(allow_risky_mints)
+    }
+
+    pub fn set_max_claim_amount(ctx: Context<SetMaxClaimAmount>, max_claim_amount: Option<u64>) -> Result<()> {
+        ctx.accounts.set_max_claim_amount(max_claim_amount)
+    }
+
+    pub fn set_rate_limit(ctx: Context<SetRateLimit>, max_claims_per_window: u64, rate_limit_window_slots: u64) -> Result<()> {
+        ctx.accounts.set_rate_limit(max_claims_per_window, rate_limit_window_slots)
+    }
+
+    pub fn pause(ctx: Context<Pause>) -> Result<()> {
+        ctx.accounts.pause()
+    }
+
+    pub fn unpause(ctx: Context<Unpause>) -> Result<()> {
+        ctx.accounts.unpause()
+    }
+
+    pub fn set_paused_by_signature(ctx: Context<SetPausedBySignature>, nonce: u64) -> Result<()> {
+        ctx.accounts.set_paused_by_signature(nonce)
+    }
+
+    pub fn update_distributors_by_signature(ctx: Context<UpdateDistributorsBySignature>, nonce: u64) -> Result<()> {
+        ctx.accounts.update_distributors_by_signature(nonce)
+    }
+
+    pub fn create_project(ctx: Context<CreateProject>, start_time: i64, end_time: i64, metadata: ProjectMetadataArgs) -> Result<()> {
+        ctx.accounts.create_project(start_time, end_time, metadata)
+    }
+
+    pub fn skip_project_nonce(ctx: Context<SkipProjectNonce>) -> Result<()> {
+        ctx.accounts.skip()
+    }
+
+    pub fn update_project_metadata(ctx: Context<UpdateProjectMetadata>, project_nonce: u64, metadata: ProjectMetadataArgs) -> Result<()> {
+        ctx.accounts.update(project_nonce, metadata)
+    }
+
+    pub fn fund_project<'info>(ctx: Context<'_, '_, 'info, 'info, FundProject<'info>>, project_nonce: u64, amount: u64) -> Result<()> {
+        ctx.accounts.fund(project_nonce, amount, ctx.remaining_accounts)
+    }
+
+    pub fn fund_rent_pool(ctx: Context<FundRentPool>, project_nonce: u64, amount: u64) -> Result<()> {
+        ctx.accounts.fund_rent_pool(project_nonce, amount)
+    }
+
+    pub fn close_project<'info>(ctx: Context<'_, '_, 'info, 'info, CloseProject<'info>>, project_nonce: u64, archive: bool) -> Result<()> {
+        ctx.accounts.close(project_nonce, archive, ctx.remaining_accounts)
+    }
+
+    pub fn close_nullifier(ctx: Context<CloseNullifier>, project_nonce: u64, nonce: u64) -> Result<()> {
+        ctx.accounts.close_nullifier(project_nonce, nonce)
+    }
+
+    pub fn set_project_nullifier_mode(ctx: Context<SetProjectNullifierMode>, project_nonce: u64, nullifier_mode: NullifierMode) -> Result<()> {
+        ctx.accounts.set_project_nullifier_mode(project_nonce, nullifier_mode)
+    }
+
+    pub fn refund_funder<'info>(ctx: Context<'_, '_, 'info, 'info, RefundFunder<'info>>, project_nonce: u64) -> Result<()> {
+        ctx.accounts.refund(project_nonce, ctx.remaining_accounts)
+    }
+
+    pub fn get_project_status(ctx: Context<GetProjectStatus>, project_nonce: u64) -> Result<ProjectStatus> {
+        ctx.accounts.get_status(project_nonce)
+    }
+
+    pub fn burn_unclaimed(ctx: Context<BurnUnclaimed>, project_nonce: u64) -> Result<()> {
+        ctx.accounts.burn_unclaimed(project_nonce)
+    }
+
+    pub fn set_project_budget(ctx: Context<SetProjectBudget>, project_nonce: u64, budget: u64) -> Result<()> {
+        ctx.accounts.set_budget(project_nonce, budget)
+    }
+
+    pub fn set_project_claim_window(ctx: Context<SetProjectClaimWindow>, project_nonce: u64, start_time: i64, end_time: i64) -> Result<()> {
+        ctx.accounts.set_claim_window(project_nonce, start_time, end_time)
+    }
+
+    pub fn set_project_max_claim_amount(ctx: Context<SetProjectMaxClaimAmount>, project_nonce: u64, max_claim_amount: Option<u64>) -> Result<()> {
+        ctx.accounts.set_max_claim_amount(project_nonce, max_claim_amount)
+    }
+
+    pub fn set_project_max_per_recipient(ctx: Context<SetProjectMaxPerRecipient>, project_nonce: u64, max_per_recipient: Option<u64>) -> Result<()> {
+        ctx.accounts.set_max_per_recipient(project_nonce, max_per_recipient)
+    }
+
+    pub fn set_project_transfer_fee_mode(ctx: Context<SetProjectTransferFeeMode>, project_nonce: u64, gross_up_transfer_fees: bool) -> Result<()> {
+        ctx.accounts.set_transfer_fee_mode(project_nonce, gross_up_transfer_fees)
+    }
+
+    pub fn set_project_fee_override(ctx: Context<SetProjectFeeOverride>, project_nonce: u64, fee_override: Option<FeeOverride>) -> Result<()> {
+        ctx.accounts.set_fee_override(project_nonce, fee_override)
+    }
+
+    pub fn set_project_claim_fee(ctx: Context<SetProjectClaimFee>, project_nonce: u64, claim_fee_lamports: u64) -> Result<()> {
+        ctx.accounts.set_project_claim_fee(project_nonce, claim_fee_lamports)
+    }
+
+    pub fn set_project_frozen(ctx: Context<SetProjectFrozen>, project_nonce: u64, frozen: bool) -> Result<()> {
+        ctx.accounts.set_frozen(project_nonce, frozen)
+    }
+
+    pub fn add_manager(ctx: Context<AddManager>, project_nonce: u64, manager: Pubkey) -> Result<()> {
+        ctx.accounts.add(project_nonce, manager)
+    }
+
+    pub fn remove_manager(ctx: Context<RemoveManager>, project_nonce: u64, manager: Pubkey) -> Result<()> {
+        ctx.accounts.remove(project_nonce, manager)
+    }
+
+    pub fn set_project_low_balance_threshold(ctx: Context<SetProjectLowBalanceThreshold>, project_nonce: u64, low_balance_threshold: u64) -> Result<()> {
+        ctx.accounts.set_low_balance_threshold(project_nonce, low_balance_threshold)
+    }
+
+    pub fn set_project_paused(ctx: Context<SetProjectPaused>, project_nonce: u64, paused: bool) -> Result<()> {
+        ctx.accounts.set_paused(project_nonce, paused)
+    }
+
+    pub fn set_project_phases(ctx: Context<SetProjectPhases>, project_nonce: u64, phases: Vec<Phase>) -> Result<()> {
+        ctx.accounts.set_phases(project_nonce, phases)
+    }
+
+    pub fn set_project_throttle(ctx: Context<SetProjectThrottle>, project_nonce: u64, max_claims_per_window: u64, throttle_window_slots: u64) -> Result<()> {
+        ctx.accounts.set_throttle(project_nonce, max_claims_per_window, throttle_window_slots)
+    }
+
+    pub fn set_project_recipient_must_pay(ctx: Context<SetProjectRecipientMustPay>, project_nonce: u64, recipient_must_pay: bool) -> Result<()> {
+        ctx.accounts.set_recipient_must_pay(project_nonce, recipient_must_pay)
+    }
+
+    pub fn set_project_mint_on_claim(ctx: Context<SetProjectMintOnClaim>, project_nonce: u64, mint_on_claim: bool) -> Result<()> {
+        ctx.accounts.set_mint_on_claim(project_nonce, mint_on_claim)
+    }
+
+    pub fn set_project_ticket(ctx: Context<SetProjectTicket>, project_nonce: u64, ticket: Option<TicketConfig>) -> Result<()> {
+        ctx.accounts.set_ticket(project_nonce, ticket)
+    }
+
+    pub fn set_project_collection_gate(ctx: Context<SetProjectCollectionGate>, project_nonce: u64, collection_gate: Option<CollectionGate>) -> Result<()> {
+        ctx.accounts.set_collection_gate(project_nonce, collection_gate)
+    }
+
+    pub fn set_project_compressed_nft_gate(ctx: Context<SetProjectCompressedNftGate>, project_nonce: u64, compressed_nft_gate: Option<CompressedNftGate>) -> Result<()> {
+        ctx.accounts.set_compressed_nft_gate(project_nonce, compressed_nft_gate)
+    }
+
+    pub fn set_project_attestation_gate(ctx: Context<SetProjectAttestationGate>, project_nonce: u64, attestation_gate: Option<AttestationGate>) -> Result<()> {
+        ctx.accounts.set_attestation_gate(project_nonce, attestation_gate)
+    }
+
+    pub fn set_project_claim_group(ctx: Context<SetProjectClaimGroup>, project_nonce: u64, claim_group: Option<u64>) -> Result<()> {
+        ctx.accounts.set_claim_group(project_nonce, claim_group)
+    }
+
+    pub fn set_project_tiers(ctx: Context<SetProjectTiers>, project_nonce: u64, tiers: Vec<Tier>) -> Result<()> {
+        ctx.accounts.set_tiers(project_nonce, tiers)
+    }
+
+    pub fn set_project_stake_vote_account(ctx: Context<SetProjectStakeVoteAccount>, project_nonce: u64, stake_vote_account: Option<Pubkey>) -> Result<()> {
+        ctx.accounts.set_stake_vote_account(project_nonce, stake_vote_account)
+    }
+
+    pub fn set_project_stake_pool(ctx: Context<SetProjectStakePool>, project_nonce: u64, stake_pool: Option<Pubkey>) -> Result<()> {
+        ctx.accounts.set_stake_pool(project_nonce, stake_pool)
+    }
+
+    pub fn set_project_lock_duration(ctx: Context<SetProjectLockDuration>, project_nonce: u64, lock_duration: i64) -> Result<()> {
+        ctx.accounts.set_lock_duration(project_nonce, lock_duration)
+    }
+
+    pub fn set_project_deadline_grace(ctx: Context<SetProjectDeadlineGrace>, project_nonce: u64, deadline_grace_secs: Option<u32>) -> Result<()> {
+        ctx.accounts.set_deadline_grace(project_nonce, deadline_grace_secs)
+    }
+
+    pub fn set_project_distributors(ctx: Context<SetProjectDistributors>, project_nonce: u64, distributors: Vec<Distributor>) -> Result<()> {
+        ctx.accounts.set_distributors(project_nonce, distributors)
+    }
+
+    pub fn set_project_threshold(ctx: Context<SetProjectThreshold>, project_nonce: u64, threshold: Option<u8>) -> Result<()> {
+        ctx.accounts.set_threshold(project_nonce, threshold)
+    }
+
+    pub fn claim<'info>(ctx: Context<'_, '_, 'info, 'info, Claim<'info>>, project_nonce: u64, nonce: u64, ed25519_ix_index: u8, merkle_claim: Option<MerkleClaim>) -> Result<()> {
+        ctx.accounts.claim(project_nonce, nonce, ed25519_ix_index, merkle_claim, ctx.remaining_accounts)
+    }
+
+    pub fn claim_batch<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimBatch<'info>>, project_nonce: u64, nonces: Vec<u64>) -> Result<()> {
+        ctx.accounts.claim_batch(project_nonce, nonces, ctx.remaining_accounts)
+    }
+
+    pub fn claim_and_delegate<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimAndDelegate<'info>>, project_nonce: u64, nonce: u64) -> Result<()> {
+        ctx.accounts.claim_and_delegate(project_nonce, nonce, ctx.remaining_accounts)
+    }
+
+    pub fn claim_and_stake(ctx: Context<ClaimAndStake>, project_nonce: u64, nonce: u64) -> Result<()> {
+        ctx.accounts.claim_and_stake(project_nonce, nonce)
+    }
+
+    pub fn claim_entry<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimEntry<'info>>, project_nonce: u64, nonce: u64, index: u16) -> Result<()> {
+        ctx.accounts.claim_entry(project_nonce, nonce, index, ctx.remaining_accounts)
+    }
+
+    pub fn claim_for<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimFor<'info>>, project_nonce: u64, nonce: u64) -> Result<()> {
+        ctx.accounts.claim_for(project_nonce, nonce, ctx.remaining_accounts)
+    }
+
+    pub fn claim_idempotent<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimIdempotent<'info>>, project_nonce: u64, nonce: u64) -> Result<()> {
+        ctx.accounts.claim_idempotent(project_nonce, nonce, ctx.bumps.nullifier, ctx.remaining_accounts)
+    }
+
+    pub fn claim_into_pool(ctx: Context<ClaimIntoPool>, project_nonce: u64, nonce: u64) -> Result<()> {
+        ctx.accounts.claim_into_pool(project_nonce, nonce)
+    }
+
+    pub fn claim_partial<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimPartial<'info>>, project_nonce: u64, nonce: u64, claim_amount: u64) -> Result<()> {
+        ctx.accounts.claim_partial(project_nonce, nonce, claim_amount, ctx.remaining_accounts)
+    }
+
+    pub fn claim_vesting<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimVesting<'info>>, project_nonce: u64, nonce: u64) -> Result<()> {
+        ctx.accounts.claim_vesting(project_nonce, nonce, ctx.remaining_accounts)
+    }
+
+    pub fn claim_with_cnft<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimWithCnft<'info>>, project_nonce: u64, leaf: CompressedNftLeaf, proof_len: u8) -> Result<()> {
+        ctx.accounts.claim_with_cnft(project_nonce, leaf, proof_len, ctx.remaining_accounts)
+    }
+
+    pub fn claim_with_nft<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimWithNft<'info>>, project_nonce: u64) -> Result<()> {
+        ctx.accounts.claim_with_nft(project_nonce, ctx.remaining_accounts)
+    }
+
+    pub fn claim_with_ticket<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimWithTicket<'info>>, project_nonce: u64) -> Result<()> {
+        ctx.accounts.claim_with_ticket(project_nonce, ctx.remaining_accounts)
+    }
+
+    pub fn create_stream(ctx: Context<CreateStream>, project_nonce: u64, nonce: u64) -> Result<()> {
+        ctx.accounts.create_stream(project_nonce, nonce)
+    }
+
+    pub fn withdraw_stream<'info>(ctx: Context<'_, '_, 'info, 'info, WithdrawStream<'info>>, project_nonce: u64, nonce: u64) -> Result<()> {
+        ctx.accounts.withdraw_stream(project_nonce, nonce, ctx.remaining_accounts)
+    }
+
+    pub fn unlock<'info>(ctx: Context<'_, '_, 'info, 'info, Unlock<'info>>, project_nonce: u64) -> Result<()> {
+        ctx.accounts.unlock(project_nonce, ctx.remaining_accounts)
     }
 }
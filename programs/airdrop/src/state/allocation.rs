@@ -0,0 +1,73 @@
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+/// Tracks a signed allocation that is claimed in chunks or released on a vesting schedule.
+///
+/// Lives at the grant's nullifier address, so a nonce claimed in chunks can never
+/// also be claimed in full, and vice versa.
+#[account]
+#[derive(InitSpace)]
+pub struct Allocation {
+    /// The project the allocation is claimed from
+    pub project: Pubkey,
+
+    /// The recipient of the allocation
+    pub recipient: Pubkey,
+
+    /// The nonce of the signed grant
+    pub nonce: u64,
+
+    /// The total signed allocation
+    pub total: u64,
+
+    /// The amount claimed from the allocation so far
+    pub claimed_so_far: u64,
+
+    /// Unix timestamp from which a vesting allocation starts releasing; 0 when not vesting
+    pub cliff_ts: i64,
+
+    /// Unix timestamp at which a vesting allocation is fully released; 0 when not vesting
+    pub vest_end_ts: i64,
+}
+
+impl Allocation {
+    /// Whether the allocation is released on a vesting schedule
+    pub fn is_vesting(&self) -> bool {
+        self.vest_end_ts != 0
+    }
+
+    /// Validates a vesting schedule, which must start after the epoch and end after its cliff
+    pub fn validate_schedule(cliff_ts: i64, vest_end_ts: i64) -> Result<()> {
+        require!(
+            cliff_ts > 0 && vest_end_ts > cliff_ts,
+            AirdropError::InvalidVestingSchedule
+        );
+
+        Ok(())
+    }
+
+    /// The amount vested at `now`: nothing before the cliff, then linear until the vesting end
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        if now < self.cliff_ts {
+            0
+        } else if now >= self.vest_end_ts {
+            self.total
+        } else {
+            let elapsed = (now - self.cliff_ts) as u128;
+            let duration = (self.vest_end_ts - self.cliff_ts) as u128;
+            (self.total as u128 * elapsed / duration) as u64
+        }
+    }
+
+    /// Adds a chunk to `claimed_so_far`, rejecting it if it is empty or exceeds the remainder
+    pub fn record_chunk(&mut self, claim_amount: u64) -> Result<()> {
+        let remaining = self.total - self.claimed_so_far;
+        require!(
+            claim_amount > 0 && claim_amount <= remaining,
+            AirdropError::ClaimExceedsAllocation
+        );
+        self.claimed_so_far += claim_amount;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+/// The minimal record a project PDA is shrunk into when closed with `archive`,
+/// so historical campaign totals stay resolvable at the project's address
+#[account]
+#[derive(InitSpace)]
+pub struct ArchivedProject {
+    /// The nonce the project was created with
+    pub nonce: u64,
+
+    /// The mint of the SPL token that was distributed
+    pub mint: Pubkey,
+
+    /// The authority that closed the project
+    pub authority: Pubkey,
+
+    /// The total amount of tokens deposited through `fund_project`
+    pub total_funded: u64,
+
+    /// The total amount claimed from the project (fees included)
+    pub total_claimed: u64,
+
+    /// The number of successful claims against the project
+    pub claim_count: u64,
+
+    /// The slot in which the project was closed
+    pub closed_slot: u64,
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+/// An optional on-chain proof that a recipient claimed from a project, letting other
+/// programs gate follow-on rewards on a campaign claim. Records the latest receipted claim.
+#[account]
+#[derive(InitSpace)]
+pub struct ClaimReceipt {
+    /// The project claimed from
+    pub project: Pubkey,
+
+    /// The wallet that claimed
+    pub recipient: Pubkey,
+
+    /// The amount granted by the claim, phase bonus included
+    pub amount: u64,
+
+    /// The slot at which the claim was made
+    pub slot: u64,
+
+    /// The nonce of the claimed grant
+    pub nonce: u64,
+}
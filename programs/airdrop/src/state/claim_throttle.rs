@@ -0,0 +1,43 @@
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+/// A project's rolling claim counter, smoothing out claim storms by capping the claims
+/// accepted per window of slots
+#[account]
+#[derive(InitSpace)]
+pub struct ClaimThrottle {
+    /// The project whose claims are counted
+    pub project: Pubkey,
+
+    /// The slot at which the current window started
+    pub window_start_slot: u64,
+
+    /// The number of claims accepted in the current window
+    pub claims_this_window: u64,
+}
+
+impl ClaimThrottle {
+    /// Counts a claim against the project's limit, starting a new window once the current one has elapsed
+    pub fn record_claim(
+        &mut self,
+        project: Pubkey,
+        max_claims_per_window: u64,
+        window_slots: u64,
+        current_slot: u64,
+    ) -> Result<()> {
+        self.project = project;
+
+        if current_slot.saturating_sub(self.window_start_slot) >= window_slots {
+            self.window_start_slot = current_slot;
+            self.claims_this_window = 0;
+        }
+
+        require!(
+            self.claims_this_window < max_claims_per_window,
+            AirdropError::ProjectRateLimitExceeded
+        );
+        self.claims_this_window += 1;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+/// Marks a key as approved to create projects while creation is allowlist-only
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorAllowlistEntry {
+    /// The approved project creator
+    pub creator: Pubkey,
+}
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+/// Marks a recipient as barred from claiming; the account's existence is the flag
+#[account]
+#[derive(InitSpace)]
+pub struct DenylistEntry {
+    /// The denylisted recipient
+    pub recipient: Pubkey,
+}
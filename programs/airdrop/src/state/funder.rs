@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// Tracks a single funder's deposits into a project, used to pay out pro-rata refunds
+#[account]
+#[derive(InitSpace)]
+pub struct Funder {
+    /// The project the deposits were made into
+    pub project: Pubkey,
+
+    /// The funder's wallet
+    pub funder: Pubkey,
+
+    /// The total amount deposited through `fund_project`
+    pub deposited: u64,
+}
@@ -1,13 +1,189 @@
+use crate::{constants::*, errors::*};
 use anchor_lang::prelude::*;
 
+/// The signature scheme a distributor signs claims with
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum DistributorKeyType {
+    /// Verified by the Ed25519 precompile
+    Ed25519,
+    /// Verified by the secp256k1 precompile, the key being a left-padded Ethereum address
+    Secp256k1,
+    /// Verified by the secp256r1 precompile (passkeys and secure enclaves), the key being the
+    /// SHA-256 hash of the compressed public key
+    Secp256r1,
+}
+
+/// A distributor key and the weight its signature contributes towards a claim
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct Distributor {
+    /// The distributor public key, or its key ID for secp256k1 and secp256r1 distributors
+    pub pubkey: Pubkey,
+
+    /// The weight this distributor's signature contributes
+    pub weight: u16,
+
+    /// Whether this distributor's signature currently counts; inactive entries keep their position
+    pub active: bool,
+
+    /// The signature scheme the distributor signs with
+    pub key_type: DistributorKeyType,
+}
+
+/// Who may create projects under the global config
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ProjectCreationMode {
+    /// Anyone may create a project
+    Open,
+    /// Only the authority and the operators may create projects
+    OperatorsOnly,
+    /// The authority, the operators and allowlisted creators may create projects
+    AllowlistOnly,
+}
+
 /// Global configuration for the airdrop program
+///
+/// New fields must be appended at the end so `migrate_global_config` can grow
+/// existing accounts and decode the added bytes as zero defaults.
+///
+/// The account stays Borsh-encoded rather than `zero_copy`: distributor sets are
+/// variable-length and grown in place by `expand_distributors`, and the layout is
+/// versioned through `migrate_global_config`, neither of which a fixed `Pod` layout
+/// supports. Large signer sets should be handled by raising the capacity, not by
+/// changing the encoding.
 #[account]
 #[derive(InitSpace)]
 pub struct GlobalConfig {
     /// The authority that can update the configuration
     pub authority: Pubkey,
     
-    /// The expected distributor public key (for Ed25519 signature verification)
-    pub distributor: Pubkey,
+    /// The distributors allowed to sign claims
+    #[max_len(MAX_DISTRIBUTORS)]
+    pub distributors: Vec<Distributor>,
+
+    /// The number of distinct distributor signatures required to authorize a claim
+    pub threshold: u8,
+
+    /// Whether claims and project creation are halted
+    pub paused: bool,
+
+    /// The number of projects created under this config that have not been closed
+    pub project_count: u64,
+
+    /// The distributor set replaced by the last rotation, still accepted during the grace period
+    #[max_len(MAX_DISTRIBUTORS)]
+    pub previous_distributors: Vec<Distributor>,
+
+    /// The threshold that applied to the previous distributor set
+    pub previous_threshold: u8,
+
+    /// The slot at which signatures from the previous distributor set stop being accepted
+    pub previous_distributors_expiry_slot: u64,
+
+    /// The total weight of distinct distributor signatures required to authorize a claim
+    pub required_weight: u16,
+
+    /// The required weight that applied to the previous distributor set
+    pub previous_required_weight: u16,
+
+    /// The authority nominated by the current authority, pending acceptance
+    pub pending_authority: Option<Pubkey>,
+
+    /// The layout version of this account
+    pub version: u8,
+
+    /// The protocol fee taken from each claimed amount, in basis points
+    pub fee_bps: u16,
+
+    /// The wallet that receives withdrawn protocol fees
+    pub fee_treasury: Pubkey,
+
+    /// A key that may pause (but not unpause) the program; `Pubkey::default()` when unset
+    pub guardian: Pubkey,
+
+    /// The number of distributors the account has room for, grown by `expand_distributors`
+    pub distributor_capacity: u16,
+
+    /// Keys allowed to run day-to-day operations such as creating projects
+    #[max_len(MAX_OPERATORS)]
+    pub operators: Vec<Pubkey>,
+
+    /// Who may create projects
+    pub project_creation_mode: ProjectCreationMode,
+
+    /// Upper bound on the amount a single signed claim may transfer, if any
+    pub max_claim_amount: Option<u64>,
+
+    /// The maximum number of claims accepted per rate limit window; 0 disables the limit
+    pub max_claims_per_window: u64,
+
+    /// The length of a rate limit window, in slots
+    pub rate_limit_window_slots: u64,
+
+    /// The slot at which the current rate limit window started
+    pub window_start_slot: u64,
+
+    /// The number of claims accepted in the current rate limit window
+    pub claims_this_window: u64,
+
+    /// Whether projects may be created for mints that fail the mint safety checks
+    pub allow_risky_mints: bool,
+
+    /// Seconds a signed message is still accepted after its deadline, absorbing clock skew
+    /// and transactions that land late
+    pub deadline_grace_secs: u32,
+
+    /// The genesis hash of the cluster this config lives on, which every signed message must
+    /// carry; all zeros until the authority sets it
+    pub genesis_hash: [u8; 32],
+}
+
+impl GlobalConfig {
+    /// Whether the key is the authority or one of the operators
+    pub fn is_operator(&self, key: &Pubkey) -> bool {
+        self.authority == *key || self.operators.contains(key)
+    }
+
+    /// Whether the key may create a project under the current creation mode
+    pub fn can_create_project(&self, key: &Pubkey, allowlisted: bool) -> bool {
+        match self.project_creation_mode {
+            ProjectCreationMode::Open => true,
+            ProjectCreationMode::OperatorsOnly => self.is_operator(key),
+            ProjectCreationMode::AllowlistOnly => allowlisted || self.is_operator(key),
+        }
+    }
+
+    /// Counts a claim against the rate limit, starting a new window once the current one has elapsed
+    pub fn record_claim(&mut self, current_slot: u64) -> Result<()> {
+        if self.max_claims_per_window == 0 {
+            return Ok(());
+        }
+
+        if current_slot.saturating_sub(self.window_start_slot) >= self.rate_limit_window_slots {
+            self.window_start_slot = current_slot;
+            self.claims_this_window = 0;
+        }
+
+        require!(
+            self.claims_this_window < self.max_claims_per_window,
+            AirdropError::RateLimitExceeded
+        );
+        self.claims_this_window += 1;
+
+        Ok(())
+    }
+
+    /// The number of distributors (per set) this account has room for
+    pub fn max_distributors(&self) -> usize {
+        (self.distributor_capacity as usize).max(MAX_DISTRIBUTORS)
+    }
+
+    /// The account size needed to hold `capacity` entries in both the current and previous distributor sets
+    pub fn space(capacity: usize) -> usize {
+        let extra_distributors = capacity.saturating_sub(MAX_DISTRIBUTORS);
+
+        GlobalConfig::DISCRIMINATOR.len()
+            + GlobalConfig::INIT_SPACE
+            + 2 * extra_distributors * Distributor::INIT_SPACE
+    }
 }
 
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+/// Marks a wallet as having claimed from one of the projects of a campaign group, so it
+/// cannot claim from another project of the same group
+#[account]
+#[derive(InitSpace)]
+pub struct GroupClaim {
+    /// The campaign group claimed in
+    pub group_id: u64,
+
+    /// The wallet that claimed
+    pub recipient: Pubkey,
+
+    /// The project of the group the wallet claimed from
+    pub project: Pubkey,
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+/// The record `claim_idempotent` leaves at the grant's nullifier address, so a retry can
+/// be checked against the grant that was actually paid
+#[account]
+#[derive(InitSpace)]
+pub struct IdempotentClaim {
+    /// The nonce of the claimed grant
+    pub nonce: u64,
+
+    /// The recipient the grant was paid to
+    pub recipient: Pubkey,
+
+    /// The mint the grant was paid in
+    pub mint: Pubkey,
+
+    /// The signed amount of the grant
+    pub amount: u64,
+}
+
+impl IdempotentClaim {
+    /// Whether this record was left by the same grant as the one being retried
+    pub fn matches(&self, nonce: u64, recipient: &Pubkey, mint: &Pubkey, amount: u64) -> bool {
+        self.nonce == nonce && self.recipient == *recipient && self.mint == *mint && self.amount == amount
+    }
+}
@@ -0,0 +1,35 @@
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+/// A recipient's claimed tokens held in escrow until the project's lockup ends. The tokens
+/// sit in a token account at the lockup vault PDA, released by `unlock`.
+#[account]
+#[derive(InitSpace)]
+pub struct LockedClaim {
+    /// The project claimed from
+    pub project: Pubkey,
+
+    /// The wallet the tokens are released to
+    pub recipient: Pubkey,
+
+    /// The amount of tokens held in escrow
+    pub amount: u64,
+
+    /// Unix timestamp from which the tokens can be unlocked
+    pub unlock_ts: i64,
+}
+
+impl LockedClaim {
+    /// Adds `amount` to the escrow, pushing the unlock time out to `unlock_ts` if it is later
+    pub fn lock(&mut self, project: Pubkey, recipient: Pubkey, amount: u64, unlock_ts: i64) -> Result<()> {
+        self.project = project;
+        self.recipient = recipient;
+        self.amount = self
+            .amount
+            .checked_add(amount)
+            .ok_or(AirdropError::MathOverflow)?;
+        self.unlock_ts = self.unlock_ts.max(unlock_ts);
+
+        Ok(())
+    }
+}
@@ -1,7 +1,47 @@
+pub mod allocation;
+pub mod archived_project;
 pub mod claim_nullifier;
+pub mod claim_receipt;
+pub mod claim_throttle;
+pub mod creator_allowlist_entry;
+pub mod denylist_entry;
+pub mod funder;
+pub mod group_claim;
+pub mod idempotent_claim;
+pub mod locked_claim;
+pub mod nft_claim;
+pub mod nullifier_shard;
 pub mod global_config;
 pub mod project;
+pub mod project_counter;
+pub mod project_metadata;
+pub mod project_registry;
+pub mod protocol_stats;
+pub mod recipient_claim_state;
+pub mod recipient_history;
+pub mod recipient_nonce;
+pub mod stream;
 
+pub use allocation::*;
+pub use archived_project::*;
 pub use claim_nullifier::*;
+pub use claim_receipt::*;
+pub use claim_throttle::*;
+pub use creator_allowlist_entry::*;
+pub use denylist_entry::*;
+pub use funder::*;
+pub use group_claim::*;
+pub use idempotent_claim::*;
+pub use locked_claim::*;
+pub use nft_claim::*;
+pub use nullifier_shard::*;
 pub use global_config::*;
-pub use project::*;
\ No newline at end of file
+pub use project::*;
+pub use project_counter::*;
+pub use project_metadata::*;
+pub use project_registry::*;
+pub use protocol_stats::*;
+pub use recipient_claim_state::*;
+pub use recipient_history::*;
+pub use recipient_nonce::*;
+pub use stream::*;
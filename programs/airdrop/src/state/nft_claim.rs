@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+/// Marks an NFT of a project's gating collection as claimed, so each NFT pays out once
+/// no matter who holds it
+#[account]
+#[derive(InitSpace)]
+pub struct NftClaim {
+    /// The project claimed from
+    pub project: Pubkey,
+
+    /// The mint of the NFT that was claimed with, or the asset ID of a compressed NFT
+    pub asset: Pubkey,
+
+    /// The holder the claim was paid to
+    pub recipient: Pubkey,
+}
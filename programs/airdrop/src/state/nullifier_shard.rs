@@ -0,0 +1,40 @@
+use crate::{constants::*, errors::*};
+use anchor_lang::prelude::*;
+
+/// A bitmap nullifier covering `NULLIFIER_SHARD_NONCES` consecutive nonces of a project,
+/// used instead of one nullifier PDA per nonce by projects in bitmap nullifier mode
+#[account]
+#[derive(InitSpace)]
+pub struct NullifierShard {
+    /// The project the shard belongs to
+    pub project: Pubkey,
+
+    /// The shard index; the shard covers nonces from `index * NULLIFIER_SHARD_NONCES`
+    pub index: u64,
+
+    /// One bit per nonce, set once the nonce is claimed
+    pub bits: [u8; NULLIFIER_SHARD_NONCES / 8],
+}
+
+impl NullifierShard {
+    /// The index of the shard covering `nonce`
+    pub fn index_for(nonce: u64) -> u64 {
+        nonce / NULLIFIER_SHARD_NONCES as u64
+    }
+
+    /// Sets the nonce's bit, failing if it is already set
+    pub fn consume(&mut self, project: Pubkey, nonce: u64) -> Result<()> {
+        self.project = project;
+        self.index = Self::index_for(nonce);
+
+        let bit = (nonce % NULLIFIER_SHARD_NONCES as u64) as usize;
+        let mask = 1u8 << (bit % 8);
+        require!(
+            self.bits[bit / 8] & mask == 0,
+            AirdropError::InvalidNullifier
+        );
+        self.bits[bit / 8] |= mask;
+
+        Ok(())
+    }
+}
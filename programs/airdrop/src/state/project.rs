@@ -1,6 +1,104 @@
+use crate::{constants::*, errors::*, state::{Distributor, GlobalConfig}};
 use anchor_lang::prelude::*;
 
+/// A project-specific fee tier that supersedes the global fee config
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct FeeOverride {
+    /// The protocol fee taken from each claimed amount, in basis points
+    pub fee_bps: u16,
+
+    /// The wallet whose token account receives this project's fees directly
+    pub fee_treasury: Pubkey,
+}
+
+/// A ticket mint whose tokens `claim_with_ticket` redeems for a fixed amount each
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct TicketConfig {
+    /// The mint of the tickets, one whole token of which is burned per claim
+    pub mint: Pubkey,
+
+    /// The amount released for each ticket redeemed
+    pub amount: u64,
+}
+
+/// A Metaplex verified collection whose holders `claim_with_nft` pays a fixed amount per NFT
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct CollectionGate {
+    /// The collection mint NFTs must be verified members of
+    pub collection: Pubkey,
+
+    /// The amount released for each NFT, once per NFT
+    pub amount: u64,
+}
+
+/// A Bubblegum merkle tree whose compressed NFT holders `claim_with_cnft` pays a fixed amount
+/// per NFT
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct CompressedNftGate {
+    /// The merkle tree the compressed NFTs must be leaves of
+    pub merkle_tree: Pubkey,
+
+    /// The amount released for each compressed NFT, once per NFT
+    pub amount: u64,
+}
+
+/// An attestation `claim` requires the recipient to hold, e.g. a KYC credential
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct AttestationGate {
+    /// The attestation program, laid out like the Solana Attestation Service
+    pub program: Pubkey,
+
+    /// The credential (issuer) the attestation must be issued under
+    pub credential: Pubkey,
+
+    /// The schema the attestation must follow
+    pub schema: Pubkey,
+}
+
+/// A campaign phase with its own timing, claim limit and bonus
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct Phase {
+    /// Unix timestamp from which the phase is active
+    pub start_time: i64,
+
+    /// Unix timestamp from which the phase is no longer active
+    pub end_time: i64,
+
+    /// The maximum number of claims accepted during the phase; 0 means unlimited
+    pub max_claims: u64,
+
+    /// The bonus added on top of each signed amount claimed in the phase, in basis points
+    pub bonus_bps: u16,
+
+    /// The number of claims accepted during the phase so far
+    pub claims: u64,
+}
+
+/// A recipient tier, such as early supporters, with its own claim opening time and bonus
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct Tier {
+    /// Unix timestamp from which recipients in the tier may claim
+    pub earliest_claim_time: i64,
+
+    /// The bonus added on top of each signed amount claimed by the tier, in basis points
+    pub bonus_bps: u16,
+}
+
+/// How a project's `claim` records used nonces
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum NullifierMode {
+    /// One nullifier PDA per nonce
+    Pda,
+    /// One bit per nonce in `NullifierShard` accounts
+    Bitmap,
+    /// A `RecipientNonce` counter per recipient, which each claim must match and advance
+    Sequential,
+}
+
 /// The Project account that holds SPL tokens for distribution
+///
+/// New fields must be appended at the end so `migrate_project` can grow
+/// existing accounts and decode the added bytes as zero defaults.
 #[account]
 #[derive(InitSpace)]
 pub struct Project {
@@ -12,4 +110,319 @@ pub struct Project {
 
     /// The authority that can manage this project
     pub authority: Pubkey,
+
+    /// Project-level distributor set; when non-empty it is used instead of the global set
+    #[max_len(MAX_DISTRIBUTORS)]
+    pub distributors: Vec<Distributor>,
+
+    /// The layout version of this account
+    pub version: u8,
+
+    /// The total amount of tokens deposited through `fund_project`
+    pub total_funded: u64,
+
+    /// Unix timestamp from which claims are accepted
+    pub start_time: i64,
+
+    /// Unix timestamp from which claims are rejected; 0 means the window never closes
+    pub end_time: i64,
+
+    /// The maximum total amount that may be claimed from the project; 0 means uncapped
+    pub budget: u64,
+
+    /// The total amount claimed from the project so far (fees included)
+    pub total_claimed: u64,
+
+    /// The maximum cumulative amount a single recipient may claim, if any
+    pub max_per_recipient: Option<u64>,
+
+    /// Whether claims send extra tokens to cover a Token-2022 transfer fee so the
+    /// recipient receives the full signed amount; otherwise the fee is deducted in transit
+    pub gross_up_transfer_fees: bool,
+
+    /// Set by the global authority to block claims and authority withdrawals for this project
+    pub frozen_by_admin: bool,
+
+    /// A fee tier negotiated for this project, used instead of the global fee config
+    pub fee_override: Option<FeeOverride>,
+
+    /// Keys that may pause the project and update its metadata, but not withdraw or close it
+    #[max_len(MAX_PROJECT_MANAGERS)]
+    pub managers: Vec<Pubkey>,
+
+    /// Set by the project authority or a manager to temporarily block claims
+    pub paused: bool,
+
+    /// Campaign phases, in chronological order; when empty the message's phase is ignored
+    #[max_len(MAX_PHASES)]
+    pub phases: Vec<Phase>,
+
+    /// Deposits tracked in `Funder` accounts that have not yet been refunded
+    pub outstanding_deposits: u64,
+
+    /// The number of distinct distributor signatures this project's claims require,
+    /// overriding the global threshold when set
+    pub threshold: Option<u8>,
+
+    /// The vault balance below which `VaultLowBalance` is emitted; 0 disables the alert
+    pub low_balance_threshold: u64,
+
+    /// The number of successful claims against the project
+    pub claim_count: u64,
+
+    /// The mint's decimals, recorded at creation and checked on every claim transfer
+    pub decimals: u8,
+
+    /// A flat fee in lamports charged to the claim payer and sent to the fee treasury; 0 disables it
+    pub claim_fee_lamports: u64,
+
+    /// Whether nullifiers have been closed, which freezes the ended claim window so the
+    /// signed messages they guarded can never be replayed
+    pub claim_window_sealed: bool,
+
+    /// Lamports held by the project account that claims draw on to reimburse their payer's rent
+    pub rent_pool_lamports: u64,
+
+    /// How `claim` records used nonces; projects not using nullifier PDAs accept no other
+    /// claim instruction
+    pub nullifier_mode: NullifierMode,
+
+    /// Recipient tiers, indexed by the message's tier; when empty the message's tier is ignored
+    #[max_len(MAX_TIERS)]
+    pub tiers: Vec<Tier>,
+
+    /// The vote account `claim_and_stake` delegates claimed SOL to; `None` disables staking claims
+    pub stake_vote_account: Option<Pubkey>,
+
+    /// The SPL stake pool `claim_into_pool` deposits claimed SOL into; `None` disables pool claims
+    pub stake_pool: Option<Pubkey>,
+
+    /// Seconds claimed tokens stay in the recipient's lockup escrow; 0 pays claims out directly
+    pub lock_duration: i64,
+
+    /// The grace period after a message deadline for this project's claims, overriding the
+    /// global grace period when set
+    pub deadline_grace_secs: Option<u32>,
+
+    /// Upper bound on the amount a single signed grant of this project may carry, if any
+    pub max_claim_amount: Option<u64>,
+
+    /// The maximum number of claims accepted per throttle window; 0 disables the throttle
+    pub max_claims_per_window: u64,
+
+    /// The length of a throttle window, in slots
+    pub throttle_window_slots: u64,
+
+    /// Whether `claim` requires the recipient to be the payer, so farming wallets cannot fund
+    /// claims for many Sybil recipients
+    pub recipient_must_pay: bool,
+
+    /// Whether claims mint the paid amount with the project's mint authority instead of
+    /// transferring it out of the vault
+    pub mint_on_claim: bool,
+
+    /// The ticket mint redeemable through `claim_with_ticket`, if any
+    pub ticket: Option<TicketConfig>,
+
+    /// The verified collection whose holders can claim through `claim_with_nft`, if any
+    pub collection_gate: Option<CollectionGate>,
+
+    /// The Bubblegum tree whose compressed NFT holders can claim through `claim_with_cnft`, if any
+    pub compressed_nft_gate: Option<CompressedNftGate>,
+
+    /// The attestation recipients must hold to claim, if any
+    pub attestation_gate: Option<AttestationGate>,
+
+    /// The campaign group the project belongs to, whose wallets may claim from only one of its
+    /// projects (`one_claim_per_wallet_group`), if any
+    pub claim_group: Option<u64>,
+
+    /// Vesting allocations granted through `claim_vesting` that have not yet been released
+    pub outstanding_vesting: u64,
+
+    /// Stream totals created through `create_stream` that have not yet been withdrawn
+    pub outstanding_streamed: u64,
+
+    /// Claimed tokens held in lockup vaults that have not yet been released through `unlock`
+    pub outstanding_locked: u64,
+}
+
+impl Project {
+    /// Validates a claim window, where an `end_time` of 0 leaves it open-ended
+    pub fn validate_claim_window(start_time: i64, end_time: i64) -> Result<()> {
+        require!(
+            end_time == 0 || end_time > start_time,
+            AirdropError::InvalidClaimWindow
+        );
+
+        Ok(())
+    }
+
+    /// Checks that `now` falls within the project's claim window
+    pub fn check_claim_window(&self, now: i64) -> Result<()> {
+        require!(now >= self.start_time, AirdropError::ClaimWindowNotStarted);
+        require!(
+            self.end_time == 0 || now < self.end_time,
+            AirdropError::ClaimWindowEnded
+        );
+
+        Ok(())
+    }
+
+    /// The grace period after a message deadline, in seconds, that applies to this project
+    pub fn deadline_grace_secs(&self, global_config: &GlobalConfig) -> u32 {
+        self.deadline_grace_secs.unwrap_or(global_config.deadline_grace_secs)
+    }
+
+    /// Whether the key is the project authority or one of its managers
+    pub fn is_manager(&self, key: &Pubkey) -> bool {
+        self.authority == *key || self.managers.contains(key)
+    }
+
+    /// The signature threshold for this project's claims, falling back to `default`
+    pub fn threshold_or(&self, default: u8) -> u8 {
+        self.threshold.unwrap_or(default)
+    }
+
+    /// Whether funders may currently withdraw their deposits: before the claim window
+    /// opens or after it has ended
+    pub fn is_refundable(&self, now: i64) -> bool {
+        now < self.start_time || self.has_ended(now)
+    }
+
+    /// The part of a vault balance of `vault_amount` that backs deposits still owed to
+    /// funders, which only `refund_funder` may pay out
+    pub fn deposited_pool(&self, vault_amount: u64) -> u64 {
+        vault_amount.min(self.outstanding_deposits)
+    }
+
+    /// The part of a vault balance of `vault_amount` that is already owed, to funders or to
+    /// recipients with unreleased vesting allocations or open streams, and so must not be
+    /// burned or swept
+    pub fn reserved_pool(&self, vault_amount: u64) -> u64 {
+        vault_amount.min(
+            self.outstanding_deposits
+                .saturating_add(self.outstanding_vesting)
+                .saturating_add(self.outstanding_streamed),
+        )
+    }
+
+    /// Whether the claim window has a fixed end that `now` has reached
+    pub fn has_ended(&self, now: i64) -> bool {
+        self.end_time != 0 && now >= self.end_time
+    }
+
+    /// Validates that phases are within bounds, well-formed and in non-overlapping chronological order
+    pub fn validate_phases(phases: &[Phase]) -> Result<()> {
+        require!(phases.len() <= MAX_PHASES, AirdropError::InvalidPhases);
+
+        for (index, phase) in phases.iter().enumerate() {
+            require!(
+                phase.end_time > phase.start_time
+                    && phase.bonus_bps as u64 <= BPS_DENOMINATOR
+                    && (index == 0 || phase.start_time >= phases[index - 1].end_time),
+                AirdropError::InvalidPhases
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Checks the signed phase against the phase active at `now`, counts the claim against
+    /// its limit and returns the signed amount with the phase bonus added
+    pub fn apply_phase(&mut self, phase: u8, now: i64, amount: u64) -> Result<u64> {
+        if self.phases.is_empty() {
+            return Ok(amount);
+        }
+
+        let index = self
+            .phases
+            .iter()
+            .position(|p| now >= p.start_time && now < p.end_time)
+            .ok_or(AirdropError::NoActivePhase)?;
+        require!(index == phase as usize, AirdropError::PhaseMismatch);
+
+        let active = &mut self.phases[index];
+        require!(
+            active.max_claims == 0 || active.claims < active.max_claims,
+            AirdropError::PhaseClaimLimitReached
+        );
+        active.claims += 1;
+
+        let bonus = (amount as u128 * active.bonus_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        Ok(amount.checked_add(bonus).ok_or(AirdropError::MathOverflow)?)
+    }
+
+    /// Validates that tiers are within bounds with bonuses of at most 100%
+    pub fn validate_tiers(tiers: &[Tier]) -> Result<()> {
+        require!(tiers.len() <= MAX_TIERS, AirdropError::InvalidTiers);
+        for tier in tiers {
+            require!(
+                tier.bonus_bps as u64 <= BPS_DENOMINATOR,
+                AirdropError::InvalidTiers
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the signed tier may claim at `now` and returns the amount with the tier
+    /// bonus added
+    pub fn apply_tier(&self, tier: u8, now: i64, amount: u64) -> Result<u64> {
+        if self.tiers.is_empty() {
+            return Ok(amount);
+        }
+
+        let tier = self
+            .tiers
+            .get(tier as usize)
+            .ok_or(AirdropError::InvalidTiers)?;
+        require!(
+            now >= tier.earliest_claim_time,
+            AirdropError::TierNotOpen
+        );
+
+        let bonus = (amount as u128 * tier.bonus_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        Ok(amount.checked_add(bonus).ok_or(AirdropError::MathOverflow)?)
+    }
+
+    /// Whether the vault balance has dropped below the low-balance threshold, capped at the
+    /// budget still left to claim so a nearly exhausted campaign does not keep alerting
+    pub fn is_low_balance(&self, vault_balance: u64) -> bool {
+        if self.low_balance_threshold == 0 {
+            return false;
+        }
+
+        let floor = if self.budget == 0 {
+            self.low_balance_threshold
+        } else {
+            self.low_balance_threshold
+                .min(self.budget.saturating_sub(self.total_claimed))
+        };
+        vault_balance < floor
+    }
+
+    /// Counts a claim of `amount` against the project, so `claim_count` and `total_claimed`
+    /// always move together
+    pub fn record_claim(&mut self, amount: u64) -> Result<()> {
+        self.record_outflow(amount)?;
+        self.claim_count = self.claim_count.saturating_add(1);
+
+        Ok(())
+    }
+
+    /// Adds an amount leaving the vault to `total_claimed`, rejecting it if it would exceed the budget
+    pub fn record_outflow(&mut self, amount: u64) -> Result<()> {
+        let total_claimed = self
+            .total_claimed
+            .checked_add(amount)
+            .ok_or(AirdropError::MathOverflow)?;
+        require!(
+            self.budget == 0 || total_claimed <= self.budget,
+            AirdropError::BudgetExceeded
+        );
+        self.total_claimed = total_claimed;
+
+        Ok(())
+    }
 }
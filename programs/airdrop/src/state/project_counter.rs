@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+/// Hands out project nonces so callers never have to pick (and collide on) one themselves
+#[account]
+#[derive(InitSpace)]
+pub struct ProjectCounter {
+    /// The number of nonces assigned so far
+    pub count: u64,
+}
+
+impl ProjectCounter {
+    /// The nonce the next project will be created with; nonces start at 1
+    pub fn next_nonce(&self) -> u64 {
+        self.count + 1
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{constants::*, errors::*};
+use anchor_lang::prelude::*;
+
+/// Human-readable information about a project, kept in its own PDA
+#[account]
+#[derive(InitSpace)]
+pub struct ProjectMetadata {
+    /// The project this metadata describes
+    pub project: Pubkey,
+
+    /// The campaign name
+    #[max_len(MAX_PROJECT_NAME_LEN)]
+    pub name: String,
+
+    /// A short ticker-style symbol
+    #[max_len(MAX_PROJECT_SYMBOL_LEN)]
+    pub symbol: String,
+
+    /// A URI pointing to off-chain metadata
+    #[max_len(MAX_PROJECT_URI_LEN)]
+    pub uri: String,
+
+    /// A free-form description of the campaign
+    #[max_len(MAX_PROJECT_DESCRIPTION_LEN)]
+    pub description: String,
+}
+
+/// The metadata fields supplied when creating or updating a project's metadata
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProjectMetadataArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub description: String,
+}
+
+impl ProjectMetadataArgs {
+    /// Validates every field against its maximum length
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.name.len() <= MAX_PROJECT_NAME_LEN
+                && self.symbol.len() <= MAX_PROJECT_SYMBOL_LEN
+                && self.uri.len() <= MAX_PROJECT_URI_LEN
+                && self.description.len() <= MAX_PROJECT_DESCRIPTION_LEN,
+            AirdropError::MetadataTooLong
+        );
+
+        Ok(())
+    }
+}
+
+impl ProjectMetadata {
+    /// Replaces the metadata fields with the given values
+    pub fn apply(&mut self, args: ProjectMetadataArgs) {
+        self.name = args.name;
+        self.symbol = args.symbol;
+        self.uri = args.uri;
+        self.description = args.description;
+    }
+}
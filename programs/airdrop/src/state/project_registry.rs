@@ -0,0 +1,35 @@
+use crate::constants::*;
+use anchor_lang::prelude::*;
+
+/// A project listed in the registry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct ProjectRegistryEntry {
+    /// The project PDA
+    pub project: Pubkey,
+
+    /// The nonce the project was created with
+    pub nonce: u64,
+}
+
+/// One page of the append-only project registry.
+///
+/// Project nonces are assigned sequentially from 1, so page `n` holds the projects with
+/// nonces `n * PROJECT_REGISTRY_PAGE_SIZE + 1` through `(n + 1) * PROJECT_REGISTRY_PAGE_SIZE`.
+/// Entries are never removed; closed projects simply no longer exist at their address.
+#[account]
+#[derive(InitSpace)]
+pub struct ProjectRegistry {
+    /// The index of this page
+    pub page: u64,
+
+    /// The projects listed on this page, in creation order
+    #[max_len(PROJECT_REGISTRY_PAGE_SIZE)]
+    pub entries: Vec<ProjectRegistryEntry>,
+}
+
+impl ProjectRegistry {
+    /// The page a project with the given nonce is listed on
+    pub fn page_for(nonce: u64) -> u64 {
+        nonce.saturating_sub(1) / PROJECT_REGISTRY_PAGE_SIZE as u64
+    }
+}
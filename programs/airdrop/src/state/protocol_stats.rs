@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// Aggregate counters across every project, kept so totals are readable on-chain without an indexer
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolStats {
+    /// The total number of projects ever created
+    pub total_projects: u64,
+
+    /// The total number of successful claims
+    pub total_claims: u64,
+
+    /// The total amount of tokens claimed (fees included), summed across all mints
+    pub total_distributed: u128,
+}
@@ -0,0 +1,45 @@
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+/// Tracks how much a recipient has claimed from a single project across all grants
+#[account]
+#[derive(InitSpace)]
+pub struct RecipientClaimState {
+    /// The project the claims were made against
+    pub project: Pubkey,
+
+    /// The recipient of the claims
+    pub recipient: Pubkey,
+
+    /// The cumulative amount claimed by the recipient (fees included)
+    pub total_claimed: u64,
+}
+
+impl RecipientClaimState {
+    /// Adds the amount to the recipient's cumulative total for the project,
+    /// rejecting it if it would exceed the project's per-recipient maximum
+    pub fn record(
+        &mut self,
+        project: Pubkey,
+        recipient: Pubkey,
+        amount: u64,
+        max_per_recipient: Option<u64>,
+    ) -> Result<()> {
+        self.project = project;
+        self.recipient = recipient;
+
+        let total_claimed = self
+            .total_claimed
+            .checked_add(amount)
+            .ok_or(AirdropError::MathOverflow)?;
+        if let Some(max_per_recipient) = max_per_recipient {
+            require!(
+                total_claimed <= max_per_recipient,
+                AirdropError::RecipientCapExceeded
+            );
+        }
+        self.total_claimed = total_claimed;
+
+        Ok(())
+    }
+}
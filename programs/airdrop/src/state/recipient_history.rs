@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+/// Optional per-recipient claim history for a project, for campaigns that issue repeated
+/// grants and need each wallet's claim cadence on-chain
+#[account]
+#[derive(InitSpace)]
+pub struct RecipientHistory {
+    /// The project claimed from
+    pub project: Pubkey,
+
+    /// The wallet whose claims are counted
+    pub recipient: Pubkey,
+
+    /// The number of claims recorded in the history
+    pub claim_count: u64,
+
+    /// Unix timestamp of the latest recorded claim
+    pub last_claim_ts: i64,
+}
+
+impl RecipientHistory {
+    /// Records a claim made at `timestamp`
+    pub fn record(&mut self, project: Pubkey, recipient: Pubkey, timestamp: i64) {
+        self.project = project;
+        self.recipient = recipient;
+        self.claim_count = self.claim_count.saturating_add(1);
+        self.last_claim_ts = timestamp;
+    }
+}
@@ -0,0 +1,32 @@
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+/// The next nonce a recipient's claim must carry, for projects using sequential nonces
+#[account]
+#[derive(InitSpace)]
+pub struct RecipientNonce {
+    /// The project the counter belongs to
+    pub project: Pubkey,
+
+    /// The recipient whose claims are counted
+    pub recipient: Pubkey,
+
+    /// The nonce the recipient's next claim must carry
+    pub next_nonce: u64,
+}
+
+impl RecipientNonce {
+    /// Consumes `nonce`, failing unless it is the next expected one
+    pub fn consume(&mut self, project: Pubkey, recipient: Pubkey, nonce: u64) -> Result<()> {
+        require!(nonce == self.next_nonce, AirdropError::NonceMismatch);
+
+        self.project = project;
+        self.recipient = recipient;
+        self.next_nonce = self
+            .next_nonce
+            .checked_add(1)
+            .ok_or(AirdropError::MathOverflow)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+/// A signed grant that streams to its recipient every second between its start and end.
+///
+/// Lives at the grant's nullifier address, so a streamed nonce can never also be claimed
+/// through any other claim instruction.
+#[account]
+#[derive(InitSpace)]
+pub struct Stream {
+    /// The project the stream is paid from
+    pub project: Pubkey,
+
+    /// The recipient of the stream
+    pub recipient: Pubkey,
+
+    /// The nonce of the signed grant
+    pub nonce: u64,
+
+    /// The total amount streamed by `end_ts`
+    pub total: u64,
+
+    /// The amount withdrawn so far
+    pub withdrawn: u64,
+
+    /// The amount that accrues every second, the last second also accruing any remainder
+    pub rate_per_second: u64,
+
+    /// Unix timestamp at which the stream starts accruing
+    pub start_ts: i64,
+
+    /// Unix timestamp at which the whole total has accrued
+    pub end_ts: i64,
+
+    /// Unix timestamp of the last withdrawal, or `start_ts` before the first
+    pub last_withdrawn_ts: i64,
+}
+
+impl Stream {
+    /// The amount that accrues per second for a total streamed between `start_ts` and `end_ts`
+    pub fn rate_for(total: u64, start_ts: i64, end_ts: i64) -> Result<u64> {
+        require!(end_ts > start_ts, AirdropError::InvalidVestingSchedule);
+        Ok(total / (end_ts - start_ts) as u64)
+    }
+
+    /// The amount accrued since the last withdrawal as of `now`
+    pub fn withdrawable(&self, now: i64) -> u64 {
+        if now >= self.end_ts {
+            return self.total - self.withdrawn;
+        }
+        let elapsed = now.saturating_sub(self.last_withdrawn_ts.max(self.start_ts)).max(0) as u64;
+        elapsed
+            .saturating_mul(self.rate_per_second)
+            .min(self.total - self.withdrawn)
+    }
+
+    /// Records a withdrawal at `now` of the amount returned by `withdrawable`
+    pub fn record_withdrawal(&mut self, amount: u64, now: i64) -> Result<()> {
+        require!(amount > 0, AirdropError::NothingVested);
+        self.withdrawn = self
+            .withdrawn
+            .checked_add(amount)
+            .ok_or(AirdropError::MathOverflow)?;
+        self.last_withdrawn_ts = now.min(self.end_ts);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use borsh::BorshDeserialize;
+
+/// The Solana Attestation Service program
+pub const SOLANA_ATTESTATION_SERVICE_PROGRAM_ID: Pubkey =
+    pubkey!("22zoJMtdu4tQc2PzL74ZUT7FrwgB1Udec8DdW4yw4BdG");
+
+/// The discriminator of an attestation account in the Solana Attestation Service layout
+const ATTESTATION_DISCRIMINATOR: u8 = 2;
+
+/// An attestation account in the Solana Attestation Service layout, after its discriminator
+#[derive(BorshDeserialize)]
+struct Attestation {
+    nonce: Pubkey,
+    credential: Pubkey,
+    schema: Pubkey,
+    _data: Vec<u8>,
+    _signer: Pubkey,
+    expiry: i64,
+}
+
+/// Whether `attestation` is an unexpired attestation about `subject`, issued by `program`
+/// under `credential` and `schema`.
+///
+/// The attestation must use the Solana Attestation Service account layout, with the
+/// subject's wallet as its nonce. An expiry of 0 never expires.
+pub fn is_valid_attestation(
+    attestation: &AccountInfo,
+    program: &Pubkey,
+    credential: &Pubkey,
+    schema: &Pubkey,
+    subject: &Pubkey,
+    now: i64,
+) -> bool {
+    if attestation.owner != program {
+        return false;
+    }
+
+    let Ok(data) = attestation.try_borrow_data() else {
+        return false;
+    };
+    let Some((&ATTESTATION_DISCRIMINATOR, mut body)) = data.split_first() else {
+        return false;
+    };
+    let Ok(attestation) = Attestation::deserialize(&mut body) else {
+        return false;
+    };
+
+    attestation.nonce == *subject
+        && attestation.credential == *credential
+        && attestation.schema == *schema
+        && (attestation.expiry == 0 || attestation.expiry > now)
+}
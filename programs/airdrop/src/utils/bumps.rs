@@ -18,4 +18,11 @@ pub fn get_project_bump(
     );
     
     project_bump
+}
+
+/// Returns the bump seed of the global config PDA
+pub fn get_global_config_bump(program_id: &Pubkey) -> u8 {
+    let (_, global_config_bump) = Pubkey::find_program_address(&[GLOBAL_CONFIG_SEED], program_id);
+
+    global_config_bump
 }
\ No newline at end of file
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+};
+
+/// The Metaplex Bubblegum program
+pub const BUBBLEGUM_PROGRAM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+
+/// The SPL account compression program holding Bubblegum's merkle trees
+pub const SPL_ACCOUNT_COMPRESSION_PROGRAM_ID: Pubkey =
+    pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+
+/// The Anchor discriminator of the account compression program's `verify_leaf`
+const VERIFY_LEAF_DISCRIMINATOR: [u8; 8] = [124, 220, 22, 223, 104, 10, 250, 224];
+
+/// The version byte of a Bubblegum `LeafSchema::V1` leaf
+const LEAF_SCHEMA_V1: u8 = 1;
+
+/// The asset ID of the compressed NFT minted into `merkle_tree` with leaf nonce `nonce`
+pub fn compressed_asset_id(merkle_tree: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"asset", merkle_tree.as_ref(), nonce.to_le_bytes().as_ref()],
+        &BUBBLEGUM_PROGRAM_ID,
+    )
+    .0
+}
+
+/// The hash of a Bubblegum `LeafSchema::V1` leaf, as stored in its merkle tree
+pub fn compressed_leaf_hash(
+    asset_id: &Pubkey,
+    owner: &Pubkey,
+    delegate: &Pubkey,
+    nonce: u64,
+    data_hash: &[u8; 32],
+    creator_hash: &[u8; 32],
+) -> [u8; 32] {
+    solana_keccak_hasher::hashv(&[
+        &[LEAF_SCHEMA_V1],
+        asset_id.as_ref(),
+        owner.as_ref(),
+        delegate.as_ref(),
+        nonce.to_le_bytes().as_ref(),
+        data_hash.as_ref(),
+        creator_hash.as_ref(),
+    ])
+    .to_bytes()
+}
+
+/// Verifies through the account compression program that `leaf` sits at `index` of
+/// `merkle_tree` under `root`, with `proof` as the tree's proof nodes. Fails the
+/// transaction if it does not.
+pub fn verify_leaf<'info>(
+    compression_program: AccountInfo<'info>,
+    merkle_tree: AccountInfo<'info>,
+    proof: &[AccountInfo<'info>],
+    root: [u8; 32],
+    leaf: [u8; 32],
+    index: u32,
+) -> Result<()> {
+    let mut data = VERIFY_LEAF_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&root);
+    data.extend_from_slice(&leaf);
+    data.extend_from_slice(&index.to_le_bytes());
+
+    let mut accounts = vec![AccountMeta::new_readonly(merkle_tree.key(), false)];
+    accounts.extend(
+        proof
+            .iter()
+            .map(|node| AccountMeta::new_readonly(node.key(), false)),
+    );
+
+    let mut account_infos = vec![merkle_tree];
+    account_infos.extend_from_slice(proof);
+    account_infos.push(compression_program.clone());
+
+    invoke(
+        &Instruction {
+            program_id: compression_program.key(),
+            accounts,
+            data,
+        },
+        &account_infos,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+use crate::{errors::AirdropError, state::{Distributor, DistributorKeyType}, utils::*};
+use anchor_lang::solana_program::sysvar::instructions as ix_sysvar;
+use solana_program::secp256k1_program;
+
+/// Returns the index of the distributor with the given public key
+pub fn find_distributor(distributors: &[Distributor], pubkey: &Pubkey) -> Option<usize> {
+    distributors.iter().position(|d| d.pubkey == *pubkey)
+}
+
+/// Removes repeated signer keys, keeping the first occurrence of each, so a signature entry
+/// repeated in the verification instruction cannot inflate the signer count
+pub fn dedup_signers(signers: Vec<Pubkey>) -> Vec<Pubkey> {
+    let mut unique: Vec<Pubkey> = Vec::with_capacity(signers.len());
+    for signer in signers {
+        if !unique.contains(&signer) {
+            unique.push(signer);
+        }
+    }
+    unique
+}
+
+/// Reduces the signer/message pairs of a signature verification instruction to the claim
+/// message, the one covered by the first signature, and the distinct signers of exactly that
+/// message. Signatures over any other message in the instruction are not counted. A claim
+/// message in the Solana off-chain message format is returned unwrapped.
+pub fn signers_of_claim_message(signed: Vec<(Pubkey, Vec<u8>)>) -> Result<(Vec<Pubkey>, Vec<u8>)> {
+    let mut signed = signed.into_iter();
+    let Some((first_signer, message)) = signed.next() else {
+        return Ok((Vec::new(), Vec::new()));
+    };
+
+    let mut signers = vec![first_signer];
+    signers.extend(signed.filter(|(_, m)| *m == message).map(|(signer, _)| signer));
+    Ok((dedup_signers(signers), unwrap_offchain_message(message)?))
+}
+
+/// Returns the number of active distributors in the set
+pub fn active_count(distributors: &[Distributor]) -> usize {
+    distributors.iter().filter(|d| d.active).count()
+}
+
+/// Returns the combined weight of all active distributors in the set
+pub fn total_weight(distributors: &[Distributor]) -> u64 {
+    distributors
+        .iter()
+        .filter(|d| d.active)
+        .map(|d| d.weight as u64)
+        .sum()
+}
+
+/// Validates and parses the distributor signatures of the instruction immediately preceding
+/// the current one, which may be an Ed25519, a secp256k1 or a secp256r1 verification. Returns the
+/// key type of the signers, their keys and the signed message.
+pub fn verify_distributor_signature(
+    ix_sysvar_account: &AccountInfo,
+) -> Result<(DistributorKeyType, Vec<Pubkey>, Vec<u8>)> {
+    let current_ix_index = ix_sysvar::load_current_index_checked(ix_sysvar_account)
+        .map_err(|_| error!(AirdropError::InvalidInstructionSysvar))?;
+    require!(current_ix_index >= 1, AirdropError::InvalidInstructionSysvar);
+
+    verify_distributor_signature_at(ix_sysvar_account, current_ix_index as usize - 1)
+}
+
+/// Like `verify_distributor_signature`, but for the signature verification instruction at
+/// `sig_ix_index` in the transaction, which must come before the current one
+pub fn verify_distributor_signature_at(
+    ix_sysvar_account: &AccountInfo,
+    sig_ix_index: usize,
+) -> Result<(DistributorKeyType, Vec<Pubkey>, Vec<u8>)> {
+    let sig_ix = ix_sysvar::load_instruction_at_checked(sig_ix_index, ix_sysvar_account)
+        .map_err(|_| error!(AirdropError::InvalidInstructionSysvar))?;
+
+    if sig_ix.program_id == secp256k1_program::id() {
+        let (signers, message) = verify_secp256k1_signature(ix_sysvar_account, sig_ix_index)?;
+        return Ok((DistributorKeyType::Secp256k1, signers, message));
+    }
+    if sig_ix.program_id == SECP256R1_PROGRAM_ID {
+        let (signers, message) = verify_secp256r1_signature(ix_sysvar_account, sig_ix_index)?;
+        return Ok((DistributorKeyType::Secp256r1, signers, message));
+    }
+
+    let (signers, message) = verify_ed25519_signature_at_index(ix_sysvar_account, sig_ix_index)?;
+    Ok((DistributorKeyType::Ed25519, signers, message))
+}
+
+/// Tallies the distinct distributors that signed a message.
+///
+/// Every signer must be a known distributor of the signature's key type; repeated signers
+/// are only counted once and inactive distributors are skipped.
+/// Returns the number of distinct active distributors and the sum of their weights.
+///
+/// # Arguments
+/// * `key_type`     - The signature scheme the signers signed with
+/// * `signers`      - The public keys extracted from the signature instruction
+/// * `distributors` - The distributor set the signers are checked against
+pub fn tally_distributor_signatures(
+    key_type: DistributorKeyType,
+    signers: &[Pubkey],
+    distributors: &[Distributor],
+) -> Result<(usize, u64)> {
+    let mut counted: Vec<usize> = Vec::with_capacity(signers.len());
+    let mut weight: u64 = 0;
+
+    for signer in signers {
+        let index = find_distributor(distributors, signer)
+            .filter(|index| distributors[*index].key_type == key_type)
+            .ok_or(AirdropError::DistributorMismatch)?;
+
+        if distributors[index].active && !counted.contains(&index) {
+            counted.push(index);
+            weight += distributors[index].weight as u64;
+        }
+    }
+
+    Ok((counted.len(), weight))
+}
+
+/// Validates that the signers are distributors of the given set and meet both its
+/// distinct-signer threshold and its required weight
+pub fn validate_distributor_signatures(
+    key_type: DistributorKeyType,
+    signers: &[Pubkey],
+    distributors: &[Distributor],
+    threshold: u8,
+    required_weight: u16,
+) -> Result<()> {
+    let (signature_count, weight) = tally_distributor_signatures(key_type, signers, distributors)?;
+    require!(
+        signature_count >= threshold as usize,
+        AirdropError::ThresholdNotMet
+    );
+    require!(
+        weight >= required_weight as u64,
+        AirdropError::RequiredWeightNotMet
+    );
+
+    Ok(())
+}
+
+/// Validates that a full distributor set is non-empty, within `max_len` and free of duplicate keys
+pub fn validate_distributor_list(distributors: &[Distributor], max_len: usize) -> Result<()> {
+    require!(!distributors.is_empty(), AirdropError::EmptyDistributorSet);
+    require!(
+        distributors.len() <= max_len,
+        AirdropError::TooManyDistributors
+    );
+
+    for (index, distributor) in distributors.iter().enumerate() {
+        require!(
+            find_distributor(&distributors[..index], &distributor.pubkey).is_none(),
+            AirdropError::DistributorAlreadyExists
+        );
+        validate_distributor_key(&distributor.pubkey, distributor.key_type)?;
+    }
+
+    Ok(())
+}
+
+/// Validates that a distributor key is well-formed for its key type
+pub fn validate_distributor_key(pubkey: &Pubkey, key_type: DistributorKeyType) -> Result<()> {
+    require!(
+        key_type != DistributorKeyType::Secp256k1 || is_eth_address_pubkey(pubkey),
+        AirdropError::InvalidDistributorKey
+    );
+
+    Ok(())
+}
+
+/// Validates the threshold and required weight against a distributor set
+pub fn validate_distributor_set(
+    distributors: &[Distributor],
+    threshold: u8,
+    required_weight: u16,
+) -> Result<()> {
+    require!(
+        threshold >= 1 && threshold as usize <= active_count(distributors),
+        AirdropError::InvalidThreshold
+    );
+    require!(
+        required_weight as u64 <= total_weight(distributors),
+        AirdropError::InvalidRequiredWeight
+    );
+
+    Ok(())
+}
@@ -5,12 +5,16 @@ use anchor_lang::solana_program::{
     sysvar::instructions as ix_sysvar,
 };
 use solana_program::ed25519_program;
-use crate::errors::AirdropError;
+use crate::{errors::AirdropError, utils::signers_of_claim_message};
 
 /// Constants for parsing Ed25519 instruction data
-pub const HEADER_LEN: usize = 16;  // fixed-size instruction header
+pub const HEADER_LEN: usize = 16;  // fixed-size instruction header (single signature)
+pub const OFFSETS_START: usize = 2; // signature count byte + padding byte
+pub const OFFSETS_LEN: usize = 14; // size of one serialized offsets entry
 pub const PUBKEY_LEN: usize = 32;  // size of an Ed25519 public key
 pub const SIG_LEN: usize = 64;     // size of an Ed25519 signature
+pub const MAX_SIGNED_MESSAGE_LEN: usize = 1024; // cap on a signed message in strict mode
+pub const THIS_INSTRUCTION: u16 = u16::MAX; // instruction index sentinel for the Ed25519 ix itself
 pub const ED25519_PROGRAM_ID: Pubkey = pubkey!("Ed25519SigVerify111111111111111111111111111");
 
 /// Parsed Ed25519 signature data
@@ -31,12 +35,25 @@ pub fn validate_ed25519_ix(
     ix_sysvar_account: &AccountInfo,
     current_ix_index: usize,
 ) -> Result<Instruction> {
-    // The Ed25519 verification must have run just before this instruction
-    require!(current_ix_index > 0, AirdropError::InvalidInstructionSysvar);
+    load_ed25519_ix(ix_sysvar_account, current_ix_index, 1)
+}
+
+/// Validates that the instruction `distance` positions before the current one is an Ed25519
+/// signature verification instruction, and returns it.
+pub fn load_ed25519_ix(
+    ix_sysvar_account: &AccountInfo,
+    current_ix_index: usize,
+    distance: usize,
+) -> Result<Instruction> {
+    // The Ed25519 verification must have run before this instruction
+    require!(
+        distance >= 1 && current_ix_index >= distance,
+        AirdropError::InvalidInstructionSysvar
+    );
 
-    // Load the immediately preceding instruction (the Ed25519 ix)
+    // Load the Ed25519 ix at the requested position
     let ed_ix = ix_sysvar::load_instruction_at_checked(
-        current_ix_index - 1,
+        current_ix_index - distance,
         ix_sysvar_account,
     )
     .map_err(|_| error!(AirdropError::InvalidInstructionSysvar))?;
@@ -51,21 +68,87 @@ pub fn validate_ed25519_ix(
     Ok(ed_ix)
 }
 
-/// Parses the Ed25519 instruction data format to extract offsets for signature, pubkey, and message
-pub fn parse_ed25519_ix_data(data: &[u8]) -> Result<Ed25519SignatureOffsets> {
+/// Parses the Ed25519 instruction data format to extract offsets for every signature, pubkey, and message
+pub fn parse_ed25519_ix_data(data: &[u8]) -> Result<Vec<Ed25519SignatureOffsets>> {
     // Verify minimum length
     require!(
         data.len() >= HEADER_LEN,
         AirdropError::InvalidInstructionSysvar
     );
 
-    // First byte: number of signatures (must be 1)
+    // First byte: number of signatures (at least one)
     let sig_count = data[0] as usize;
-    require!(sig_count == 1, AirdropError::InvalidInstructionSysvar);
+    require!(sig_count >= 1, AirdropError::InvalidInstructionSysvar);
+
+    // The header holds one offsets entry per signature
+    let header_len = OFFSETS_START + OFFSETS_LEN * sig_count;
+    require!(
+        data.len() >= header_len,
+        AirdropError::InvalidInstructionSysvar
+    );
+
+    let offsets = (0..sig_count)
+        .map(|i| parse_ed25519_offsets(data, OFFSETS_START + OFFSETS_LEN * i, header_len))
+        .collect::<Result<Vec<_>>>()?;
+
+    // Strict payload validation is the default; the `lenient-ed25519` feature opts out
+    #[cfg(not(feature = "lenient-ed25519"))]
+    validate_ed25519_payload(data, header_len, &offsets)?;
+
+    Ok(offsets)
+}
+
+/// Strict validation of an Ed25519 instruction's layout: the padding byte is zero, every
+/// message fits in `MAX_SIGNED_MESSAGE_LEN`, and the regions in the instruction itself
+/// neither overlap each other nor leave trailing bytes after the last of them. Several
+/// signatures may share the exact same region, e.g. one message signed by every signer.
+#[cfg(not(feature = "lenient-ed25519"))]
+fn validate_ed25519_payload(
+    data: &[u8],
+    header_len: usize,
+    offsets: &[Ed25519SignatureOffsets],
+) -> Result<()> {
+    require!(data[OFFSETS_START - 1] == 0, AirdropError::Ed25519PaddingNotZero);
+
+    let mut regions: Vec<(usize, usize)> = Vec::with_capacity(3 * offsets.len());
+    for o in offsets {
+        require!(
+            o.message_data_size <= MAX_SIGNED_MESSAGE_LEN,
+            AirdropError::Ed25519MessageTooLong
+        );
+        for (instruction_index, offset, len) in [
+            (o.signature_instruction_index, o.signature_offset, SIG_LEN),
+            (o.public_key_instruction_index, o.public_key_offset, PUBKEY_LEN),
+            (o.message_instruction_index, o.message_data_offset, o.message_data_size),
+        ] {
+            if instruction_index == THIS_INSTRUCTION {
+                regions.push((offset, offset + len));
+            }
+        }
+    }
+    regions.sort_unstable();
+    regions.dedup();
+
+    // Walk the regions in order, each starting at or after the end of the previous one
+    let mut end = header_len;
+    for (start, region_end) in regions {
+        require!(start >= end, AirdropError::Ed25519RegionOverlap);
+        end = region_end;
+    }
+    require!(data.len() == end, AirdropError::Ed25519TrailingBytes);
 
-    // Helper to read u16 offsets from the header (little-endian)
+    Ok(())
+}
+
+/// Parses and validates a single offsets entry starting at `entry_start`
+fn parse_ed25519_offsets(
+    data: &[u8],
+    entry_start: usize,
+    header_len: usize,
+) -> Result<Ed25519SignatureOffsets> {
+    // Helper to read u16 offsets from the entry (little-endian)
     let read_u16 = |i: usize| -> Result<u16> {
-        let start = 2 + 2 * i;
+        let start = entry_start + 2 * i;
         let end = start + 2;
         let src = data
             .get(start..end)
@@ -84,40 +167,23 @@ pub fn parse_ed25519_ix_data(data: &[u8]) -> Result<Ed25519SignatureOffsets> {
     let message_data_size = read_u16(5)? as usize;
     let message_instruction_index = read_u16(6)?;
 
-    // Enforce that all offsets point to the current instruction's data.
-    // The Ed25519 program uses u16::MAX as a sentinel value for "current instruction".
-    // This prevents the program from accidentally reading signature, public key,
-    // or message bytes from some other instruction in the transaction.
-    let this_ix = u16::MAX;
-    require!(
-        signature_instruction_index == this_ix
-            && public_key_instruction_index == this_ix
-            && message_instruction_index == this_ix,
-        AirdropError::InvalidInstructionSysvar
-    );
-
-    // Ensure all offsets point beyond the 16-byte header,
-    // i.e. into the region containing the signature, public key, and message
-    require!(
-        signature_offset >= HEADER_LEN
-            && public_key_offset >= HEADER_LEN
-            && message_data_offset >= HEADER_LEN,
-        AirdropError::InvalidInstructionSysvar
-    );
-
-    // Bounds checks for signature, pubkey, and message slices
-    require!(
-        data.len() >= signature_offset + SIG_LEN,
-        AirdropError::InvalidInstructionSysvar
-    );
-    require!(
-        data.len() >= public_key_offset + PUBKEY_LEN,
-        AirdropError::InvalidInstructionSysvar
-    );
-    require!(
-        data.len() >= message_data_offset + message_data_size,
-        AirdropError::InvalidInstructionSysvar
-    );
+    // Regions in the Ed25519 instruction itself (the u16::MAX sentinel) must point beyond
+    // the header, i.e. into the region containing the signatures, public keys, and messages,
+    // and lie within its data. Regions in other instructions of the transaction are
+    // bounds-checked when they are loaded through the sysvar.
+    for (instruction_index, offset, len) in [
+        (signature_instruction_index, signature_offset, SIG_LEN),
+        (public_key_instruction_index, public_key_offset, PUBKEY_LEN),
+        (message_instruction_index, message_data_offset, message_data_size),
+    ] {
+        if instruction_index == THIS_INSTRUCTION {
+            require!(offset >= header_len, AirdropError::Ed25519RegionOverlap);
+            require!(
+                data.len() >= offset + len,
+                AirdropError::InvalidInstructionSysvar
+            );
+        }
+    }
 
     Ok(Ed25519SignatureOffsets {
         signature_offset,
@@ -130,37 +196,204 @@ pub fn parse_ed25519_ix_data(data: &[u8]) -> Result<Ed25519SignatureOffsets> {
     })
 }
 
-/// Extracts the public key from Ed25519 instruction data at the specified offset
-pub fn extract_signer_pubkey(data: &[u8], offsets: &Ed25519SignatureOffsets) -> Result<Pubkey> {
-    let pk_slice = &data[offsets.public_key_offset..offsets.public_key_offset + PUBKEY_LEN];
+/// Returns the `len` bytes at `offset` of the instruction an offsets entry references: the
+/// Ed25519 instruction itself for the `THIS_INSTRUCTION` sentinel, or another instruction of
+/// the transaction loaded through the sysvar. The Ed25519 program verified the signature over
+/// these same bytes, so reading them from the referenced instruction is safe.
+fn load_region(
+    ix_sysvar_account: &AccountInfo,
+    ed_ix_data: &[u8],
+    instruction_index: u16,
+    offset: usize,
+    len: usize,
+) -> Result<Vec<u8>> {
+    let region = |data: &[u8]| -> Result<Vec<u8>> {
+        data.get(offset..offset + len)
+            .map(<[u8]>::to_vec)
+            .ok_or(error!(AirdropError::InvalidInstructionSysvar))
+    };
+
+    if instruction_index == THIS_INSTRUCTION {
+        return region(ed_ix_data);
+    }
+
+    let referenced_ix =
+        ix_sysvar::load_instruction_at_checked(instruction_index as usize, ix_sysvar_account)
+            .map_err(|_| error!(AirdropError::InvalidInstructionSysvar))?;
+    region(&referenced_ix.data)
+}
+
+/// Extracts the public key an offsets entry points at
+pub fn extract_signer_pubkey(
+    ix_sysvar_account: &AccountInfo,
+    data: &[u8],
+    offsets: &Ed25519SignatureOffsets,
+) -> Result<Pubkey> {
+    let pk_bytes = load_region(
+        ix_sysvar_account,
+        data,
+        offsets.public_key_instruction_index,
+        offsets.public_key_offset,
+        PUBKEY_LEN,
+    )?;
     let mut pk_arr = [0u8; 32];
-    pk_arr.copy_from_slice(pk_slice);
+    pk_arr.copy_from_slice(&pk_bytes);
     Ok(Pubkey::new_from_array(pk_arr))
 }
 
-/// Extracts the message data from Ed25519 instruction data at the specified offset
-pub fn extract_signed_message<'a>(data: &'a [u8], offsets: &Ed25519SignatureOffsets) -> &'a [u8] {
-    &data[offsets.message_data_offset..offsets.message_data_offset + offsets.message_data_size]
+/// Extracts the message data an offsets entry points at
+pub fn extract_signed_message(
+    ix_sysvar_account: &AccountInfo,
+    data: &[u8],
+    offsets: &Ed25519SignatureOffsets,
+) -> Result<Vec<u8>> {
+    load_region(
+        ix_sysvar_account,
+        data,
+        offsets.message_instruction_index,
+        offsets.message_data_offset,
+        offsets.message_data_size,
+    )
 }
 
-/// Validates and parses an Ed25519 signature, returning the signed message
+/// Validates and parses the Ed25519 signatures, returning the signed message (the one covered
+/// by the first signature) and the public keys of its signers
 pub fn verify_ed25519_signature(
     ix_sysvar_account: &AccountInfo,
-) -> Result<(Pubkey, Vec<u8>)> {
+) -> Result<(Vec<Pubkey>, Vec<u8>)> {
+    verify_ed25519_signature_at(ix_sysvar_account, 1)
+}
+
+/// Like `verify_ed25519_signature`, but for the Ed25519 instruction `distance` positions
+/// before the current one
+pub fn verify_ed25519_signature_at(
+    ix_sysvar_account: &AccountInfo,
+    distance: usize,
+) -> Result<(Vec<Pubkey>, Vec<u8>)> {
+    let signed = verify_ed25519_signed_messages_at(ix_sysvar_account, distance)?;
+
+    // Only the signers of the claim message count, each once
+    signers_of_claim_message(signed)
+}
+
+/// Validates and parses the Ed25519 instruction `distance` positions before the current one,
+/// returning every signer paired with the message it signed, in instruction order
+pub fn verify_ed25519_signed_messages_at(
+    ix_sysvar_account: &AccountInfo,
+    distance: usize,
+) -> Result<Vec<(Pubkey, Vec<u8>)>> {
     // Get current instruction index
     let current_ix_index = ix_sysvar::load_current_index_checked(ix_sysvar_account)
         .map_err(|_| error!(AirdropError::InvalidInstructionSysvar))?;
 
-    // Validate that the previous instruction is an Ed25519 verification
-    let ed_ix = validate_ed25519_ix(ix_sysvar_account, current_ix_index as usize)?;
+    // Validate that the instruction at that distance is an Ed25519 verification
+    let ed_ix = load_ed25519_ix(ix_sysvar_account, current_ix_index as usize, distance)?;
 
     // Parse the Ed25519 instruction data
     let offsets = parse_ed25519_ix_data(&ed_ix.data)?;
 
-    // Extract the public key and message
-    let pubkey = extract_signer_pubkey(&ed_ix.data, &offsets)?;
-    let message = extract_signed_message(&ed_ix.data, &offsets).to_vec();
+    // Pair each signature's public key with the message it covers
+    offsets
+        .iter()
+        .map(|o| {
+            Ok((
+                extract_signer_pubkey(ix_sysvar_account, &ed_ix.data, o)?,
+                extract_signed_message(ix_sysvar_account, &ed_ix.data, o)?,
+            ))
+        })
+        .collect()
+}
 
-    Ok((pubkey, message))
+/// Like `verify_ed25519_signature`, but for the Ed25519 instruction at `ed_ix_index` in the
+/// transaction, which must come before the current one. Lets wallets place compute-budget
+/// or other instructions between the signature verification and the claim.
+pub fn verify_ed25519_signature_at_index(
+    ix_sysvar_account: &AccountInfo,
+    ed_ix_index: usize,
+) -> Result<(Vec<Pubkey>, Vec<u8>)> {
+    let current_ix_index = ix_sysvar::load_current_index_checked(ix_sysvar_account)
+        .map_err(|_| error!(AirdropError::InvalidInstructionSysvar))?;
+    require!(
+        ed_ix_index < current_ix_index as usize,
+        AirdropError::InvalidInstructionSysvar
+    );
+
+    verify_ed25519_signature_at(ix_sysvar_account, current_ix_index as usize - ed_ix_index)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A single-signature payload laid out as the Ed25519 program's own client builds it:
+    /// header, public key, signature, then a 10 byte message. Signatures are not checked here.
+    fn payload() -> Vec<u8> {
+        let pubkey_offset = HEADER_LEN as u16;
+        let signature_offset = pubkey_offset + PUBKEY_LEN as u16;
+        let message_offset = signature_offset + SIG_LEN as u16;
+
+        let mut data = vec![1, 0];
+        for value in [
+            signature_offset,
+            THIS_INSTRUCTION,
+            pubkey_offset,
+            THIS_INSTRUCTION,
+            message_offset,
+            10,
+            THIS_INSTRUCTION,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.resize(message_offset as usize + 10, 7);
+        data
+    }
+
+    /// The same payload with the message region widened to start at the public key
+    fn overlapping_payload() -> Vec<u8> {
+        let mut data = payload();
+        data[10..12].copy_from_slice(&(HEADER_LEN as u16).to_le_bytes());
+        data[12..14].copy_from_slice(&((PUBKEY_LEN + SIG_LEN + 10) as u16).to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn parses_a_well_formed_payload() {
+        assert_eq!(parse_ed25519_ix_data(&payload()).unwrap().len(), 1);
+    }
+
+    #[cfg(not(feature = "lenient-ed25519"))]
+    #[test]
+    fn strict_mode_rejects_loose_layouts() {
+        let mut padded = payload();
+        padded[1] = 1;
+        assert_eq!(
+            parse_ed25519_ix_data(&padded).unwrap_err(),
+            AirdropError::Ed25519PaddingNotZero.into()
+        );
+
+        let mut trailing = payload();
+        trailing.push(0);
+        assert_eq!(
+            parse_ed25519_ix_data(&trailing).unwrap_err(),
+            AirdropError::Ed25519TrailingBytes.into()
+        );
+
+        assert_eq!(
+            parse_ed25519_ix_data(&overlapping_payload()).unwrap_err(),
+            AirdropError::Ed25519RegionOverlap.into()
+        );
+    }
+
+    #[cfg(feature = "lenient-ed25519")]
+    #[test]
+    fn lenient_mode_accepts_loose_layouts() {
+        let mut padded = payload();
+        padded[1] = 1;
+        let mut trailing = payload();
+        trailing.push(0);
+
+        for data in [padded, trailing, overlapping_payload()] {
+            assert_eq!(parse_ed25519_ix_data(&data).unwrap().len(), 1);
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use anchor_lang::Event;
+
+/// The seed of the event authority PDA `#[event_cpi]` adds to an instruction's accounts
+const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// Emits `event` as the data of a self-CPI signed by the event authority, like `emit_cpi!`
+/// but usable from handlers that have no `ctx` in scope
+pub fn emit_event_cpi<E: Event>(event_authority: &AccountInfo, event: &E) -> Result<()> {
+    let (_, bump) = Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &crate::ID);
+
+    let mut data = EVENT_IX_TAG_LE.to_vec();
+    data.extend(event.data());
+
+    invoke_signed(
+        &Instruction::new_with_bytes(
+            crate::ID,
+            &data,
+            vec![AccountMeta::new_readonly(event_authority.key(), true)],
+        ),
+        std::slice::from_ref(event_authority),
+        &[&[EVENT_AUTHORITY_SEED, &[bump]]],
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, errors::AirdropError};
+
+/// Calculates the fee taken from `amount` at `fee_bps` basis points, rounding down
+pub fn calculate_fee(amount: u64, fee_bps: u16) -> Result<u64> {
+    let fee = (amount as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(AirdropError::MathOverflow)?
+        / BPS_DENOMINATOR as u128;
+
+    Ok(fee as u64)
+}
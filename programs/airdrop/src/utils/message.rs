@@ -1,48 +1,157 @@
-use crate::{constants::*, errors::*};
+use crate::{constants::*, errors::*, instructions::DistributorUpdate, state::GlobalConfig};
+use crate::utils::verify_ed25519_signature;
 use anchor_lang::prelude::*;
-use borsh::BorshDeserialize;
 
-/// Generic domain fields for all signed messages
-#[derive(BorshDeserialize, Clone)]
-pub struct MessageDomain {
-    pub program_id: Pubkey,
-    pub version: u8,
-    pub nonce: u64,
-    pub deadline: i64,
+pub use airdrop_message::{
+    merkle_leaf, merkle_root_from_proof, MessageDomain, MessageKind, VersionedAirdropMessage,
+};
+use airdrop_message::AirdropMessage;
+
+/// Decodes a claim message, dispatching on its layout tag and upgrading messages signed
+/// under an earlier layout into the current one
+pub fn decode_airdrop_message(message: &[u8]) -> Result<AirdropMessage> {
+    let versioned = VersionedAirdropMessage::from_bytes(message)
+        .map_err(|_| AirdropError::InvalidMessage)?;
+    upgrade_airdrop_message(versioned)
+}
+
+/// Decodes a batch of claim messages like `decode_airdrop_message`
+pub fn decode_airdrop_batch(message: &[u8]) -> Result<Vec<AirdropMessage>> {
+    VersionedAirdropMessage::batch_from_bytes(message)
+        .map_err(|_| AirdropError::InvalidMessage)?
+        .into_iter()
+        .map(upgrade_airdrop_message)
+        .collect()
+}
+
+/// Upgrades a tagged claim message into the current layout, once its domain is checked to
+/// carry the version of the layout it is tagged with
+pub fn upgrade_airdrop_message(versioned: VersionedAirdropMessage) -> Result<AirdropMessage> {
+    require!(
+        versioned.domain().version == versioned.version(),
+        AirdropError::VersionMismatch
+    );
+    Ok(versioned.into_latest())
+}
+
+/// Checks the version of a message whose layout is not tagged, which only exists at the
+/// current message version
+pub fn validate_untagged_version(domain: &MessageDomain) -> Result<()> {
+    require!(domain.version == VERSION, AirdropError::VersionMismatch);
+    Ok(())
+}
+
+/// A configuration change approved by the global authority's signature, so the authority
+/// can stay in cold storage while anyone submits the change
+#[derive(AnchorDeserialize)]
+pub struct AdminMessage {
+    pub action: AdminAction,
+    pub domain: MessageDomain,
+}
+
+/// The configuration change an `AdminMessage` approves
+#[derive(AnchorDeserialize)]
+pub enum AdminAction {
+    /// Halt or resume claims and project creation
+    SetPaused { paused: bool },
+    /// Apply a change to the global distributor set
+    UpdateDistributors(DistributorUpdate),
+}
+
+/// Verifies that the global authority signed the admin message of the Ed25519 instruction
+/// immediately preceding the current one, and returns the approved action once the
+/// message's domain is checked against `nonce`
+pub fn verify_admin_message(
+    ix_sysvar_account: &AccountInfo,
+    config: &GlobalConfig,
+    nonce: u64,
+) -> Result<AdminAction> {
+    let (signers, message) = verify_ed25519_signature(ix_sysvar_account)?;
+    require!(
+        signers.contains(&config.authority),
+        AirdropError::Unauthorized
+    );
+
+    let admin_msg = AdminMessage::try_from_slice(&message)
+        .map_err(|_| AirdropError::InvalidMessage)?;
+    validate_untagged_version(&admin_msg.domain)?;
+    validate_message_domain(
+        &admin_msg.domain,
+        MessageKind::AdminOp,
+        nonce,
+        &config.genesis_hash,
+        config.deadline_grace_secs,
+    )?;
+
+    Ok(admin_msg.action)
 }
 
 /// Validates the generic domain fields of a signed message.
 ///
 /// Ensures:
 /// - The message was intended for this program (program_id matches crate::ID)
-/// - The message version matches the expected version
-/// - The current unix timestamp has not passed the message deadline
+/// - The current unix timestamp has not passed the message deadline plus the grace period
+/// - The message names the genesis hash of this cluster
+/// - The message is of the kind the instruction redeems
 /// - The message nonce matches the expected nonce
 ///
 /// # Arguments
-/// * `domain` - The generic message domain fields to validate
-/// * `nonce`  - The expected nonce for the current instruction used to derive the nullifier PDA
-pub fn validate_message_domain(domain: &MessageDomain, nonce: u64) -> Result<()> {
+/// * `domain`       - The generic message domain fields to validate
+/// * `kind`         - The kind of message the current instruction redeems
+/// * `nonce`        - The expected nonce for the current instruction used to derive the nullifier PDA
+/// * `genesis_hash` - The genesis hash of this cluster, as recorded in the global config
+/// * `grace_secs`   - Seconds the message is still accepted after its deadline
+pub fn validate_message_domain(
+    domain: &MessageDomain,
+    kind: MessageKind,
+    nonce: u64,
+    genesis_hash: &[u8; 32],
+    grace_secs: u32,
+) -> Result<()> {
+    validate_message_envelope(domain, kind, genesis_hash, grace_secs)?;
+
+    // Validate the nonce matches the instruction nonce
+    require!(
+        domain.nonce == nonce,
+        AirdropError::NonceMismatch
+    );
+
+    Ok(())
+}
+
+/// Validates the program id, cluster, kind and deadline of a signed message, for messages
+/// whose nonces are carried per entry rather than by the domain.
+///
+/// The version is not checked here: it depends on the layout the message was decoded as,
+/// so `upgrade_airdrop_message` checks it per layout and `validate_untagged_version` for
+/// layouts that are not tagged.
+pub fn validate_message_envelope(
+    domain: &MessageDomain,
+    kind: MessageKind,
+    genesis_hash: &[u8; 32],
+    grace_secs: u32,
+) -> Result<()> {
     // Validate the program_id matches
     require!(
         domain.program_id == crate::ID,
         AirdropError::ProgramIdMismatch
     );
 
-    // Validate the version matches 
-    require!(domain.version == VERSION, AirdropError::VersionMismatch);
-
-    // Validate the deadline hasn't expired
-    let clock = Clock::get()?;
+    // Validate the message was signed for this cluster, so it cannot be replayed on another
+    // cluster where the program is deployed at the same address
     require!(
-        clock.unix_timestamp <= domain.deadline,
-        AirdropError::DeadlineExpired
+        domain.genesis_hash == *genesis_hash,
+        AirdropError::GenesisHashMismatch
     );
 
-    // Validate the nonce matches the instruction nonce
+    // Validate the message authorizes the operation being performed
+    require!(domain.kind == kind, AirdropError::MessageKindMismatch);
+
+    // Validate the deadline hasn't expired, allowing for the grace period
+    let clock = Clock::get()?;
     require!(
-        domain.nonce == nonce,
-        AirdropError::NonceMismatch
+        clock.unix_timestamp <= domain.deadline.saturating_add(grace_secs as i64),
+        AirdropError::DeadlineExpired
     );
 
     Ok(())
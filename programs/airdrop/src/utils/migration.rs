@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::utils::transfer_native;
+
+/// Grows an account to `new_len` bytes, topping up its rent exemption from `payer`.
+///
+/// The added bytes are zero-initialized, so fields appended to the end of an account
+/// layout decode as their zero defaults. Accounts already at least `new_len` bytes
+/// long are left untouched.
+pub fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    if account.data_len() >= new_len {
+        return Ok(());
+    }
+
+    let required_lamports = Rent::get()?.minimum_balance(new_len);
+    let shortfall = required_lamports.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        transfer_native(payer, account, shortfall, None)?;
+    }
+
+    account.resize(new_len)?;
+
+    Ok(())
+}
+
+/// Shrinks an account owned by this program to `new_len` bytes, returning the rent
+/// no longer needed for exemption to `destination`.
+pub fn shrink_account<'info>(
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    account.resize(new_len)?;
+
+    let excess = account
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(new_len));
+    if excess > 0 {
+        **account.try_borrow_mut_lamports()? -= excess;
+        **destination.try_borrow_mut_lamports()? += excess;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        non_transferable::NonTransferable, permanent_delegate::PermanentDelegate,
+        BaseStateWithExtensions, StateWithExtensions,
+    },
+};
+
+/// Whether the mint lets someone other than the holder move or lock recipients' tokens:
+/// an active freeze authority, a Token-2022 permanent delegate, or the non-transferable
+/// extension that would strand airdropped tokens
+pub fn is_risky_mint(mint: &AccountInfo) -> Result<bool> {
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+
+    if mint_state.base.freeze_authority.is_some() {
+        return Ok(true);
+    }
+
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(false);
+    }
+
+    let has_permanent_delegate = mint_state
+        .get_extension::<PermanentDelegate>()
+        .is_ok_and(|extension| Option::<Pubkey>::from(extension.delegate).is_some());
+    let is_non_transferable = mint_state.get_extension::<NonTransferable>().is_ok();
+
+    Ok(has_permanent_delegate || is_non_transferable)
+}
+
+/// Whether the mint is wrapped SOL under either token program
+pub fn is_native_mint(mint: &Pubkey) -> bool {
+    *mint == anchor_spl::token::spl_token::native_mint::ID
+        || *mint == spl_token_2022::native_mint::ID
+}
@@ -1,9 +1,37 @@
 pub mod ed25519;
+pub mod secp256k1;
+pub mod secp256r1;
 pub mod transfer;
 pub mod bumps;
 pub mod message;
+pub mod distributors;
+pub mod migration;
+pub mod fees;
+pub mod accounts;
+pub mod mint;
+pub mod stake_pool;
+pub mod token_metadata;
+pub mod compression;
+pub mod attestation;
+pub mod offchain_message;
+#[cfg(feature = "event-cpi")]
+pub mod event_cpi;
 
 pub use ed25519::*;
+pub use secp256k1::*;
+pub use secp256r1::*;
 pub use transfer::*;
 pub use bumps::*;
-pub use message::*;
\ No newline at end of file
+pub use message::*;
+pub use distributors::*;
+pub use migration::*;
+pub use fees::*;
+pub use accounts::*;
+pub use mint::*;
+pub use stake_pool::*;
+pub use token_metadata::*;
+pub use compression::*;
+pub use attestation::*;
+pub use offchain_message::*;
+#[cfg(feature = "event-cpi")]
+pub use event_cpi::*;
\ No newline at end of file
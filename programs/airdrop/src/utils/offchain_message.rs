@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use crate::errors::AirdropError;
+
+/// The signing domain every Solana off-chain message starts with
+pub const OFFCHAIN_MESSAGE_SIGNING_DOMAIN: &[u8; 16] = b"\xffsolana offchain";
+
+/// Constants for parsing a version 0 off-chain message header
+const OFFCHAIN_HEADER_VERSION: u8 = 0;
+const OFFCHAIN_MAX_MESSAGE_FORMAT: u8 = 2; // extended UTF-8
+const OFFCHAIN_SIGNER_LEN: usize = 32;
+
+/// Returns the payload of a signed message, unwrapping it from the Solana off-chain message
+/// format when it starts with the off-chain signing domain, so distributors can sign claims
+/// through a wallet's standard `signMessage` flow. Other messages are returned unchanged.
+///
+/// A wrapped message must be a version 0 off-chain message whose application domain is this
+/// program's id, listing at least one signer, and whose declared length covers exactly the
+/// rest of the message. The body is the Borsh-encoded payload, so its text encoding is not
+/// checked.
+pub fn unwrap_offchain_message(message: Vec<u8>) -> Result<Vec<u8>> {
+    let Some(rest) = message.strip_prefix(OFFCHAIN_MESSAGE_SIGNING_DOMAIN.as_slice()) else {
+        return Ok(message);
+    };
+
+    // Header: version, application domain, message format and signer count
+    let (&[version], rest) = rest
+        .split_first_chunk::<1>()
+        .ok_or(AirdropError::InvalidOffchainMessage)?;
+    require!(
+        version == OFFCHAIN_HEADER_VERSION,
+        AirdropError::InvalidOffchainMessage
+    );
+    let (application_domain, rest) = rest
+        .split_first_chunk::<32>()
+        .ok_or(AirdropError::InvalidOffchainMessage)?;
+    require!(
+        *application_domain == crate::ID.to_bytes(),
+        AirdropError::InvalidOffchainMessage
+    );
+    let (&[format, signer_count], rest) = rest
+        .split_first_chunk::<2>()
+        .ok_or(AirdropError::InvalidOffchainMessage)?;
+    require!(
+        format <= OFFCHAIN_MAX_MESSAGE_FORMAT && signer_count >= 1,
+        AirdropError::InvalidOffchainMessage
+    );
+
+    // Signer keys, then the little-endian body length
+    let rest = rest
+        .get(signer_count as usize * OFFCHAIN_SIGNER_LEN..)
+        .ok_or(AirdropError::InvalidOffchainMessage)?;
+    let (length, body) = rest
+        .split_first_chunk::<2>()
+        .ok_or(AirdropError::InvalidOffchainMessage)?;
+    require!(
+        u16::from_le_bytes(*length) as usize == body.len(),
+        AirdropError::InvalidOffchainMessage
+    );
+
+    Ok(body.to_vec())
+}
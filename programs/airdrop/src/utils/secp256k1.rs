@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as ix_sysvar;
+use solana_program::secp256k1_program;
+use crate::{errors::AirdropError, utils::signers_of_claim_message};
+
+/// Constants for parsing secp256k1 instruction data
+pub const SECP256K1_OFFSETS_START: usize = 1; // signature count byte
+pub const SECP256K1_OFFSETS_LEN: usize = 11; // size of one serialized offsets entry
+pub const ETH_ADDRESS_LEN: usize = 20;       // size of an Ethereum address
+pub const SECP256K1_SIG_LEN: usize = 65;     // signature plus recovery id
+
+/// The zero bytes padding an Ethereum address to the size of a `Pubkey`
+const ETH_ADDRESS_PADDING: usize = 32 - ETH_ADDRESS_LEN;
+
+/// Parsed secp256k1 signature data
+#[derive(Debug, Clone)]
+pub struct Secp256k1SignatureOffsets {
+    pub signature_offset: usize,
+    pub eth_address_offset: usize,
+    pub message_data_offset: usize,
+    pub message_data_size: usize,
+}
+
+/// The `Pubkey` a secp256k1 distributor is stored as: its Ethereum address, left-padded
+/// with zeros like an address widened to 32 bytes in the EVM
+pub fn eth_address_pubkey(address: &[u8; ETH_ADDRESS_LEN]) -> Pubkey {
+    let mut bytes = [0u8; 32];
+    bytes[ETH_ADDRESS_PADDING..].copy_from_slice(address);
+    Pubkey::new_from_array(bytes)
+}
+
+/// Whether the key is a left-padded Ethereum address
+pub fn is_eth_address_pubkey(pubkey: &Pubkey) -> bool {
+    pubkey.as_ref()[..ETH_ADDRESS_PADDING].iter().all(|byte| *byte == 0)
+}
+
+/// Parses the secp256k1 instruction data format to extract offsets for every signature,
+/// Ethereum address and message, which must all lie in the instruction at `ix_index`
+pub fn parse_secp256k1_ix_data(data: &[u8], ix_index: u8) -> Result<Vec<Secp256k1SignatureOffsets>> {
+    // First byte: number of signatures (at least one)
+    let sig_count = *data.first().ok_or(AirdropError::InvalidInstructionSysvar)? as usize;
+    require!(sig_count >= 1, AirdropError::InvalidInstructionSysvar);
+
+    // The header holds one offsets entry per signature
+    let header_len = SECP256K1_OFFSETS_START + SECP256K1_OFFSETS_LEN * sig_count;
+    require!(
+        data.len() >= header_len,
+        AirdropError::InvalidInstructionSysvar
+    );
+
+    (0..sig_count)
+        .map(|i| {
+            let entry = &data[SECP256K1_OFFSETS_START + SECP256K1_OFFSETS_LEN * i..];
+            let read_u16 = |at: usize| u16::from_le_bytes([entry[at], entry[at + 1]]) as usize;
+
+            let offsets = Secp256k1SignatureOffsets {
+                signature_offset: read_u16(0),
+                eth_address_offset: read_u16(3),
+                message_data_offset: read_u16(6),
+                message_data_size: read_u16(8),
+            };
+
+            // Enforce that all offsets point into this instruction's data, past the header
+            require!(
+                entry[2] == ix_index && entry[5] == ix_index && entry[10] == ix_index,
+                AirdropError::InvalidInstructionSysvar
+            );
+            require!(
+                offsets.signature_offset >= header_len
+                    && offsets.eth_address_offset >= header_len
+                    && offsets.message_data_offset >= header_len,
+                AirdropError::InvalidInstructionSysvar
+            );
+
+            // Bounds checks for signature, address, and message slices
+            require!(
+                data.len() >= offsets.signature_offset + SECP256K1_SIG_LEN
+                    && data.len() >= offsets.eth_address_offset + ETH_ADDRESS_LEN
+                    && data.len() >= offsets.message_data_offset + offsets.message_data_size,
+                AirdropError::InvalidInstructionSysvar
+            );
+
+            Ok(offsets)
+        })
+        .collect()
+}
+
+/// Validates and parses the secp256k1 signatures of the instruction at `secp_ix_index`,
+/// which must come before the current one, returning the signers as Ethereum address keys
+/// and the signed message
+pub fn verify_secp256k1_signature(
+    ix_sysvar_account: &AccountInfo,
+    secp_ix_index: usize,
+) -> Result<(Vec<Pubkey>, Vec<u8>)> {
+    let current_ix_index = ix_sysvar::load_current_index_checked(ix_sysvar_account)
+        .map_err(|_| error!(AirdropError::InvalidInstructionSysvar))?;
+
+    // The secp256k1 verification must have run before this instruction
+    require!(
+        secp_ix_index < current_ix_index as usize,
+        AirdropError::InvalidInstructionSysvar
+    );
+    let secp_ix = ix_sysvar::load_instruction_at_checked(secp_ix_index, ix_sysvar_account)
+        .map_err(|_| error!(AirdropError::InvalidInstructionSysvar))?;
+    require!(
+        secp_ix.program_id == secp256k1_program::id(),
+        AirdropError::BadSecp256k1Program
+    );
+    require!(secp_ix.accounts.is_empty(), AirdropError::BadSecp256k1Accounts);
+
+    let ix_index = u8::try_from(secp_ix_index).map_err(|_| AirdropError::InvalidInstructionSysvar)?;
+    let offsets = parse_secp256k1_ix_data(&secp_ix.data, ix_index)?;
+
+    // Pair each signer's address with the message it signed
+    let signed = offsets
+        .iter()
+        .map(|o| {
+            let mut address = [0u8; ETH_ADDRESS_LEN];
+            address.copy_from_slice(
+                &secp_ix.data[o.eth_address_offset..o.eth_address_offset + ETH_ADDRESS_LEN],
+            );
+            let message = secp_ix.data
+                [o.message_data_offset..o.message_data_offset + o.message_data_size]
+                .to_vec();
+            (eth_address_pubkey(&address), message)
+        })
+        .collect();
+
+    // Only the signers of the claim message count, each once
+    signers_of_claim_message(signed)
+}
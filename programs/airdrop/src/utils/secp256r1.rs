@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as ix_sysvar;
+use solana_program::hash::hash;
+use crate::{errors::AirdropError, utils::signers_of_claim_message};
+
+/// Constants for parsing secp256r1 instruction data
+pub const SECP256R1_OFFSETS_START: usize = 2; // signature count byte + padding byte
+pub const SECP256R1_OFFSETS_LEN: usize = 14; // size of one serialized offsets entry
+pub const SECP256R1_PUBKEY_LEN: usize = 33;  // size of a compressed secp256r1 public key
+pub const SECP256R1_SIG_LEN: usize = 64;     // size of a secp256r1 signature
+pub const SECP256R1_PROGRAM_ID: Pubkey = pubkey!("Secp256r1SigVerify1111111111111111111111111");
+
+/// Parsed secp256r1 signature data
+#[derive(Debug, Clone)]
+pub struct Secp256r1SignatureOffsets {
+    pub signature_offset: usize,
+    pub public_key_offset: usize,
+    pub message_data_offset: usize,
+    pub message_data_size: usize,
+}
+
+/// The `Pubkey` a secp256r1 (passkey) distributor is stored as: the SHA-256 hash of its
+/// 33-byte compressed public key, which does not fit a `Pubkey` itself
+pub fn secp256r1_key_id(compressed_pubkey: &[u8]) -> Pubkey {
+    Pubkey::new_from_array(hash(compressed_pubkey).to_bytes())
+}
+
+/// Parses the secp256r1 instruction data format to extract offsets for every signature,
+/// public key and message, which must all lie in the instruction itself
+pub fn parse_secp256r1_ix_data(data: &[u8]) -> Result<Vec<Secp256r1SignatureOffsets>> {
+    // First byte: number of signatures (at least one)
+    let sig_count = *data.first().ok_or(AirdropError::InvalidInstructionSysvar)? as usize;
+    require!(sig_count >= 1, AirdropError::InvalidInstructionSysvar);
+
+    // The header holds one offsets entry per signature
+    let header_len = SECP256R1_OFFSETS_START + SECP256R1_OFFSETS_LEN * sig_count;
+    require!(
+        data.len() >= header_len,
+        AirdropError::InvalidInstructionSysvar
+    );
+
+    (0..sig_count)
+        .map(|i| {
+            let entry = &data[SECP256R1_OFFSETS_START + SECP256R1_OFFSETS_LEN * i..];
+            let read_u16 =
+                |field: usize| u16::from_le_bytes([entry[2 * field], entry[2 * field + 1]]);
+
+            // The precompile uses u16::MAX as the "current instruction" sentinel
+            let this_ix = u16::MAX;
+            require!(
+                read_u16(1) == this_ix && read_u16(3) == this_ix && read_u16(6) == this_ix,
+                AirdropError::InvalidInstructionSysvar
+            );
+
+            let offsets = Secp256r1SignatureOffsets {
+                signature_offset: read_u16(0) as usize,
+                public_key_offset: read_u16(2) as usize,
+                message_data_offset: read_u16(4) as usize,
+                message_data_size: read_u16(5) as usize,
+            };
+
+            // Ensure all offsets point past the header and stay in bounds
+            require!(
+                offsets.signature_offset >= header_len
+                    && offsets.public_key_offset >= header_len
+                    && offsets.message_data_offset >= header_len,
+                AirdropError::InvalidInstructionSysvar
+            );
+            require!(
+                data.len() >= offsets.signature_offset + SECP256R1_SIG_LEN
+                    && data.len() >= offsets.public_key_offset + SECP256R1_PUBKEY_LEN
+                    && data.len() >= offsets.message_data_offset + offsets.message_data_size,
+                AirdropError::InvalidInstructionSysvar
+            );
+
+            Ok(offsets)
+        })
+        .collect()
+}
+
+/// Validates and parses the secp256r1 signatures of the instruction at `r1_ix_index`, which
+/// must come before the current one, returning the signers' key IDs and the signed message
+pub fn verify_secp256r1_signature(
+    ix_sysvar_account: &AccountInfo,
+    r1_ix_index: usize,
+) -> Result<(Vec<Pubkey>, Vec<u8>)> {
+    let current_ix_index = ix_sysvar::load_current_index_checked(ix_sysvar_account)
+        .map_err(|_| error!(AirdropError::InvalidInstructionSysvar))?;
+
+    // The secp256r1 verification must have run before this instruction
+    require!(
+        r1_ix_index < current_ix_index as usize,
+        AirdropError::InvalidInstructionSysvar
+    );
+    let r1_ix = ix_sysvar::load_instruction_at_checked(r1_ix_index, ix_sysvar_account)
+    .map_err(|_| error!(AirdropError::InvalidInstructionSysvar))?;
+    require!(
+        r1_ix.program_id == SECP256R1_PROGRAM_ID,
+        AirdropError::BadSecp256r1Program
+    );
+    require!(r1_ix.accounts.is_empty(), AirdropError::BadSecp256r1Accounts);
+
+    let offsets = parse_secp256r1_ix_data(&r1_ix.data)?;
+
+    // Pair each signer's key ID with the message it signed
+    let signed = offsets
+        .iter()
+        .map(|o| {
+            let key_id = secp256r1_key_id(
+                &r1_ix.data[o.public_key_offset..o.public_key_offset + SECP256R1_PUBKEY_LEN],
+            );
+            let message = r1_ix.data
+                [o.message_data_offset..o.message_data_offset + o.message_data_size]
+                .to_vec();
+            (key_id, message)
+        })
+        .collect();
+
+    // Only the signers of the claim message count, each once
+    signers_of_claim_message(signed)
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+
+/// The SPL stake pool program
+pub const SPL_STAKE_POOL_PROGRAM_ID: Pubkey = pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+
+/// The index of `DepositSol` in the stake pool program's instruction enum
+const DEPOSIT_SOL_INSTRUCTION: u8 = 14;
+
+/// Deposits `lamports` from the system-owned `lamports_from` into an SPL stake pool's
+/// reserve, minting pool tokens to `pool_tokens_to`.
+///
+/// `pool_tokens_to` is also passed as the referrer, so any referral fee the pool charges
+/// goes back to the depositor. The stake pool program validates every pool account
+/// against the pool itself.
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_sol<'info>(
+    stake_pool_program: AccountInfo<'info>,
+    stake_pool: AccountInfo<'info>,
+    withdraw_authority: AccountInfo<'info>,
+    reserve_stake: AccountInfo<'info>,
+    lamports_from: AccountInfo<'info>,
+    pool_tokens_to: AccountInfo<'info>,
+    manager_fee_account: AccountInfo<'info>,
+    pool_mint: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    lamports: u64,
+    signer: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = vec![DEPOSIT_SOL_INSTRUCTION];
+    data.extend_from_slice(&lamports.to_le_bytes());
+
+    let instruction = Instruction {
+        program_id: stake_pool_program.key(),
+        accounts: vec![
+            AccountMeta::new(stake_pool.key(), false),
+            AccountMeta::new_readonly(withdraw_authority.key(), false),
+            AccountMeta::new(reserve_stake.key(), false),
+            AccountMeta::new(lamports_from.key(), true),
+            AccountMeta::new(pool_tokens_to.key(), false),
+            AccountMeta::new(manager_fee_account.key(), false),
+            AccountMeta::new(pool_tokens_to.key(), false),
+            AccountMeta::new(pool_mint.key(), false),
+            AccountMeta::new_readonly(system_program.key(), false),
+            AccountMeta::new_readonly(token_program.key(), false),
+        ],
+        data,
+    };
+
+    invoke_signed(
+        &instruction,
+        &[
+            stake_pool,
+            withdraw_authority,
+            reserve_stake,
+            lamports_from,
+            pool_tokens_to,
+            manager_fee_account,
+            pool_mint,
+            system_program,
+            token_program,
+            stake_pool_program,
+        ],
+        signer,
+    )?;
+
+    Ok(())
+}
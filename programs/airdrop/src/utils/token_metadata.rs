@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use borsh::BorshDeserialize;
+
+/// The Metaplex Token Metadata program
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// The `Key` discriminator of a `MetadataV1` account
+const METADATA_V1_KEY: u8 = 4;
+
+#[derive(BorshDeserialize)]
+struct MetadataCreator {
+    _address: Pubkey,
+    _verified: bool,
+    _share: u8,
+}
+
+#[derive(BorshDeserialize)]
+struct MetadataCollection {
+    verified: bool,
+    key: Pubkey,
+}
+
+/// The leading fields of a Token Metadata `Metadata` account, up to its collection
+#[derive(BorshDeserialize)]
+struct MetadataHead {
+    key: u8,
+    _update_authority: Pubkey,
+    mint: Pubkey,
+    _name: String,
+    _symbol: String,
+    _uri: String,
+    _seller_fee_basis_points: u16,
+    _creators: Option<Vec<MetadataCreator>>,
+    _primary_sale_happened: bool,
+    _is_mutable: bool,
+    _edition_nonce: Option<u8>,
+    _token_standard: Option<u8>,
+    collection: Option<MetadataCollection>,
+}
+
+/// Returns the verified collection of the NFT `mint`, read from its Token Metadata account.
+///
+/// Returns `None` if `metadata` is not the mint's metadata PDA, cannot be parsed, or the
+/// NFT has no collection or an unverified one.
+pub fn verified_collection(metadata: &AccountInfo, mint: &Pubkey) -> Option<Pubkey> {
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"metadata", TOKEN_METADATA_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &TOKEN_METADATA_PROGRAM_ID,
+    );
+    if metadata.key() != expected || *metadata.owner != TOKEN_METADATA_PROGRAM_ID {
+        return None;
+    }
+
+    let data = metadata.try_borrow_data().ok()?;
+    let head = MetadataHead::deserialize(&mut &data[..]).ok()?;
+    if head.key != METADATA_V1_KEY || head.mint != *mint {
+        return None;
+    }
+
+    head.collection
+        .filter(|collection| collection.verified)
+        .map(|collection| collection.key)
+}
@@ -1,6 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{program_error::ProgramError, system_instruction};
-use anchor_spl::token::{self, Transfer};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+    onchain::invoke_transfer_checked,
+};
+use anchor_spl::token_interface::{self, ApproveChecked, Burn, CloseAccount, MintTo};
+use crate::errors::AirdropError;
 
 pub fn transfer_native<'info>(
     from: &AccountInfo<'info>,
@@ -42,17 +48,73 @@ pub fn transfer_native<'info>(
     Ok(())
 }
 
+/// Transfers SPL tokens with `transfer_checked`, which works for both the legacy
+/// token program and Token-2022 (whichever `token_program` is passed).
+///
+/// For Token-2022 mints with a transfer hook, the hook's extra accounts are resolved
+/// from `additional_accounts` and appended to the CPI.
+#[allow(clippy::too_many_arguments)]
 pub fn transfer_spl<'info>(
     token_program: AccountInfo<'info>,
     authority: AccountInfo<'info>,
     from: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
     to: AccountInfo<'info>,
+    additional_accounts: &[AccountInfo<'info>],
     amount: u64,
+    decimals: u8,
     signer: Option<&[&[&[u8]]]>,
 ) -> Result<()> {
-    let cpi_accounts = Transfer {
-        from: from.to_account_info(),
-        to: to.to_account_info(),
+    invoke_transfer_checked(
+        token_program.key,
+        from,
+        mint,
+        to,
+        authority,
+        additional_accounts,
+        amount,
+        decimals,
+        signer.unwrap_or(&[]),
+    )?;
+
+    Ok(())
+}
+
+pub fn close_spl_account<'info>(
+    token_program: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    account: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    signer: Option<&[&[&[u8]]]>,
+) -> Result<()> {
+    let cpi_accounts = CloseAccount {
+        account,
+        destination,
+        authority,
+    };
+
+    let cpi_ctx = if let Some(signer) = signer {
+        CpiContext::new_with_signer(token_program, cpi_accounts, signer)
+    } else {
+        CpiContext::new(token_program, cpi_accounts)
+    };
+
+    token_interface::close_account(cpi_ctx)?;
+
+    Ok(())
+}
+
+pub fn burn_spl<'info>(
+    token_program: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    amount: u64,
+    signer: Option<&[&[&[u8]]]>,
+) -> Result<()> {
+    let cpi_accounts = Burn {
+        mint,
+        from,
         authority,
     };
 
@@ -62,7 +124,91 @@ pub fn transfer_spl<'info>(
         CpiContext::new(token_program, cpi_accounts)
     };
 
-    token::transfer(cpi_ctx, amount)?;
+    token_interface::burn(cpi_ctx, amount)?;
 
     Ok(())
 }
+
+pub fn mint_spl<'info>(
+    token_program: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    amount: u64,
+    signer: Option<&[&[&[u8]]]>,
+) -> Result<()> {
+    let cpi_accounts = MintTo {
+        mint,
+        to,
+        authority,
+    };
+
+    let cpi_ctx = if let Some(signer) = signer {
+        CpiContext::new_with_signer(token_program, cpi_accounts, signer)
+    } else {
+        CpiContext::new(token_program, cpi_accounts)
+    };
+
+    token_interface::mint_to(cpi_ctx, amount)?;
+
+    Ok(())
+}
+
+pub fn approve_spl<'info>(
+    token_program: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    delegate: AccountInfo<'info>,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    let cpi_accounts = ApproveChecked {
+        to,
+        mint,
+        delegate,
+        authority,
+    };
+
+    token_interface::approve_checked(CpiContext::new(token_program, cpi_accounts), amount, decimals)?;
+
+    Ok(())
+}
+
+/// Returns the mint's Token-2022 transfer fee configuration, if it has one
+fn get_transfer_fee_config(mint: &AccountInfo) -> Result<Option<TransferFeeConfig>> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(None);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+
+    Ok(mint_state.get_extension::<TransferFeeConfig>().ok().copied())
+}
+
+/// Returns the transfer fee the token program withholds when sending `amount` of the mint.
+/// Always 0 for legacy mints and Token-2022 mints without the transfer-fee extension.
+pub fn get_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    match get_transfer_fee_config(mint)? {
+        Some(config) => config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or_else(|| AirdropError::MathOverflow.into()),
+        None => Ok(0),
+    }
+}
+
+/// Returns the amount to send so that the destination receives exactly `net_amount`
+/// after the mint's transfer fee is withheld
+pub fn get_gross_amount_for_net(mint: &AccountInfo, net_amount: u64) -> Result<u64> {
+    let fee = match get_transfer_fee_config(mint)? {
+        Some(config) => config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, net_amount)
+            .ok_or(AirdropError::MathOverflow)?,
+        None => 0,
+    };
+
+    Ok(net_amount
+        .checked_add(fee)
+        .ok_or(AirdropError::MathOverflow)?)
+}
@@ -0,0 +1,15 @@
+#!/usr/bin/env bash
+# Dumps the mainnet programs the tests load but this workspace does not build.
+# Run it once as `yarn fixtures` before `yarn test`; it needs the solana CLI and network access.
+# Programs already present are left alone.
+set -euo pipefail
+cd "$(dirname "$0")"
+
+dump() {
+  if [ ! -f "$2" ]; then
+    solana program dump --url mainnet-beta "$1" "$2"
+  fi
+}
+
+dump SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf squads_multisig_program.so
+dump GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw spl_governance.so
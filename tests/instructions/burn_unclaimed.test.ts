@@ -0,0 +1,146 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Airdrop } from "../../target/types/airdrop";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { fromWorkspace, LiteSVMProvider } from 'anchor-litesvm';
+import { sendTransaction } from "../utils/svm";
+import { createSplToken, getOrCreateAssociatedTokenAccount, getTokenAccountBalance } from "../utils/spl";
+import { emptyProjectMetadata, getTokenVaultAddress } from "../utils/project";
+import { createMintToInstruction, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+describe("burn_unclaimed", () => {
+  let svm: LiteSVM;
+  let provider: LiteSVMProvider;
+  let program: Program<Airdrop>;
+
+  let authorityKeypair: Keypair;
+  let mint: PublicKey;
+
+  before(async () => {
+    svm = fromWorkspace('./')
+      .withBuiltins()
+      .withSysvars()
+      .withDefaultPrograms()
+      .withPrecompiles();
+    provider = new LiteSVMProvider(svm);
+    anchor.setProvider(provider);
+    program = anchor.workspace.Airdrop as Program<Airdrop>;
+
+    authorityKeypair = Keypair.generate();
+    await svm.airdrop(authorityKeypair.publicKey, BigInt(10000000000));
+
+    await program.methods
+      .createGlobalConfig([{ pubkey: Keypair.generate().publicKey, weight: 1, active: true, keyType: { ed25519: {} } }], 1, 1)
+      .accountsPartial({
+        payer: authorityKeypair.publicKey,
+        authority: authorityKeypair.publicKey,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+
+    mint = await createSplToken(provider, authorityKeypair, 9);
+  });
+
+  const createFundedProject = async (nonce: number, endTime: number) => {
+    const projectNonce = new anchor.BN(nonce);
+    const [projectPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("project"), projectNonce.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    await program.methods
+      .createProject(new anchor.BN(0), new anchor.BN(endTime), emptyProjectMetadata)
+      .accountsPartial({ tokenProgram: TOKEN_PROGRAM_ID, authority: authorityKeypair.publicKey, mint: mint })
+      .signers([authorityKeypair])
+      .rpc();
+
+    const tokenVault = getTokenVaultAddress(program.programId, projectPda);
+    const mintToIx = createMintToInstruction(
+      mint,
+      tokenVault,
+      authorityKeypair.publicKey,
+      BigInt(1000000),
+      [],
+      TOKEN_PROGRAM_ID
+    );
+    await sendTransaction(svm, authorityKeypair, [mintToIx]);
+
+    return projectPda;
+  };
+
+  it("Burns the remaining balance once the claim window has ended", async () => {
+    const now = Number(svm.getClock().unixTimestamp);
+    const projectPda = await createFundedProject(1, now - 1);
+
+    const outsider = Keypair.generate();
+    await svm.airdrop(outsider.publicKey, BigInt(10000000000));
+    try {
+      await program.methods
+        .burnUnclaimed(new anchor.BN(1))
+        .accountsPartial({ signer: outsider.publicKey, project: projectPda, mint: mint, tokenProgram: TOKEN_PROGRAM_ID })
+        .signers([outsider])
+        .rpc();
+      expect.fail("Should have failed for a signer that is neither authority");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+
+    await program.methods
+      .burnUnclaimed(new anchor.BN(1))
+      .accountsPartial({ signer: authorityKeypair.publicKey, project: projectPda, mint: mint, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([authorityKeypair])
+      .rpc();
+
+    expect(await getTokenAccountBalance(svm, getTokenVaultAddress(program.programId, projectPda))).to.equal(BigInt(0));
+  });
+
+  it("Fails while the claim window is still open", async () => {
+    const projectPda = await createFundedProject(2, 0);
+
+    try {
+      await program.methods
+        .burnUnclaimed(new anchor.BN(2))
+        .accountsPartial({ signer: authorityKeypair.publicKey, project: projectPda, mint: mint, tokenProgram: TOKEN_PROGRAM_ID })
+        .signers([authorityKeypair])
+        .rpc();
+      expect.fail("Should have failed before the claim window ended");
+    } catch (error) {
+      expect(error.message).to.include("ClaimWindowNotEnded");
+    }
+  });
+
+  it("Leaves funder deposits in the vault for refunds", async () => {
+    const now = Number(svm.getClock().unixTimestamp);
+    const projectPda = await createFundedProject(3, now - 1);
+    const tokenVault = getTokenVaultAddress(program.programId, projectPda);
+
+    const funder = Keypair.generate();
+    await svm.airdrop(funder.publicKey, BigInt(10000000000));
+    const funderTokenAccount = await getOrCreateAssociatedTokenAccount(svm, mint, funder.publicKey, false, funder);
+    await sendTransaction(svm, authorityKeypair, [
+      createMintToInstruction(mint, funderTokenAccount, authorityKeypair.publicKey, BigInt(300000), [], TOKEN_PROGRAM_ID),
+    ]);
+    await program.methods
+      .fundProject(new anchor.BN(3), new anchor.BN(300000))
+      .accountsPartial({ tokenProgram: TOKEN_PROGRAM_ID, funder: funder.publicKey, funderTokenAccount, project: projectPda, mint: mint })
+      .signers([funder])
+      .rpc();
+
+    await program.methods
+      .burnUnclaimed(new anchor.BN(3))
+      .accountsPartial({ signer: authorityKeypair.publicKey, project: projectPda, mint: mint, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([authorityKeypair])
+      .rpc();
+    expect(await getTokenAccountBalance(svm, tokenVault)).to.equal(BigInt(300000));
+
+    await program.methods
+      .refundFunder(new anchor.BN(3))
+      .accountsPartial({ tokenProgram: TOKEN_PROGRAM_ID, funder: funder.publicKey, funderTokenAccount, project: projectPda, mint: mint })
+      .signers([funder])
+      .rpc();
+    expect(await getTokenAccountBalance(svm, funderTokenAccount)).to.equal(BigInt(300000));
+    expect(await getTokenAccountBalance(svm, tokenVault)).to.equal(BigInt(0));
+  });
+});
@@ -1,16 +1,29 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Airdrop } from "../../target/types/airdrop";
-import { Ed25519Program, Keypair, PublicKey } from "@solana/web3.js";
+import { ComputeBudgetProgram, Ed25519Program, Keypair, PublicKey, StakeProgram, SystemProgram, SYSVAR_STAKE_HISTORY_PUBKEY, TransactionInstruction, VoteInit, VoteProgram } from "@solana/web3.js";
 import { expect } from "chai";
 import { LiteSVM, Clock } from "litesvm";
 import { fromWorkspace, LiteSVMProvider } from 'anchor-litesvm';
 import { sendTransaction } from "../utils/svm";
+import { createSecp256k1Instruction, secp256k1KeyId } from "../utils/secp256k1";
+import { createSecp256r1Instruction, secp256r1KeyId } from "../utils/secp256r1";
+import { findCpiEvent, findEvent } from "../utils/events";
 import { Schema as BorshSchema, serialize } from "borsh";
-import { createEd25519Instruction } from "../utils/ed25519";
-import { createSplToken, getSplTokenBalance } from "../utils/spl";
-import { createMintToInstruction, getAssociatedTokenAddress, TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { createEd25519Instruction, createEd25519InstructionWithExternalMessage, createEd25519InstructionWithMessages, createMultiEd25519Instruction } from "../utils/ed25519";
+import { createSplToken, createTokenAccount, createTransferFeeToken, getOrCreateAssociatedTokenAccount, getSplTokenBalance, getTokenAccountBalance } from "../utils/spl";
+import { AuthorityType, createMintToInstruction, createSetAuthorityInstruction, createSyncNativeInstruction, getAccount, getAssociatedTokenAddress, getMint, NATIVE_MINT, TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import * as nacl from "tweetnacl";
+import { keccak_256 } from "@noble/hashes/sha3";
+import { p256 } from "@noble/curves/p256";
+import { secp256k1 } from "@noble/curves/secp256k1";
+import { emptyProjectMetadata, getTokenVaultAddress } from "../utils/project";
+
+// The retired stake config account the Stake program still takes when delegating
+const STAKE_CONFIG_ID = new PublicKey("StakeConfig11111111111111111111111111111111");
+
+// The SPL stake pool program claim_into_pool deposits through
+const SPL_STAKE_POOL_PROGRAM_ID = new PublicKey("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
 
 // Define the message structure for Borsh serialization
 
@@ -20,12 +33,28 @@ class AirdropMessageData {
   mint: Uint8Array;
   project_nonce: bigint;
   amount: bigint;
+  phase: number;
+  destination: Uint8Array | null;
+  cliff_ts: bigint | null;
+  vest_end_ts: bigint | null;
+  referrer: Uint8Array | null;
+  referral_bps: number;
+  tier: number;
+  memo: string | null;
 
-  constructor(fields: { recipient: Uint8Array; mint: Uint8Array; project_nonce: bigint; amount: bigint }) {
+  constructor(fields: { recipient: Uint8Array; mint: Uint8Array; project_nonce: bigint; amount: bigint; phase: number; destination: Uint8Array | null; cliff_ts: bigint | null; vest_end_ts: bigint | null; referrer: Uint8Array | null; referral_bps: number; tier: number; memo: string | null }) {
     this.recipient = fields.recipient;
     this.mint = fields.mint;
     this.project_nonce = fields.project_nonce;
     this.amount = fields.amount;
+    this.phase = fields.phase;
+    this.destination = fields.destination;
+    this.cliff_ts = fields.cliff_ts;
+    this.vest_end_ts = fields.vest_end_ts;
+    this.referrer = fields.referrer;
+    this.referral_bps = fields.referral_bps;
+    this.tier = fields.tier;
+    this.memo = fields.memo;
   }
 
   static schema: BorshSchema = {
@@ -34,22 +63,43 @@ class AirdropMessageData {
       mint: { array: { type: 'u8', len: 32 } },
       project_nonce: 'u64',
       amount: 'u64',
+      phase: 'u8',
+      destination: { option: { array: { type: 'u8', len: 32 } } },
+      cliff_ts: { option: 'i64' },
+      vest_end_ts: { option: 'i64' },
+      referrer: { option: { array: { type: 'u8', len: 32 } } },
+      referral_bps: 'u16',
+      tier: 'u8',
+      memo: { option: 'string' },
     }
   };
 }
 
+// The operation a signed message authorizes
+enum MessageKind {
+  Claim = 0,
+  Refund = 1,
+  AdminOp = 2,
+  Delegation = 3,
+  MerkleRoot = 4,
+}
+
 // Generic domain fields for signed messages
 class MessageDomain {
   program_id: Uint8Array;
   version: number;
   nonce: bigint;
   deadline: bigint;
+  genesis_hash: Uint8Array;
+  kind: number;
 
-  constructor(fields: { program_id: Uint8Array; version: number; nonce: bigint; deadline: bigint }) {
+  constructor(fields: { program_id: Uint8Array; version: number; nonce: bigint; deadline: bigint; genesis_hash?: Uint8Array; kind?: number }) {
     this.program_id = fields.program_id;
     this.version = fields.version;
     this.nonce = fields.nonce;
     this.deadline = fields.deadline;
+    this.genesis_hash = fields.genesis_hash ?? new Uint8Array(32);
+    this.kind = fields.kind ?? MessageKind.Claim;
   }
 
   static schema: BorshSchema = {
@@ -58,29 +108,187 @@ class MessageDomain {
       version: 'u8',
       nonce: 'u64',
       deadline: 'i64',
+      genesis_hash: { array: { type: 'u8', len: 32 } },
+      kind: 'u8',
     }
   };
 }
 
-// Complete airdrop message
+// Complete airdrop message, tagged with its layout version
 class AirdropMessage {
+  layout: number;
   data: AirdropMessageData;
   domain: MessageDomain;
 
-  constructor(fields: { data: AirdropMessageData; domain: MessageDomain }) {
+  constructor(fields: { data: AirdropMessageData; domain: MessageDomain; layout?: number }) {
+    this.layout = fields.layout ?? 0; // VersionedAirdropMessage::V1
     this.data = fields.data;
     this.domain = fields.domain;
   }
 
   // Borsh schema definition
+  static schema: BorshSchema = {
+    struct: {
+      layout: 'u8',
+      data: AirdropMessageData.schema,
+      domain: MessageDomain.schema,
+    }
+  };
+}
+
+// A recipient's consent for a delegate to claim one of its grants
+class DelegationMessage {
+  recipient: Uint8Array;
+  delegate: Uint8Array;
+  project_nonce: bigint;
+  domain: MessageDomain;
+
+  constructor(fields: { recipient: Uint8Array; delegate: Uint8Array; project_nonce: bigint; domain: MessageDomain }) {
+    this.recipient = fields.recipient;
+    this.delegate = fields.delegate;
+    this.project_nonce = fields.project_nonce;
+    this.domain = fields.domain;
+  }
+
+  static schema: BorshSchema = {
+    struct: {
+      recipient: { array: { type: 'u8', len: 32 } },
+      delegate: { array: { type: 'u8', len: 32 } },
+      project_nonce: 'u64',
+      domain: MessageDomain.schema,
+    }
+  };
+}
+
+// Grants for several recipients signed together, each redeemed with claim_entry
+class MultiGrantMessage {
+  mint: Uint8Array;
+  project_nonce: bigint;
+  phase: number;
+  tier: number;
+  entries: { recipient: Uint8Array; amount: bigint; nonce: bigint }[];
+  domain: MessageDomain;
+
+  constructor(fields: { mint: Uint8Array; project_nonce: bigint; phase: number; tier: number; entries: { recipient: Uint8Array; amount: bigint; nonce: bigint }[]; domain: MessageDomain }) {
+    this.mint = fields.mint;
+    this.project_nonce = fields.project_nonce;
+    this.phase = fields.phase;
+    this.tier = fields.tier;
+    this.entries = fields.entries;
+    this.domain = fields.domain;
+  }
+
+  static schema: BorshSchema = {
+    struct: {
+      mint: { array: { type: 'u8', len: 32 } },
+      project_nonce: 'u64',
+      phase: 'u8',
+      tier: 'u8',
+      entries: {
+        array: {
+          type: {
+            struct: {
+              recipient: { array: { type: 'u8', len: 32 } },
+              amount: 'u64',
+              nonce: 'u64',
+            }
+          }
+        }
+      },
+      domain: MessageDomain.schema,
+    }
+  };
+}
+
+// A Merkle root over a campaign's allocations, redeemed with a proof passed to claim
+class MerkleRootMessage {
+  mint: Uint8Array;
+  project_nonce: bigint;
+  phase: number;
+  tier: number;
+  root: Uint8Array;
+  domain: MessageDomain;
+
+  constructor(fields: { mint: Uint8Array; project_nonce: bigint; phase: number; tier: number; root: Uint8Array; domain: MessageDomain }) {
+    this.mint = fields.mint;
+    this.project_nonce = fields.project_nonce;
+    this.phase = fields.phase;
+    this.tier = fields.tier;
+    this.root = fields.root;
+    this.domain = fields.domain;
+  }
+
+  static schema: BorshSchema = {
+    struct: {
+      mint: { array: { type: 'u8', len: 32 } },
+      project_nonce: 'u64',
+      phase: 'u8',
+      tier: 'u8',
+      root: { array: { type: 'u8', len: 32 } },
+      domain: MessageDomain.schema,
+    }
+  };
+}
+
+// A grant whose claimed tokens claim_and_delegate approves to a token delegate
+class DelegatedClaimMessage {
+  data: AirdropMessageData;
+  token_delegate: Uint8Array;
+  domain: MessageDomain;
+
+  constructor(fields: { data: AirdropMessageData; token_delegate: Uint8Array; domain: MessageDomain }) {
+    this.data = fields.data;
+    this.token_delegate = fields.token_delegate;
+    this.domain = fields.domain;
+  }
+
   static schema: BorshSchema = {
     struct: {
       data: AirdropMessageData.schema,
+      token_delegate: { array: { type: 'u8', len: 32 } },
       domain: MessageDomain.schema,
     }
   };
 }
 
+// keccak(0x00 || recipient || amount || nonce), matching airdrop_message::merkle_leaf
+function merkleLeaf(recipient: PublicKey, amount: bigint, nonce: bigint): Uint8Array {
+  const data = Buffer.alloc(1 + 32 + 8 + 8);
+  recipient.toBuffer().copy(data, 1);
+  data.writeBigUInt64LE(amount, 33);
+  data.writeBigUInt64LE(nonce, 41);
+  return keccak_256(data);
+}
+
+// keccak(0x01 || min(a, b) || max(a, b)), matching airdrop_message::merkle_parent
+function merkleParent(a: Uint8Array, b: Uint8Array): Uint8Array {
+  const [left, right] = Buffer.compare(Buffer.from(a), Buffer.from(b)) <= 0 ? [a, b] : [b, a];
+  return keccak_256(Buffer.concat([Buffer.from([1]), Buffer.from(left), Buffer.from(right)]));
+}
+
+// The Metaplex Token Metadata program
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+// A Token Metadata `MetadataV1` account for `mint`, up to and including its collection
+function metadataAccountData(mint: PublicKey, collection: PublicKey, verified: boolean): Buffer {
+  const string = (value: string) => {
+    const length = Buffer.alloc(4);
+    length.writeUInt32LE(value.length);
+    return Buffer.concat([length, Buffer.from(value)]);
+  };
+  return Buffer.concat([
+    Buffer.from([4]), // Key::MetadataV1
+    Keypair.generate().publicKey.toBuffer(), // update authority
+    mint.toBuffer(),
+    string("NFT"),
+    string("NFT"),
+    string(""),
+    Buffer.alloc(2), // seller fee basis points
+    Buffer.from([0, 0, 1, 0, 0]), // no creators, primary sale, mutable, no edition nonce or token standard
+    Buffer.from([1, verified ? 1 : 0]),
+    collection.toBuffer(),
+  ]);
+}
 
 describe("claim", () => {
   let svm: LiteSVM;
@@ -101,7 +309,7 @@ describe("claim", () => {
   let projectNonce: bigint;
   let projectPda: PublicKey;
   let mint: PublicKey;
-  let projectTokenAccount: PublicKey;
+  let tokenVault: PublicKey;
 
   // Helper function to get nullifier PDA
   const getNullifierPda = (projectPda: PublicKey, nonce: bigint) => {
@@ -125,12 +333,29 @@ describe("claim", () => {
     version: number;
     nonce: bigint;
     deadline: bigint;
+    phase?: number;
+    destination?: PublicKey;
+    cliffTs?: bigint;
+    vestEndTs?: bigint;
+    referrer?: PublicKey;
+    referralBps?: number;
+    tier?: number;
+    memo?: string;
+    genesisHash?: Uint8Array;
   }) => {
     const data = new AirdropMessageData({
       recipient: params.recipient.toBytes(),
       mint: params.mint.toBytes(),
       project_nonce: params.projectNonce,
       amount: params.amount,
+      phase: params.phase ?? 0,
+      destination: params.destination?.toBytes() ?? null,
+      cliff_ts: params.cliffTs ?? null,
+      vest_end_ts: params.vestEndTs ?? null,
+      referrer: params.referrer?.toBytes() ?? null,
+      referral_bps: params.referralBps ?? 0,
+      tier: params.tier ?? 0,
+      memo: params.memo ?? null,
     });
 
     const domain = new MessageDomain({
@@ -138,11 +363,139 @@ describe("claim", () => {
       version: params.version,
       nonce: params.nonce,
       deadline: params.deadline,
+      genesis_hash: params.genesisHash,
     });
 
     return new AirdropMessage({ data, domain });
   };
 
+  // Helper function to build a plain claim of `amount` under `nonce`, signed by the distributor
+  // (or several signers in one ed25519 instruction), with any optional accounts the claim needs
+  const buildSignedClaim = async (
+    nonce: bigint,
+    amount: bigint,
+    signer: Keypair | Keypair[] = distributorKeypair,
+    extraAccounts: Record<string, PublicKey> = {},
+  ) => {
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount,
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline: BigInt(9999999999),
+    });
+    const message = Buffer.from(serialize(AirdropMessage.schema, msg));
+    const ed25519Ix = Array.isArray(signer)
+      ? createMultiEd25519Instruction(signer, message)
+      : createEd25519Instruction(signer, message);
+    const claimIx = await program.methods
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        payer: recipientKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: projectPda,
+        nullifier: getNullifierPda(projectPda, nonce),
+        mint: mint,
+        tokenVault: tokenVault,
+        recipientTokenAccount: await getAssociatedTokenAddress(
+          mint,
+          recipientKeypair.publicKey,
+          false,
+          TOKEN_PROGRAM_ID,
+          ASSOCIATED_TOKEN_PROGRAM_ID
+        ),
+        ...extraAccounts,
+      })
+      .instruction();
+    return [ed25519Ix, claimIx];
+  };
+
+  // Helper function to create and fund another project for `projectMint`, for tests that need a
+  // project without claims or leave it in a state other tests must not see
+  const createFundedProject = async (projectMint: PublicKey = mint) => {
+    const [projectCounterPda] = PublicKey.findProgramAddressSync([Buffer.from("project_counter")], program.programId);
+    const nonce = BigInt((await program.account.projectCounter.fetch(projectCounterPda)).count.toNumber() + 1);
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("project"), Buffer.from(new anchor.BN(nonce.toString()).toArray("le", 8))],
+      program.programId
+    );
+    const vault = getTokenVaultAddress(program.programId, pda);
+
+    await program.methods
+      .createProject(new anchor.BN(0), new anchor.BN(0), emptyProjectMetadata)
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        authority: authorityKeypair.publicKey,
+        project: pda,
+        mint: projectMint,
+        tokenVault: vault,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+    // A wrapped SOL vault is funded by wrapping lamports
+    await sendTransaction(
+      svm,
+      authorityKeypair,
+      projectMint.equals(NATIVE_MINT)
+        ? [
+          SystemProgram.transfer({ fromPubkey: authorityKeypair.publicKey, toPubkey: vault, lamports: 5000000000 }),
+          createSyncNativeInstruction(vault),
+        ]
+        : [createMintToInstruction(projectMint, vault, authorityKeypair.publicKey, BigInt(1000000000), [], TOKEN_PROGRAM_ID)]
+    );
+
+    return { nonce, pda, vault };
+  };
+
+  // Helper function to build a claim of `amount` under `nonce` against a project from
+  // createFundedProject, signed by the distributor
+  const buildSignedProjectClaim = async (
+    project: { nonce: bigint; pda: PublicKey; vault: PublicKey },
+    nonce: bigint,
+    amount: bigint,
+    extraAccounts: Record<string, PublicKey | null> = {},
+  ) => {
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: project.nonce,
+      amount,
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline: BigInt(9999999999),
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+    const claimIx = await program.methods
+      .claim(new anchor.BN(project.nonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        payer: recipientKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: project.pda,
+        nullifier: getNullifierPda(project.pda, nonce),
+        mint: mint,
+        tokenVault: project.vault,
+        recipientTokenAccount: await getAssociatedTokenAddress(
+          mint,
+          recipientKeypair.publicKey,
+          false,
+          TOKEN_PROGRAM_ID,
+          ASSOCIATED_TOKEN_PROGRAM_ID
+        ),
+        ...extraAccounts,
+      })
+      .instruction();
+    return [ed25519Ix, claimIx];
+  };
+
   // Create global config, create project, deploy and mint spl
   before(async () => {
     svm = fromWorkspace('./')
@@ -173,8 +526,9 @@ describe("claim", () => {
     );
 
     await program.methods
-      .createGlobalConfig(distributorKeypair.publicKey)
+      .createGlobalConfig([{ pubkey: distributorKeypair.publicKey, weight: 1, active: true, keyType: { ed25519: {} } }], 1, 1)
       .accountsPartial({
+        payer: authorityKeypair.publicKey,
         authority: authorityKeypair.publicKey,
         globalConfig: globalConfigPda,
       })
@@ -191,29 +545,24 @@ describe("claim", () => {
       program.programId
     );
 
-    projectTokenAccount = await getAssociatedTokenAddress(
-      mint,
-      projectPda,
-      true,
-      TOKEN_PROGRAM_ID,
-      ASSOCIATED_TOKEN_PROGRAM_ID
-    );
+    tokenVault = getTokenVaultAddress(program.programId, projectPda);
 
     await program.methods
-      .createProject(new anchor.BN(projectNonce.toString()))
+      .createProject(new anchor.BN(0), new anchor.BN(0), emptyProjectMetadata)
       .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
         authority: authorityKeypair.publicKey,
         project: projectPda,
         mint: mint,
-        projectTokenAccount: projectTokenAccount,
+        tokenVault: tokenVault,
       })
       .signers([authorityKeypair])
       .rpc();
 
-    // Mint tokens to the project token account
+    // Mint tokens to the project token vault
     const mintToIx = createMintToInstruction(
       mint,
-      projectTokenAccount,
+      tokenVault,
       authorityKeypair.publicKey,
       BigInt(1000000000), // 1 billion tokens
       [],
@@ -269,13 +618,15 @@ describe("claim", () => {
 
     // Create the claim instruction
     const claimIx = await program.methods
-      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()))
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
       .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
         recipient: recipientKeypair.publicKey,
+        payer: recipientKeypair.publicKey,
         project: projectPda,
         nullifier: nullifierPda,
         mint: mint,
-        projectTokenAccount: projectTokenAccount,
+        tokenVault: tokenVault,
         recipientTokenAccount: recipientTokenAccount
       })
       .instruction();
@@ -284,6 +635,16 @@ describe("claim", () => {
     // Verify the balance has increased by the claim amount
     const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
     expect(balanceAfter - balanceBefore).to.equal(BigInt(claimAmount));
+
+    // Verify the protocol statistics picked up the project and the claim
+    const [protocolStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol_stats")],
+      program.programId
+    );
+    const stats = await program.account.protocolStats.fetch(protocolStatsPda);
+    expect(stats.totalProjects.toNumber()).to.equal(1);
+    expect(stats.totalClaims.toNumber()).to.equal(1);
+    expect(stats.totalDistributed.toString()).to.equal(claimAmount.toString());
   });
 
   it("Fails when Ed25519 instruction is not first", async () => {
@@ -302,14 +663,16 @@ describe("claim", () => {
     const nullifierPda = getNullifierPda(projectPda, nonce);
 
     const claimIx = await program.methods
-      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()))
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
       .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
         recipient: recipientKeypair.publicKey,
+        payer: recipientKeypair.publicKey,
         globalConfig: globalConfigPda,
         project: projectPda,
         nullifier: nullifierPda,
         mint: mint,
-        projectTokenAccount: projectTokenAccount,
+        tokenVault: tokenVault,
         recipientTokenAccount: recipientTokenAccount
       })
       .instruction();
@@ -377,14 +740,16 @@ describe("claim", () => {
     );
 
     const claimIx = await program.methods
-      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()))
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
       .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
         recipient: recipientKeypair.publicKey,
+        payer: recipientKeypair.publicKey,
         globalConfig: globalConfigPda, // But we expect the correct one
         project: projectPda,
         nullifier: nullifierPda,
         mint: mint,
-        projectTokenAccount: projectTokenAccount,
+        tokenVault: tokenVault,
         recipientTokenAccount: recipientTokenAccount
       })
       .instruction();
@@ -430,14 +795,16 @@ describe("claim", () => {
     );
 
     const claimIx = await program.methods
-      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()))
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
       .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
         recipient: recipientKeypair.publicKey,
+        payer: recipientKeypair.publicKey,
         globalConfig: globalConfigPda,
         project: projectPda,
         nullifier: nullifierPda,
         mint: mint,
-        projectTokenAccount: projectTokenAccount,
+        tokenVault: tokenVault,
         recipientTokenAccount: recipientTokenAccount
       })
       .instruction();
@@ -483,14 +850,16 @@ describe("claim", () => {
     );
 
     const claimIx = await program.methods
-      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()))
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
       .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
         recipient: recipientKeypair.publicKey,
+        payer: recipientKeypair.publicKey,
         globalConfig: globalConfigPda,
         project: projectPda,
         nullifier: nullifierPda,
         mint: mint,
-        projectTokenAccount: projectTokenAccount,
+        tokenVault: tokenVault,
         recipientTokenAccount: recipientTokenAccount
       })
       .instruction();
@@ -535,14 +904,16 @@ describe("claim", () => {
     );
 
     const claimIx = await program.methods
-      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()))
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
       .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
         recipient: recipientKeypair.publicKey,
+        payer: recipientKeypair.publicKey,
         globalConfig: globalConfigPda,
         project: projectPda,
         nullifier: nullifierPda,
         mint: mint,
-        projectTokenAccount: projectTokenAccount,
+        tokenVault: tokenVault,
         recipientTokenAccount: recipientTokenAccount
       })
       .instruction();
@@ -588,28 +959,32 @@ describe("claim", () => {
 
     // First claim instruction (valid)
     const claimIx1 = await program.methods
-      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()))
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
       .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
         recipient: recipientKeypair.publicKey,
+        payer: recipientKeypair.publicKey,
         globalConfig: globalConfigPda,
         project: projectPda,
         nullifier: nullifierPda,
         mint: mint,
-        projectTokenAccount: projectTokenAccount,
+        tokenVault: tokenVault,
         recipientTokenAccount: recipientTokenAccount
       })
       .instruction();
 
     // Second claim instruction (tries to reuse the same Ed25519)
     const claimIx2 = await program.methods
-      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()))
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
       .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
         recipient: recipientKeypair.publicKey,
+        payer: recipientKeypair.publicKey,
         globalConfig: globalConfigPda,
         project: projectPda,
         nullifier: nullifierPda,
         mint: mint,
-        projectTokenAccount: projectTokenAccount,
+        tokenVault: tokenVault,
         recipientTokenAccount: recipientTokenAccount
       })
       .instruction();
@@ -669,14 +1044,16 @@ describe("claim", () => {
     );
 
     const claimIx = await program.methods
-      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()))
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
       .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
         recipient: recipientKeypair.publicKey,
+        payer: recipientKeypair.publicKey,
         globalConfig: globalConfigPda,
         project: projectPda,
         nullifier: nullifierPda,
         mint: mint,
-        projectTokenAccount: projectTokenAccount,
+        tokenVault: tokenVault,
         recipientTokenAccount: recipientTokenAccount
       })
       .instruction();
@@ -722,14 +1099,16 @@ describe("claim", () => {
     );
 
     const claimIx = await program.methods
-      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()))
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
       .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
         recipient: recipientKeypair.publicKey,
+        payer: recipientKeypair.publicKey,
         globalConfig: globalConfigPda,
         project: projectPda,
         nullifier: nullifierPda,
         mint: mint,
-        projectTokenAccount: projectTokenAccount,
+        tokenVault: tokenVault,
         recipientTokenAccount: recipientTokenAccount
       })
       .instruction();
@@ -743,14 +1122,16 @@ describe("claim", () => {
     // Try to reuse the SAME signature with a different claim instruction
     // This should fail because the nullifier for this nonce already exists
     const claimIx2 = await program.methods
-      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()))
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
       .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
         recipient: recipientKeypair.publicKey,
+        payer: recipientKeypair.publicKey,
         globalConfig: globalConfigPda,
         project: projectPda,
         nullifier: nullifierPda,
         mint: mint,
-        projectTokenAccount: projectTokenAccount,
+        tokenVault: tokenVault,
         recipientTokenAccount: recipientTokenAccount
       })
       .instruction();
@@ -767,4 +1148,4014 @@ describe("claim", () => {
     }
   });
 
-});
\ No newline at end of file
+  it("Claims with M-of-N distributor signatures and fails below the threshold", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+    const secondDistributor = Keypair.generate();
+    const thirdDistributor = Keypair.generate();
+
+    // Require 2 of 3 distributors
+    for (const distributor of [secondDistributor, thirdDistributor]) {
+      await program.methods
+        .updateGlobalConfig({ add: { distributor: distributor.publicKey, weight: 1, keyType: { ed25519: {} } } })
+        .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+        .signers([authorityKeypair])
+        .rpc();
+    }
+    await program.methods
+      .updateGlobalConfig({ setThreshold: { threshold: 2 } })
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
+    const buildClaim = async (nonce: bigint, signers: Keypair[]) => {
+      const msg = createAirdropMessage({
+        recipient: recipientKeypair.publicKey,
+        mint: mint,
+        projectNonce: projectNonce,
+        amount: BigInt(claimAmount),
+        programId: program.programId,
+        version: 1,
+        nonce,
+        deadline,
+      });
+
+      const ed25519Ix = createMultiEd25519Instruction(
+        signers,
+        Buffer.from(serialize(AirdropMessage.schema, msg))
+      );
+
+      const claimIx = await program.methods
+        .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+        .accountsPartial({
+          tokenProgram: TOKEN_PROGRAM_ID,
+          recipient: recipientKeypair.publicKey,
+          payer: recipientKeypair.publicKey,
+          globalConfig: globalConfigPda,
+          project: projectPda,
+          nullifier: getNullifierPda(projectPda, nonce),
+          mint: mint,
+          tokenVault: tokenVault,
+          recipientTokenAccount: recipientTokenAccount
+        })
+        .instruction();
+
+      return [ed25519Ix, claimIx];
+    };
+
+    // The same distributor signing twice only counts once
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildClaim(BigInt(200), [secondDistributor, secondDistributor]));
+      expect.fail("Should have failed below the threshold");
+    } catch (error) {
+      expect(error.message).to.include("ThresholdNotMet");
+    }
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, await buildClaim(BigInt(201), [distributorKeypair, thirdDistributor]));
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(claimAmount));
+
+    // Restore the single-signature threshold for other tests
+    await program.methods
+      .updateGlobalConfig({ setThreshold: { threshold: 1 } })
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+  });
+
+  it("Fails while the program is paused and succeeds after unpausing", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+    const nonce = BigInt(300);
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(claimAmount),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline,
+    });
+
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+
+    const claimIx = await program.methods
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        recipient: recipientKeypair.publicKey,
+        payer: recipientKeypair.publicKey,
+        globalConfig: globalConfigPda,
+        project: projectPda,
+        nullifier: getNullifierPda(projectPda, nonce),
+        mint: mint,
+        tokenVault: tokenVault,
+        recipientTokenAccount: recipientTokenAccount
+      })
+      .instruction();
+
+    await program.methods
+      .pause()
+      .accountsPartial({ signer: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    try {
+      await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimIx]);
+      expect.fail("Should have failed while paused");
+    } catch (error) {
+      expect(error.message).to.include("ProgramPaused");
+    }
+
+    await program.methods
+      .unpause()
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimIx]);
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(claimAmount));
+  });
+
+  it("Deducts the protocol fee and lets the authority withdraw it to the treasury", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+    const nonce = BigInt(400);
+    const treasury = Keypair.generate();
+
+    await program.methods
+      .setFeeConfig(1000, treasury.publicKey) // 10%
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(claimAmount),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline,
+    });
+
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+
+    const claimIx = await program.methods
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        recipient: recipientKeypair.publicKey,
+        payer: recipientKeypair.publicKey,
+        globalConfig: globalConfigPda,
+        project: projectPda,
+        nullifier: getNullifierPda(projectPda, nonce),
+        mint: mint,
+        tokenVault: tokenVault,
+        recipientTokenAccount: recipientTokenAccount
+      })
+      .instruction();
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimIx]);
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(900000));
+
+    await program.methods
+      .withdrawFees()
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        authority: authorityKeypair.publicKey,
+        globalConfig: globalConfigPda,
+        mint: mint,
+        feeTreasury: treasury.publicKey,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+
+    expect(await getSplTokenBalance(svm, mint, treasury.publicKey)).to.equal(BigInt(100000));
+
+    // Restore the fee-free configuration for other tests
+    await program.methods
+      .setFeeConfig(0, treasury.publicKey)
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+  });
+
+  it("Fails for a denylisted recipient until removed from the denylist", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+    const nonce = BigInt(500);
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(claimAmount),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline,
+    });
+
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+
+    const claimIx = await program.methods
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        recipient: recipientKeypair.publicKey,
+        payer: recipientKeypair.publicKey,
+        globalConfig: globalConfigPda,
+        project: projectPda,
+        nullifier: getNullifierPda(projectPda, nonce),
+        mint: mint,
+        tokenVault: tokenVault,
+        recipientTokenAccount: recipientTokenAccount
+      })
+      .instruction();
+
+    await program.methods
+      .addToDenylist(recipientKeypair.publicKey)
+      .accountsPartial({ payer: authorityKeypair.publicKey, authority: authorityKeypair.publicKey })
+      .signers([authorityKeypair])
+      .rpc();
+
+    try {
+      await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimIx]);
+      expect.fail("Should have failed for a denylisted recipient");
+    } catch (error) {
+      expect(error.message).to.include("RecipientDenylisted");
+    }
+
+    await program.methods
+      .removeFromDenylist(recipientKeypair.publicKey)
+      .accountsPartial({ authority: authorityKeypair.publicKey })
+      .signers([authorityKeypair])
+      .rpc();
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimIx]);
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(claimAmount));
+  });
+
+
+  it("Rejects claims beyond the global rate limit", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
+    const buildClaim = async (nonce: bigint) => {
+      const msg = createAirdropMessage({
+        recipient: recipientKeypair.publicKey,
+        mint: mint,
+        projectNonce: projectNonce,
+        amount: BigInt(claimAmount),
+        programId: program.programId,
+        version: 1,
+        nonce,
+        deadline,
+      });
+      const ed25519Ix = createEd25519Instruction(
+        distributorKeypair,
+        Buffer.from(serialize(AirdropMessage.schema, msg))
+      );
+      const claimIx = await program.methods
+        .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+        .accountsPartial({
+          tokenProgram: TOKEN_PROGRAM_ID,
+          recipient: recipientKeypair.publicKey,
+          payer: recipientKeypair.publicKey,
+          globalConfig: globalConfigPda,
+          project: projectPda,
+          nullifier: getNullifierPda(projectPda, nonce),
+          mint: mint,
+          tokenVault: tokenVault,
+          recipientTokenAccount: recipientTokenAccount
+        })
+        .instruction();
+      return [ed25519Ix, claimIx];
+    };
+
+    await program.methods
+      .setRateLimit(new anchor.BN(1), new anchor.BN(1000))
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    await sendTransaction(svm, recipientKeypair, await buildClaim(BigInt(600)));
+
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildClaim(BigInt(601)));
+      expect.fail("Should have failed once the rate limit was reached");
+    } catch (error) {
+      expect(error.message).to.include("RateLimitExceeded");
+    }
+
+    // Disable the rate limit for other tests
+    await program.methods
+      .setRateLimit(new anchor.BN(0), new anchor.BN(0))
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    await sendTransaction(svm, recipientKeypair, await buildClaim(BigInt(601)));
+  });
+
+
+  it("Fails outside the project's claim window", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+    const nonce = BigInt(700);
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(claimAmount),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline,
+    });
+
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+
+    const claimIx = await program.methods
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        recipient: recipientKeypair.publicKey,
+        payer: recipientKeypair.publicKey,
+        globalConfig: globalConfigPda,
+        project: projectPda,
+        nullifier: getNullifierPda(projectPda, nonce),
+        mint: mint,
+        tokenVault: tokenVault,
+        recipientTokenAccount: recipientTokenAccount
+      })
+      .instruction();
+
+    const now = Number(svm.getClock().unixTimestamp);
+    const setWindow = (startTime: number, endTime: number) =>
+      program.methods
+        .setProjectClaimWindow(
+          new anchor.BN(projectNonce.toString()),
+          new anchor.BN(startTime),
+          new anchor.BN(endTime)
+        )
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda })
+        .signers([authorityKeypair])
+        .rpc();
+
+    await setWindow(now + 3600, 0);
+    try {
+      await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimIx]);
+      expect.fail("Should have failed before the claim window opened");
+    } catch (error) {
+      expect(error.message).to.include("ClaimWindowNotStarted");
+    }
+
+    await setWindow(now - 7200, now - 3600);
+    try {
+      await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimIx]);
+      expect.fail("Should have failed after the claim window closed");
+    } catch (error) {
+      expect(error.message).to.include("ClaimWindowEnded");
+    }
+
+    // Reopen the window for other tests
+    await setWindow(0, 0);
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimIx]);
+  });
+
+
+  it("Enforces the per-recipient cumulative cap", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
+    const buildClaim = async (nonce: bigint) => {
+      const msg = createAirdropMessage({
+        recipient: recipientKeypair.publicKey,
+        mint: mint,
+        projectNonce: projectNonce,
+        amount: BigInt(claimAmount),
+        programId: program.programId,
+        version: 1,
+        nonce,
+        deadline,
+      });
+      const ed25519Ix = createEd25519Instruction(
+        distributorKeypair,
+        Buffer.from(serialize(AirdropMessage.schema, msg))
+      );
+      const claimIx = await program.methods
+        .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+        .accountsPartial({
+          tokenProgram: TOKEN_PROGRAM_ID,
+          recipient: recipientKeypair.publicKey,
+          payer: recipientKeypair.publicKey,
+          globalConfig: globalConfigPda,
+          project: projectPda,
+          nullifier: getNullifierPda(projectPda, nonce),
+          mint: mint,
+          tokenVault: tokenVault,
+          recipientTokenAccount: recipientTokenAccount
+        })
+        .instruction();
+      return [ed25519Ix, claimIx];
+    };
+
+    const setMaxPerRecipient = (maxPerRecipient: anchor.BN | null) =>
+      program.methods
+        .setProjectMaxPerRecipient(new anchor.BN(projectNonce.toString()), maxPerRecipient)
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda })
+        .signers([authorityKeypair])
+        .rpc();
+
+    // Earlier tests already claimed for this recipient, so cap relative to that total
+    const [recipientClaimStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("recipient_claim"), projectPda.toBuffer(), recipientKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+    const state = await program.account.recipientClaimState.fetch(recipientClaimStatePda);
+    await setMaxPerRecipient(state.totalClaimed.add(new anchor.BN(claimAmount)));
+
+    await sendTransaction(svm, recipientKeypair, await buildClaim(BigInt(800)));
+
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildClaim(BigInt(801)));
+      expect.fail("Should have failed above the per-recipient cap");
+    } catch (error) {
+      expect(error.message).to.include("RecipientCapExceeded");
+    }
+
+    // Remove the cap for other tests
+    await setMaxPerRecipient(null);
+  });
+
+
+  it("Enforces the active phase and applies its bonus", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
+    const buildClaim = async (nonce: bigint, phase: number) => {
+      const msg = createAirdropMessage({
+        recipient: recipientKeypair.publicKey,
+        mint: mint,
+        projectNonce: projectNonce,
+        amount: BigInt(claimAmount),
+        programId: program.programId,
+        version: 1,
+        nonce,
+        deadline,
+        phase,
+      });
+      const ed25519Ix = createEd25519Instruction(
+        distributorKeypair,
+        Buffer.from(serialize(AirdropMessage.schema, msg))
+      );
+      const claimIx = await program.methods
+        .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+        .accountsPartial({
+          tokenProgram: TOKEN_PROGRAM_ID,
+          recipient: recipientKeypair.publicKey,
+          payer: recipientKeypair.publicKey,
+          globalConfig: globalConfigPda,
+          project: projectPda,
+          nullifier: getNullifierPda(projectPda, nonce),
+          mint: mint,
+          tokenVault: tokenVault,
+          recipientTokenAccount: recipientTokenAccount
+        })
+        .instruction();
+      return [ed25519Ix, claimIx];
+    };
+
+    const setPhases = (phases: any[]) =>
+      program.methods
+        .setProjectPhases(new anchor.BN(projectNonce.toString()), phases)
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda })
+        .signers([authorityKeypair])
+        .rpc();
+
+    // A finished whitelist phase followed by a public phase with a 10% bonus and one claim
+    const now = Number(svm.getClock().unixTimestamp);
+    await setPhases([
+      { startTime: new anchor.BN(now - 7200), endTime: new anchor.BN(now - 3600), maxClaims: new anchor.BN(0), bonusBps: 0, claims: new anchor.BN(0) },
+      { startTime: new anchor.BN(now - 3600), endTime: new anchor.BN(now + 3600), maxClaims: new anchor.BN(1), bonusBps: 1000, claims: new anchor.BN(0) },
+    ]);
+
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildClaim(BigInt(900), 0));
+      expect.fail("Should have failed for a message signed for an inactive phase");
+    } catch (error) {
+      expect(error.message).to.include("PhaseMismatch");
+    }
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, await buildClaim(BigInt(901), 1));
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(1100000));
+
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildClaim(BigInt(902), 1));
+      expect.fail("Should have failed once the phase claim limit was reached");
+    } catch (error) {
+      expect(error.message).to.include("PhaseClaimLimitReached");
+    }
+
+    // Remove the phases for other tests
+    await setPhases([]);
+  });
+
+  it("Claims several grants in one batch with a single transfer", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+    const nonces = [BigInt(1000), BigInt(1001), BigInt(1002)];
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
+    // The distributors sign one Borsh-encoded vector holding a message per nonce
+    const messages = nonces.map((nonce) =>
+      createAirdropMessage({
+        recipient: recipientKeypair.publicKey,
+        mint: mint,
+        projectNonce: projectNonce,
+        amount: BigInt(claimAmount),
+        programId: program.programId,
+        version: 1,
+        nonce,
+        deadline,
+      })
+    );
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize({ array: { type: AirdropMessage.schema } }, messages))
+    );
+
+    const claimBatchIx = await program.methods
+      .claimBatch(
+        new anchor.BN(projectNonce.toString()),
+        nonces.map((nonce) => new anchor.BN(nonce.toString()))
+      )
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        recipient: recipientKeypair.publicKey,
+        payer: recipientKeypair.publicKey,
+        project: projectPda,
+        mint: mint,
+        tokenVault: tokenVault,
+        recipientTokenAccount: recipientTokenAccount
+      })
+      .remainingAccounts(
+        nonces.map((nonce) => ({ pubkey: getNullifierPda(projectPda, nonce), isSigner: false, isWritable: true }))
+      )
+      .instruction();
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimBatchIx]);
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(claimAmount * nonces.length));
+
+    for (const nonce of nonces) {
+      const nullifier = await program.account.claimNullifier.fetch(getNullifierPda(projectPda, nonce));
+      expect(nullifier.nonce.toString()).to.equal(nonce.toString());
+    }
+
+    // Replaying the batch fails on the already-used nullifiers
+    svm.expireBlockhash();
+    try {
+      await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimBatchIx]);
+      expect.fail("Should have failed for already-used nullifiers");
+    } catch (error) {
+      expect(error.message).to.include("InvalidNullifier");
+    }
+  });
+
+  it("Lets a delegate claim with the recipient's signed consent", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+    const nonce = BigInt(1100);
+    const delegateKeypair = Keypair.generate();
+    await svm.airdrop(delegateKeypair.publicKey, BigInt(10000000000));
+
+    const delegateTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      delegateKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(claimAmount),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline,
+    });
+    const distributorIx = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+
+    const buildDelegationIx = (signer: Keypair) => {
+      const delegation = new DelegationMessage({
+        recipient: recipientKeypair.publicKey.toBytes(),
+        delegate: delegateKeypair.publicKey.toBytes(),
+        project_nonce: projectNonce,
+        domain: new MessageDomain({
+          program_id: program.programId.toBytes(),
+          version: 1,
+          nonce,
+          deadline,
+          kind: MessageKind.Delegation,
+        }),
+      });
+      return createEd25519Instruction(signer, Buffer.from(serialize(DelegationMessage.schema, delegation)));
+    };
+
+    const claimForIx = await program.methods
+      .claimFor(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()))
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        delegate: delegateKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: projectPda,
+        nullifier: getNullifierPda(projectPda, nonce),
+        mint: mint,
+        tokenVault: tokenVault,
+        delegateTokenAccount: delegateTokenAccount
+      })
+      .instruction();
+
+    // A delegation signed by anyone but the recipient is rejected
+    try {
+      await sendTransaction(svm, delegateKeypair, [buildDelegationIx(delegateKeypair), distributorIx, claimForIx]);
+      expect.fail("Should have failed without the recipient's signature");
+    } catch (error) {
+      expect(error.message).to.include("InvalidDelegation");
+    }
+
+    await sendTransaction(svm, delegateKeypair, [buildDelegationIx(recipientKeypair), distributorIx, claimForIx]);
+    expect(await getSplTokenBalance(svm, mint, delegateKeypair.publicKey)).to.equal(BigInt(claimAmount));
+  });
+
+  it("Claims a signed allocation in chunks up to its total", async () => {
+    const allocationAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+    const nonce = BigInt(1200);
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(allocationAmount),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline,
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+
+    const claimChunk = async (claimAmount: number) => {
+      const claimPartialIx = await program.methods
+        .claimPartial(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), new anchor.BN(claimAmount))
+        .accountsPartial({
+          tokenProgram: TOKEN_PROGRAM_ID,
+          payer: recipientKeypair.publicKey,
+          recipient: recipientKeypair.publicKey,
+          project: projectPda,
+          allocation: getNullifierPda(projectPda, nonce),
+          mint: mint,
+          tokenVault: tokenVault,
+          recipientTokenAccount: recipientTokenAccount
+        })
+        .instruction();
+      svm.expireBlockhash();
+      await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimPartialIx]);
+    };
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await claimChunk(400000);
+    await claimChunk(600000);
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(allocationAmount));
+
+    const allocation = await program.account.allocation.fetch(getNullifierPda(projectPda, nonce));
+    expect(allocation.total.toNumber()).to.equal(allocationAmount);
+    expect(allocation.claimedSoFar.toNumber()).to.equal(allocationAmount);
+
+    try {
+      await claimChunk(1);
+      expect.fail("Should have failed once the allocation was exhausted");
+    } catch (error) {
+      expect(error.message).to.include("ClaimExceedsAllocation");
+    }
+  });
+
+
+  it("Releases a vesting allocation linearly after its cliff", async () => {
+    const allocationAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+    const nonce = BigInt(1300);
+    const cliffTs = svm.getClock().unixTimestamp;
+    const vestEndTs = cliffTs + BigInt(1000);
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(allocationAmount),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline,
+      cliffTs,
+      vestEndTs,
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+
+    const claimVestedAt = async (unixTimestamp: bigint) => {
+      const currentClock = svm.getClock();
+      svm.setClock(new Clock(
+        currentClock.slot,
+        currentClock.epochStartTimestamp,
+        currentClock.epoch,
+        currentClock.leaderScheduleEpoch,
+        unixTimestamp
+      ));
+
+      const claimVestingIx = await program.methods
+        .claimVesting(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()))
+        .accountsPartial({
+          tokenProgram: TOKEN_PROGRAM_ID,
+          payer: recipientKeypair.publicKey,
+          recipient: recipientKeypair.publicKey,
+          project: projectPda,
+          allocation: getNullifierPda(projectPda, nonce),
+          mint: mint,
+          tokenVault: tokenVault,
+          recipientTokenAccount: recipientTokenAccount
+        })
+        .instruction();
+      svm.expireBlockhash();
+      await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimVestingIx]);
+    };
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await claimVestedAt(cliffTs + BigInt(500));
+    const balanceHalfway = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceHalfway - balanceBefore).to.equal(BigInt(allocationAmount / 2));
+
+    try {
+      await claimVestedAt(cliffTs + BigInt(500));
+      expect.fail("Should have failed with nothing newly vested");
+    } catch (error) {
+      expect(error.message).to.include("NothingVested");
+    }
+
+    await claimVestedAt(vestEndTs);
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(allocationAmount));
+
+    const allocation = await program.account.allocation.fetch(getNullifierPda(projectPda, nonce));
+    expect(allocation.claimedSoFar.toNumber()).to.equal(allocationAmount);
+  });
+
+
+  it("Streams a grant that anyone can withdraw as it accrues", async () => {
+    const streamAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+    const nonce = BigInt(1400);
+    const startTs = svm.getClock().unixTimestamp;
+    const endTs = startTs + BigInt(1000);
+    const streamPda = getNullifierPda(projectPda, nonce);
+
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(streamAmount),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline,
+      cliffTs: startTs,
+      vestEndTs: endTs,
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+    const createStreamIx = await program.methods
+      .createStream(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()))
+      .accountsPartial({
+        payer: recipientKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: projectPda,
+        stream: streamPda,
+        mint: mint,
+      })
+      .instruction();
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, createStreamIx]);
+
+    // Withdraw halfway through, paid for by an unrelated account
+    const currentClock = svm.getClock();
+    svm.setClock(new Clock(
+      currentClock.slot,
+      currentClock.epochStartTimestamp,
+      currentClock.epoch,
+      currentClock.leaderScheduleEpoch,
+      startTs + BigInt(250)
+    ));
+
+    const keeper = Keypair.generate();
+    await svm.airdrop(keeper.publicKey, BigInt(10000000000));
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    const withdrawStreamIx = await program.methods
+      .withdrawStream(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()))
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        payer: keeper.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: projectPda,
+        stream: streamPda,
+        mint: mint,
+        tokenVault: tokenVault,
+      })
+      .instruction();
+    await sendTransaction(svm, keeper, [withdrawStreamIx]);
+
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(streamAmount / 4));
+
+    const stream = await program.account.stream.fetch(streamPda);
+    expect(stream.withdrawn.toNumber()).to.equal(streamAmount / 4);
+    expect(stream.lastWithdrawnTs.toString()).to.equal((startTs + BigInt(250)).toString());
+  });
+
+
+  it("Splits a referral reward out of the claim", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+    const nonce = BigInt(1500);
+
+    const referrerTokenAccount = await getOrCreateAssociatedTokenAccount(
+      svm,
+      mint,
+      partnerKeypair.publicKey,
+      false,
+      partnerKeypair
+    );
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(claimAmount),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline,
+      referrer: partnerKeypair.publicKey,
+      referralBps: 500, // 5%
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+
+    const recipientBalanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    const referrerBalanceBefore = await getSplTokenBalance(svm, mint, partnerKeypair.publicKey);
+
+    const claimIx = await program.methods
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        payer: recipientKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: projectPda,
+        mint: mint,
+        tokenVault: tokenVault,
+        recipientTokenAccount: recipientTokenAccount,
+        referrerTokenAccount: referrerTokenAccount,
+      })
+      .instruction();
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimIx]);
+
+    const recipientBalanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    const referrerBalanceAfter = await getSplTokenBalance(svm, mint, partnerKeypair.publicKey);
+    expect(referrerBalanceAfter - referrerBalanceBefore).to.equal(BigInt(50000));
+    expect(recipientBalanceAfter - recipientBalanceBefore).to.equal(BigInt(950000));
+  });
+
+
+  it("Enforces tier opening times and bonuses", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
+    const buildClaim = async (nonce: bigint, tier: number) => {
+      const msg = createAirdropMessage({
+        recipient: recipientKeypair.publicKey,
+        mint: mint,
+        projectNonce: projectNonce,
+        amount: BigInt(claimAmount),
+        programId: program.programId,
+        version: 1,
+        nonce,
+        deadline,
+        tier,
+      });
+      const ed25519Ix = createEd25519Instruction(
+        distributorKeypair,
+        Buffer.from(serialize(AirdropMessage.schema, msg))
+      );
+      const claimIx = await program.methods
+        .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+        .accountsPartial({
+          tokenProgram: TOKEN_PROGRAM_ID,
+          recipient: recipientKeypair.publicKey,
+          payer: recipientKeypair.publicKey,
+          project: projectPda,
+          nullifier: getNullifierPda(projectPda, nonce),
+          mint: mint,
+          tokenVault: tokenVault,
+          recipientTokenAccount: recipientTokenAccount
+        })
+        .instruction();
+      return [ed25519Ix, claimIx];
+    };
+
+    const setTiers = (tiers: any[]) =>
+      program.methods
+        .setProjectTiers(new anchor.BN(projectNonce.toString()), tiers)
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda })
+        .signers([authorityKeypair])
+        .rpc();
+
+    // A public tier opening in an hour, and an OG tier already open with a 10% bonus
+    const now = Number(svm.getClock().unixTimestamp);
+    await setTiers([
+      { earliestClaimTime: new anchor.BN(now + 3600), bonusBps: 0 },
+      { earliestClaimTime: new anchor.BN(0), bonusBps: 1000 },
+    ]);
+
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildClaim(BigInt(1600), 0));
+      expect.fail("Should have failed before the tier opened");
+    } catch (error) {
+      expect(error.message).to.include("TierNotOpen");
+    }
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, await buildClaim(BigInt(1601), 1));
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(1100000));
+
+    // Remove the tiers for other tests
+    await setTiers([]);
+  });
+
+
+  it("Redeems one entry of a multi-grant message", async () => {
+    const deadline = BigInt(9999999999); // Far future deadline
+    const entries = [
+      { recipient: partnerKeypair.publicKey.toBytes(), amount: BigInt(300000), nonce: BigInt(1700) },
+      { recipient: recipientKeypair.publicKey.toBytes(), amount: BigInt(700000), nonce: BigInt(1701) },
+    ];
+
+    const msg = new MultiGrantMessage({
+      mint: mint.toBytes(),
+      project_nonce: projectNonce,
+      phase: 0,
+      tier: 0,
+      entries,
+      domain: new MessageDomain({
+        program_id: program.programId.toBytes(),
+        version: 1,
+        nonce: BigInt(0),
+        deadline,
+      }),
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(MultiGrantMessage.schema, msg))
+    );
+
+    const buildClaimEntry = (index: number) =>
+      program.methods
+        .claimEntry(new anchor.BN(projectNonce.toString()), new anchor.BN(entries[index].nonce.toString()), index)
+        .accountsPartial({
+          tokenProgram: TOKEN_PROGRAM_ID,
+          payer: recipientKeypair.publicKey,
+          recipient: recipientKeypair.publicKey,
+          project: projectPda,
+          nullifier: getNullifierPda(projectPda, entries[index].nonce),
+          mint: mint,
+          tokenVault: tokenVault,
+        })
+        .instruction();
+
+    try {
+      await sendTransaction(svm, recipientKeypair, [ed25519Ix, await buildClaimEntry(0)]);
+      expect.fail("Should have failed to redeem another recipient's entry");
+    } catch (error) {
+      expect(error.message).to.include("RecipientMismatch");
+    }
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, await buildClaimEntry(1)]);
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(700000));
+
+    // The other entry's nonce is still unused
+    expect(svm.getAccount(getNullifierPda(projectPda, entries[0].nonce))).to.be.null;
+  });
+
+
+
+  it("Holds claims in a lockup escrow until the lock duration passes", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+    const nonce = BigInt(1800);
+    const lockDuration = 86400;
+
+    const setLockDuration = (duration: number) =>
+      program.methods
+        .setProjectLockDuration(new anchor.BN(projectNonce.toString()), new anchor.BN(duration))
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda })
+        .signers([authorityKeypair])
+        .rpc();
+    await setLockDuration(lockDuration);
+
+    const [lockedClaimPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("locked_claim"), projectPda.toBuffer(), recipientKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+    const [lockedClaimVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("locked_claim_vault"), projectPda.toBuffer(), recipientKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(claimAmount),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline,
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    const claimIx = await program.methods
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        payer: recipientKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: projectPda,
+        nullifier: getNullifierPda(projectPda, nonce),
+        mint: mint,
+        tokenVault: tokenVault,
+        lockedClaim: lockedClaimPda,
+        lockedClaimVault: lockedClaimVault,
+      })
+      .instruction();
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimIx]);
+
+    // Nothing reaches the recipient until the lockup ends
+    expect(await getSplTokenBalance(svm, mint, recipientKeypair.publicKey)).to.equal(balanceBefore);
+    const lockedClaim = await program.account.lockedClaim.fetch(lockedClaimPda);
+    expect(lockedClaim.amount.toNumber()).to.equal(claimAmount);
+
+    const unlockIx = await program.methods
+      .unlock(new anchor.BN(projectNonce.toString()))
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        payer: recipientKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: projectPda,
+        lockedClaim: lockedClaimPda,
+        mint: mint,
+      })
+      .instruction();
+
+    try {
+      await sendTransaction(svm, recipientKeypair, [unlockIx]);
+      expect.fail("Should have failed before the lockup ended");
+    } catch (error) {
+      expect(error.message).to.include("LockupNotExpired");
+    }
+
+    const currentClock = svm.getClock();
+    svm.setClock(new Clock(
+      currentClock.slot,
+      currentClock.epochStartTimestamp,
+      currentClock.epoch,
+      currentClock.leaderScheduleEpoch,
+      BigInt(lockedClaim.unlockTs.toString())
+    ));
+    svm.expireBlockhash();
+    await sendTransaction(svm, recipientKeypair, [unlockIx]);
+
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(claimAmount));
+    expect(svm.getAccount(lockedClaimPda)).to.be.null;
+
+    // Pay claims out directly again for other tests
+    await setLockDuration(0);
+  });
+
+
+  it("Rejects zero amounts and amounts above the project maximum", async () => {
+    const deadline = BigInt(9999999999); // Far future deadline
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
+    const buildClaim = async (nonce: bigint, amount: bigint) => {
+      const msg = createAirdropMessage({
+        recipient: recipientKeypair.publicKey,
+        mint: mint,
+        projectNonce: projectNonce,
+        amount,
+        programId: program.programId,
+        version: 1,
+        nonce,
+        deadline,
+      });
+      const ed25519Ix = createEd25519Instruction(
+        distributorKeypair,
+        Buffer.from(serialize(AirdropMessage.schema, msg))
+      );
+      const claimIx = await program.methods
+        .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+        .accountsPartial({
+          tokenProgram: TOKEN_PROGRAM_ID,
+          recipient: recipientKeypair.publicKey,
+          payer: recipientKeypair.publicKey,
+          project: projectPda,
+          nullifier: getNullifierPda(projectPda, nonce),
+          mint: mint,
+          tokenVault: tokenVault,
+          recipientTokenAccount: recipientTokenAccount
+        })
+        .instruction();
+      return [ed25519Ix, claimIx];
+    };
+
+    const setMaxClaimAmount = (maxClaimAmount: anchor.BN | null) =>
+      program.methods
+        .setProjectMaxClaimAmount(new anchor.BN(projectNonce.toString()), maxClaimAmount)
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda })
+        .signers([authorityKeypair])
+        .rpc();
+
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildClaim(BigInt(1900), BigInt(0)));
+      expect.fail("Should have failed with a zero amount");
+    } catch (error) {
+      expect(error.message).to.include("ZeroClaimAmount");
+    }
+
+    await setMaxClaimAmount(new anchor.BN(500000));
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildClaim(BigInt(1901), BigInt(1000000)));
+      expect.fail("Should have failed above the project maximum");
+    } catch (error) {
+      expect(error.message).to.include("ClaimAmountExceedsProjectMax");
+    }
+
+    // Remove the cap for other tests
+    await setMaxClaimAmount(null);
+  });
+
+
+  it("Requires the recipient to pay when the project demands it", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+    const nonce = BigInt(2000);
+
+    const setRecipientMustPay = (recipientMustPay: boolean) =>
+      program.methods
+        .setProjectRecipientMustPay(new anchor.BN(projectNonce.toString()), recipientMustPay)
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda })
+        .signers([authorityKeypair])
+        .rpc();
+    await setRecipientMustPay(true);
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(claimAmount),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline,
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+    const buildClaim = (payer: Keypair) =>
+      program.methods
+        .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+        .accountsPartial({
+          tokenProgram: TOKEN_PROGRAM_ID,
+          payer: payer.publicKey,
+          recipient: recipientKeypair.publicKey,
+          project: projectPda,
+          nullifier: getNullifierPda(projectPda, nonce),
+          mint: mint,
+          tokenVault: tokenVault,
+          recipientTokenAccount: recipientTokenAccount,
+        })
+        .instruction();
+
+    // A farming wallet cannot pay for the recipient's claim
+    try {
+      await sendTransaction(svm, partnerKeypair, [ed25519Ix, await buildClaim(partnerKeypair)]);
+      expect.fail("Should have failed with a third-party payer");
+    } catch (error) {
+      expect(error.message).to.include("RecipientMustPay");
+    }
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, await buildClaim(recipientKeypair)]);
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(claimAmount));
+
+    await setRecipientMustPay(false);
+  });
+
+
+  it("Redeems a ticket for the project's fixed amount", async () => {
+    const ticketAmount = 250000;
+
+    // A ticket mint handed out at an event, one ticket per attendee
+    const ticketMint = await createSplToken(provider, authorityKeypair, 0);
+    const ticketAccount = await getOrCreateAssociatedTokenAccount(
+      svm,
+      ticketMint,
+      recipientKeypair.publicKey,
+      false,
+      recipientKeypair
+    );
+    await sendTransaction(svm, authorityKeypair, [
+      createMintToInstruction(ticketMint, ticketAccount, authorityKeypair.publicKey, BigInt(1), [], TOKEN_PROGRAM_ID),
+    ]);
+
+    await program.methods
+      .setProjectTicket(new anchor.BN(projectNonce.toString()), {
+        mint: ticketMint,
+        amount: new anchor.BN(ticketAmount),
+      })
+      .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    const claimIx = await program.methods
+      .claimWithTicket(new anchor.BN(projectNonce.toString()))
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        ticketTokenProgram: TOKEN_PROGRAM_ID,
+        payer: recipientKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: projectPda,
+        mint: mint,
+        tokenVault: tokenVault,
+        ticketMint: ticketMint,
+        ticketTokenAccount: ticketAccount,
+      })
+      .instruction();
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, [claimIx]);
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(ticketAmount));
+    expect(await getSplTokenBalance(svm, ticketMint, recipientKeypair.publicKey)).to.equal(BigInt(0));
+
+    // The burned ticket cannot be redeemed again
+    svm.expireBlockhash();
+    try {
+      await sendTransaction(svm, recipientKeypair, [claimIx]);
+      expect.fail("Should have failed without a ticket");
+    } catch (error) {
+      expect(error.message).to.include("Unknown error");
+    }
+
+    await program.methods
+      .setProjectTicket(new anchor.BN(projectNonce.toString()), null)
+      .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda })
+      .signers([authorityKeypair])
+      .rpc();
+  });
+
+  it("Requires a valid attestation when the project gates claims on one", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+    const nonce = BigInt(2100);
+
+    // An attestation program account in the Solana Attestation Service layout
+    const attestationProgram = Keypair.generate().publicKey;
+    const credential = Keypair.generate().publicKey;
+    const schema = Keypair.generate().publicKey;
+    const setAttestationGate = (gate: { program: PublicKey; credential: PublicKey; schema: PublicKey } | null) =>
+      program.methods
+        .setProjectAttestationGate(new anchor.BN(projectNonce.toString()), gate)
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda })
+        .signers([authorityKeypair])
+        .rpc();
+    await setAttestationGate({ program: attestationProgram, credential, schema });
+
+    const attestation = Keypair.generate().publicKey;
+    svm.setAccount(attestation, {
+      lamports: 1000000000,
+      data: Buffer.concat([
+        Buffer.from([2]),
+        recipientKeypair.publicKey.toBuffer(),
+        credential.toBuffer(),
+        schema.toBuffer(),
+        Buffer.alloc(4), // empty attestation data
+        Keypair.generate().publicKey.toBuffer(),
+        Buffer.alloc(8), // never expires
+        PublicKey.default.toBuffer(),
+      ]),
+      owner: attestationProgram,
+      executable: false,
+    });
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(claimAmount),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline,
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+    const buildClaim = (attestationAccount: PublicKey | null) =>
+      program.methods
+        .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+        .accountsPartial({
+          tokenProgram: TOKEN_PROGRAM_ID,
+          payer: recipientKeypair.publicKey,
+          recipient: recipientKeypair.publicKey,
+          project: projectPda,
+          nullifier: getNullifierPda(projectPda, nonce),
+          mint: mint,
+          tokenVault: tokenVault,
+          recipientTokenAccount: recipientTokenAccount,
+          attestation: attestationAccount,
+        })
+        .instruction();
+
+    try {
+      await sendTransaction(svm, recipientKeypair, [ed25519Ix, await buildClaim(null)]);
+      expect.fail("Should have failed without an attestation");
+    } catch (error) {
+      expect(error.message).to.include("AttestationRequired");
+    }
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, await buildClaim(attestation)]);
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(claimAmount));
+
+    await setAttestationGate(null);
+  });
+
+  it("Treats a retried idempotent claim as a no-op", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+    const nonce = BigInt(2200);
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(claimAmount),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline,
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+    const claimIx = await program.methods
+      .claimIdempotent(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()))
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        payer: partnerKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: projectPda,
+        nullifier: getNullifierPda(projectPda, nonce),
+        mint: mint,
+        tokenVault: tokenVault,
+        recipientTokenAccount: recipientTokenAccount,
+      })
+      .instruction();
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, partnerKeypair, [ed25519Ix, claimIx]);
+    const balanceAfterClaim = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfterClaim - balanceBefore).to.equal(BigInt(claimAmount));
+
+    // The relayer retries the same claim, which succeeds without paying again
+    svm.expireBlockhash();
+    await sendTransaction(svm, partnerKeypair, [ed25519Ix, claimIx]);
+    const balanceAfterRetry = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfterRetry).to.equal(balanceAfterClaim);
+
+    const record = await program.account.idempotentClaim.fetch(getNullifierPda(projectPda, nonce));
+    expect(record.recipient.toBase58()).to.equal(recipientKeypair.publicKey.toBase58());
+    expect(record.amount.toNumber()).to.equal(claimAmount);
+
+    // A different grant signed under the same nonce is not a retry of the claim that landed
+    const otherMsg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(claimAmount * 2),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline,
+    });
+    const otherEd25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, otherMsg))
+    );
+    try {
+      await sendTransaction(svm, partnerKeypair, [otherEd25519Ix, claimIx]);
+      expect.fail("Should have failed for a grant that does not match the recorded claim");
+    } catch (error) {
+      expect(error.message).to.include("ClaimRecordMismatch");
+    }
+  });
+
+  it("Accepts the Ed25519 instruction at an explicit index", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+    const nonce = BigInt(2300);
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(claimAmount),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline,
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+    const computeBudgetIx = ComputeBudgetProgram.setComputeUnitLimit({ units: 400000 });
+    const buildClaim = (ed25519IxIndex: number) =>
+      program.methods
+        .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), ed25519IxIndex, null)
+        .accountsPartial({
+          tokenProgram: TOKEN_PROGRAM_ID,
+          payer: recipientKeypair.publicKey,
+          recipient: recipientKeypair.publicKey,
+          project: projectPda,
+          nullifier: getNullifierPda(projectPda, nonce),
+          mint: mint,
+          tokenVault: tokenVault,
+          recipientTokenAccount: recipientTokenAccount,
+        })
+        .instruction();
+
+    // The compute-budget instruction is not a signature verification
+    try {
+      await sendTransaction(svm, recipientKeypair, [ed25519Ix, computeBudgetIx, await buildClaim(1)]);
+      expect.fail("Should have failed pointing at the compute-budget instruction");
+    } catch (error) {
+      expect(error.message).to.include("BadEd25519Program");
+    }
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, computeBudgetIx, await buildClaim(0)]);
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(claimAmount));
+  });
+
+  it("Rejects malformed Ed25519 instruction payloads", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+    const nonce = BigInt(2400);
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(claimAmount),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline,
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+    const claimIx = await program.methods
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        payer: recipientKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: projectPda,
+        nullifier: getNullifierPda(projectPda, nonce),
+        mint: mint,
+        tokenVault: tokenVault,
+        recipientTokenAccount: recipientTokenAccount,
+      })
+      .instruction();
+
+    // Bytes appended after the signed message are not covered by any signature
+    const trailingIx = new TransactionInstruction({
+      keys: [],
+      programId: Ed25519Program.programId,
+      data: Buffer.concat([ed25519Ix.data, Buffer.from([0])]),
+    });
+    try {
+      await sendTransaction(svm, recipientKeypair, [trailingIx, claimIx]);
+      expect.fail("Should have failed with trailing bytes");
+    } catch (error) {
+      expect(error.message).to.include("Ed25519TrailingBytes");
+    }
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimIx]);
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(claimAmount));
+  });
+
+  it("Only counts distributors that signed the claim message", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+    const nonce = BigInt(2500);
+    const otherDistributor = Keypair.generate();
+
+    await program.methods
+      .updateGlobalConfig({ add: { distributor: otherDistributor.publicKey, weight: 1, keyType: { ed25519: {} } } })
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+    await program.methods
+      .updateGlobalConfig({ setThreshold: { threshold: 2 } })
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    const buildMessage = (amount: number) =>
+      Buffer.from(serialize(AirdropMessage.schema, createAirdropMessage({
+        recipient: recipientKeypair.publicKey,
+        mint: mint,
+        projectNonce: projectNonce,
+        amount: BigInt(amount),
+        programId: program.programId,
+        version: 1,
+        nonce,
+        deadline,
+      })));
+    const claimIx = await program.methods
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        payer: recipientKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: projectPda,
+        nullifier: getNullifierPda(projectPda, nonce),
+        mint: mint,
+        tokenVault: tokenVault,
+        recipientTokenAccount: recipientTokenAccount,
+      })
+      .instruction();
+
+    // The second distributor signed a different grant, so only one signature counts
+    const mixedIx = createEd25519InstructionWithMessages([
+      { signer: distributorKeypair, message: buildMessage(claimAmount) },
+      { signer: otherDistributor, message: buildMessage(1) },
+    ]);
+    try {
+      await sendTransaction(svm, recipientKeypair, [mixedIx, claimIx]);
+      expect.fail("Should have failed below the threshold");
+    } catch (error) {
+      expect(error.message).to.include("ThresholdNotMet");
+    }
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    const agreedIx = createEd25519InstructionWithMessages([
+      { signer: distributorKeypair, message: buildMessage(claimAmount) },
+      { signer: otherDistributor, message: buildMessage(claimAmount) },
+    ]);
+    await sendTransaction(svm, recipientKeypair, [agreedIx, claimIx]);
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(claimAmount));
+
+    // Restore the single-signature threshold for other tests
+    await program.methods
+      .updateGlobalConfig({ setThreshold: { threshold: 1 } })
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+  });
+
+  it("Rejects messages signed for another cluster", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+    const nonce = BigInt(2600);
+    const genesisHash = Keypair.generate().publicKey.toBytes();
+
+    const setGenesisHash = (hash: Uint8Array) =>
+      program.methods
+        .setGenesisHash(Array.from(hash))
+        .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+        .signers([authorityKeypair])
+        .rpc();
+    await setGenesisHash(genesisHash);
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    const buildEd25519Ix = (hash: Uint8Array) =>
+      createEd25519Instruction(
+        distributorKeypair,
+        Buffer.from(serialize(AirdropMessage.schema, createAirdropMessage({
+          recipient: recipientKeypair.publicKey,
+          mint: mint,
+          projectNonce: projectNonce,
+          amount: BigInt(claimAmount),
+          programId: program.programId,
+          version: 1,
+          nonce,
+          deadline,
+          genesisHash: hash,
+        })))
+      );
+    const claimIx = await program.methods
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        payer: recipientKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: projectPda,
+        nullifier: getNullifierPda(projectPda, nonce),
+        mint: mint,
+        tokenVault: tokenVault,
+        recipientTokenAccount: recipientTokenAccount,
+      })
+      .instruction();
+
+    try {
+      await sendTransaction(svm, recipientKeypair, [buildEd25519Ix(new Uint8Array(32)), claimIx]);
+      expect.fail("Should have failed with a message for another cluster");
+    } catch (error) {
+      expect(error.message).to.include("GenesisHashMismatch");
+    }
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, [buildEd25519Ix(genesisHash), claimIx]);
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(claimAmount));
+
+    await setGenesisHash(new Uint8Array(32));
+  });
+
+  it("Claims with a message signed in the off-chain message format", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+    const nonce = BigInt(2700);
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    const body = Buffer.from(serialize(AirdropMessage.schema, createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(claimAmount),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline,
+    })));
+
+    // Version 0 envelope: signing domain, version, application domain, format, signers, length
+    const length = Buffer.alloc(2);
+    length.writeUInt16LE(body.length);
+    const buildEnvelope = (applicationDomain: PublicKey) =>
+      Buffer.concat([
+        Buffer.from("\xffsolana offchain", "binary"),
+        Buffer.from([0]),
+        applicationDomain.toBuffer(),
+        Buffer.from([2, 1]),
+        distributorKeypair.publicKey.toBuffer(),
+        length,
+        body,
+      ]);
+
+    const claimIx = await program.methods
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        payer: recipientKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: projectPda,
+        nullifier: getNullifierPda(projectPda, nonce),
+        mint: mint,
+        tokenVault: tokenVault,
+        recipientTokenAccount: recipientTokenAccount,
+      })
+      .instruction();
+
+    // The envelope must name this program as its application domain
+    try {
+      const foreignIx = createEd25519Instruction(distributorKeypair, buildEnvelope(Keypair.generate().publicKey));
+      await sendTransaction(svm, recipientKeypair, [foreignIx, claimIx]);
+      expect.fail("Should have failed with another application domain");
+    } catch (error) {
+      expect(error.message).to.include("InvalidOffchainMessage");
+    }
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    const ed25519Ix = createEd25519Instruction(distributorKeypair, buildEnvelope(program.programId));
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimIx]);
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(claimAmount));
+  });
+
+  it("Rejects claim messages tagged with an unknown layout", async () => {
+    const claimAmount = 1000000;
+    const deadline = BigInt(9999999999); // Far future deadline
+    const nonce = BigInt(2800);
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(claimAmount),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline,
+    });
+    const claimIx = await program.methods
+      .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        payer: recipientKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: projectPda,
+        nullifier: getNullifierPda(projectPda, nonce),
+        mint: mint,
+        tokenVault: tokenVault,
+        recipientTokenAccount: recipientTokenAccount,
+      })
+      .instruction();
+
+    try {
+      const unknownLayout = new AirdropMessage({ data: msg.data, domain: msg.domain, layout: 1 });
+      const ed25519Ix = createEd25519Instruction(
+        distributorKeypair,
+        Buffer.from(serialize(AirdropMessage.schema, unknownLayout))
+      );
+      await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimIx]);
+      expect.fail("Should have failed with an unknown layout");
+    } catch (error) {
+      expect(error.message).to.include("InvalidMessage");
+    }
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimIx]);
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(claimAmount));
+  });
+
+  it("Claims an allocation proven under a signed Merkle root", async () => {
+    const claimAmount = BigInt(1000000);
+    const nonce = BigInt(2900);
+
+    // Two allocations: the recipient's, and another recipient's that is the proof sibling
+    const recipientLeaf = merkleLeaf(recipientKeypair.publicKey, claimAmount, nonce);
+    const siblingLeaf = merkleLeaf(Keypair.generate().publicKey, claimAmount, BigInt(2901));
+    const root = merkleParent(recipientLeaf, siblingLeaf);
+
+    const rootMsg = new MerkleRootMessage({
+      mint: mint.toBytes(),
+      project_nonce: projectNonce,
+      phase: 0,
+      tier: 0,
+      root,
+      domain: new MessageDomain({
+        program_id: program.programId.toBytes(),
+        version: 1,
+        nonce: BigInt(1), // not checked; each leaf carries its claim nonce
+        deadline: BigInt(9999999999),
+        kind: MessageKind.MerkleRoot,
+      }),
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(MerkleRootMessage.schema, rootMsg))
+    );
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    const buildClaimIx = (amount: bigint) =>
+      program.methods
+        .claim(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()), 0, {
+          amount: new anchor.BN(amount.toString()),
+          proof: [Array.from(siblingLeaf)],
+        })
+        .accountsPartial({
+          tokenProgram: TOKEN_PROGRAM_ID,
+          payer: recipientKeypair.publicKey,
+          recipient: recipientKeypair.publicKey,
+          project: projectPda,
+          nullifier: getNullifierPda(projectPda, nonce),
+          mint: mint,
+          tokenVault: tokenVault,
+          recipientTokenAccount: recipientTokenAccount,
+        })
+        .instruction();
+
+    try {
+      await sendTransaction(svm, recipientKeypair, [ed25519Ix, await buildClaimIx(claimAmount * BigInt(2))]);
+      expect.fail("Should have failed with an amount outside the signed allocations");
+    } catch (error) {
+      expect(error.message).to.include("InvalidMerkleProof");
+    }
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, await buildClaimIx(claimAmount)]);
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(claimAmount);
+  });
+
+  it("Counts every claim and its amount on the project", async () => {
+    const claimAmount = BigInt(1000000);
+    const before = await program.account.project.fetch(projectPda);
+
+    await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3000), claimAmount));
+    await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3001), claimAmount));
+
+    const after = await program.account.project.fetch(projectPda);
+    expect(after.claimCount.sub(before.claimCount).toNumber()).to.equal(2);
+    expect(after.totalClaimed.sub(before.totalClaimed).toString()).to.equal((claimAmount * BigInt(2)).toString());
+  });
+
+  it("Redeems claim messages signed before layouts were tagged", async () => {
+    const claimAmount = BigInt(1000000);
+    const nonce = BigInt(3002);
+
+    // Same fields as AirdropMessage, without the leading layout tag
+    const untaggedSchema: BorshSchema = {
+      struct: {
+        data: AirdropMessageData.schema,
+        domain: MessageDomain.schema,
+      }
+    };
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: claimAmount,
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline: BigInt(9999999999),
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(untaggedSchema, msg))
+    );
+    const [, claimIx] = await buildSignedClaim(nonce, claimAmount);
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimIx]);
+    const balanceAfter = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(claimAmount);
+  });
+
+  it("Refuses to open a stream for a denylisted recipient", async () => {
+    const nonce = BigInt(3003);
+    const startTs = svm.getClock().unixTimestamp;
+    const streamPda = getNullifierPda(projectPda, nonce);
+
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(1000000),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline: BigInt(9999999999),
+      cliffTs: startTs,
+      vestEndTs: startTs + BigInt(1000),
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+    const createStreamIx = await program.methods
+      .createStream(new anchor.BN(projectNonce.toString()), new anchor.BN(nonce.toString()))
+      .accountsPartial({
+        payer: recipientKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: projectPda,
+        stream: streamPda,
+        mint: mint,
+      })
+      .instruction();
+
+    await program.methods
+      .addToDenylist(recipientKeypair.publicKey)
+      .accountsPartial({ payer: authorityKeypair.publicKey, authority: authorityKeypair.publicKey })
+      .signers([authorityKeypair])
+      .rpc();
+
+    try {
+      await sendTransaction(svm, recipientKeypair, [ed25519Ix, createStreamIx]);
+      expect.fail("Should have failed for a denylisted recipient");
+    } catch (error) {
+      expect(error.message).to.include("RecipientDenylisted");
+    }
+
+    await program.methods
+      .removeFromDenylist(recipientKeypair.publicKey)
+      .accountsPartial({ authority: authorityKeypair.publicKey })
+      .signers([authorityKeypair])
+      .rpc();
+
+    svm.expireBlockhash();
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, createStreamIx]);
+    const stream = await program.account.stream.fetch(streamPda);
+    expect(stream.total.toNumber()).to.equal(1000000);
+  });
+
+  it("Accepts the outgoing distributor set until the rotation's grace period ends", async () => {
+    const newDistributorKeypair = Keypair.generate();
+    const rotate = (distributor: PublicKey, graceSlots: number) =>
+      program.methods
+        .rotateDistributors([{ pubkey: distributor, weight: 1, active: true, keyType: { ed25519: {} } }], 1, 1, new anchor.BN(graceSlots))
+        .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+        .signers([authorityKeypair])
+        .rpc();
+
+    await rotate(newDistributorKeypair.publicKey, 100);
+
+    // Signatures issued before the rotation still claim during the grace period
+    await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3004), BigInt(1000)));
+
+    svm.warpToSlot(svm.getClock().slot + BigInt(101));
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3005), BigInt(1000)));
+      expect.fail("Should have failed once the outgoing set expired");
+    } catch (error) {
+      expect(error.message).to.include("DistributorMismatch");
+    }
+    await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3005), BigInt(1000), newDistributorKeypair));
+
+    // Restore the original distributor for other tests
+    await rotate(distributorKeypair.publicKey, 0);
+  });
+
+  it("Lets a project's own distributor set replace the global one", async () => {
+    const projectDistributorKeypair = Keypair.generate();
+    const setProjectDistributors = (distributors: PublicKey[]) =>
+      program.methods
+        .setProjectDistributors(
+          new anchor.BN(projectNonce.toString()),
+          distributors.map((distributor) => ({ pubkey: distributor, weight: 1, active: true, keyType: { ed25519: {} } }))
+        )
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda })
+        .signers([authorityKeypair])
+        .rpc();
+
+    await setProjectDistributors([projectDistributorKeypair.publicKey]);
+
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3006), BigInt(1000)));
+      expect.fail("Should have failed for a global distributor on a project with its own set");
+    } catch (error) {
+      expect(error.message).to.include("DistributorMismatch");
+    }
+    await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3006), BigInt(1000), projectDistributorKeypair));
+
+    try {
+      await setProjectDistributors([projectDistributorKeypair.publicKey, projectDistributorKeypair.publicKey]);
+      expect.fail("Should have failed for a repeated distributor");
+    } catch (error) {
+      expect(error.message).to.include("DistributorAlreadyExists");
+    }
+
+    // A project's distributors may sign with any supported scheme
+    const passkey = p256.utils.randomPrivateKey();
+    await program.methods
+      .setProjectDistributors(new anchor.BN(projectNonce.toString()), [
+        { pubkey: secp256r1KeyId(passkey), weight: 1, active: true, keyType: { secp256r1: {} } },
+      ])
+      .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda })
+      .signers([authorityKeypair])
+      .rpc();
+    const passkeyMsg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(1000),
+      programId: program.programId,
+      version: 1,
+      nonce: BigInt(3033),
+      deadline: BigInt(9999999999),
+    });
+    const [, passkeyClaimIx] = await buildSignedClaim(BigInt(3033), BigInt(1000));
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, [
+      createSecp256r1Instruction(passkey, Buffer.from(serialize(AirdropMessage.schema, passkeyMsg))),
+      passkeyClaimIx,
+    ]);
+    expect(await getSplTokenBalance(svm, mint, recipientKeypair.publicKey) - balanceBefore).to.equal(BigInt(1000));
+
+    // An empty set falls back to the global distributors
+    await setProjectDistributors([]);
+    await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3007), BigInt(1000)));
+  });
+
+  it("Rejects claims above the global per-claim maximum", async () => {
+    const setMaxClaimAmount = (maxClaimAmount: anchor.BN | null) =>
+      program.methods
+        .setMaxClaimAmount(maxClaimAmount)
+        .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+        .signers([authorityKeypair])
+        .rpc();
+
+    await setMaxClaimAmount(new anchor.BN(5000));
+
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3008), BigInt(5001)));
+      expect.fail("Should have failed above the global maximum");
+    } catch (error) {
+      expect(error.message).to.include("ClaimAmountExceedsMax");
+    }
+    await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3008), BigInt(5000)));
+
+    // Remove the cap for other tests
+    await setMaxClaimAmount(null);
+  });
+
+  it("Caps a project's total claims at its budget", async () => {
+    const setProjectBudget = (budget: anchor.BN) =>
+      program.methods
+        .setProjectBudget(new anchor.BN(projectNonce.toString()), budget)
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda })
+        .signers([authorityKeypair])
+        .rpc();
+
+    const { totalClaimed } = await program.account.project.fetch(projectPda);
+    try {
+      await setProjectBudget(totalClaimed.subn(1));
+      expect.fail("Should have failed for a budget below the amount already claimed");
+    } catch (error) {
+      expect(error.message).to.include("InvalidBudget");
+    }
+
+    await setProjectBudget(totalClaimed.addn(1000));
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3009), BigInt(1001)));
+      expect.fail("Should have failed beyond the project budget");
+    } catch (error) {
+      expect(error.message).to.include("BudgetExceeded");
+    }
+    await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3009), BigInt(1000)));
+
+    // Remove the cap for other tests
+    await setProjectBudget(new anchor.BN(0));
+  });
+
+  it("Grosses up Token-2022 transfer fees when the project opts in", async () => {
+    // A second project distributing a Token-2022 mint with a 1% transfer fee
+    const feeMint = await createTransferFeeToken(provider, authorityKeypair, 100, BigInt(1000000000));
+    const feeProjectNonce = BigInt(2);
+    const [feeProjectPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("project"), Buffer.from(new anchor.BN(feeProjectNonce.toString()).toArray("le", 8))],
+      program.programId
+    );
+    const feeTokenVault = getTokenVaultAddress(program.programId, feeProjectPda);
+    await program.methods
+      .createProject(new anchor.BN(0), new anchor.BN(0), emptyProjectMetadata)
+      .accountsPartial({
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        authority: authorityKeypair.publicKey,
+        project: feeProjectPda,
+        mint: feeMint,
+        tokenVault: feeTokenVault,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+    await sendTransaction(svm, authorityKeypair, [
+      createMintToInstruction(feeMint, feeTokenVault, authorityKeypair.publicKey, BigInt(1000000000), [], TOKEN_2022_PROGRAM_ID),
+    ]);
+
+    const recipientTokenAccount = await getAssociatedTokenAddress(
+      feeMint,
+      recipientKeypair.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    const balanceOf = async (address: PublicKey) =>
+      svm.getAccount(address)
+        ? (await getAccount(provider.connection, address, undefined, TOKEN_2022_PROGRAM_ID)).amount
+        : BigInt(0);
+    const claimReceived = async (nonce: bigint) => {
+      const msg = createAirdropMessage({
+        recipient: recipientKeypair.publicKey,
+        mint: feeMint,
+        projectNonce: feeProjectNonce,
+        amount: BigInt(10000),
+        programId: program.programId,
+        version: 1,
+        nonce,
+        deadline: BigInt(9999999999),
+      });
+      const ed25519Ix = createEd25519Instruction(
+        distributorKeypair,
+        Buffer.from(serialize(AirdropMessage.schema, msg))
+      );
+      const claimIx = await program.methods
+        .claim(new anchor.BN(feeProjectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+        .accountsPartial({
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          payer: recipientKeypair.publicKey,
+          recipient: recipientKeypair.publicKey,
+          project: feeProjectPda,
+          nullifier: getNullifierPda(feeProjectPda, nonce),
+          mint: feeMint,
+          tokenVault: feeTokenVault,
+          recipientTokenAccount,
+        })
+        .instruction();
+
+      const balanceBefore = await balanceOf(recipientTokenAccount);
+      await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimIx]);
+      return (await balanceOf(recipientTokenAccount)) - balanceBefore;
+    };
+
+    // By default the transfer fee comes out of the signed amount in transit
+    expect(await claimReceived(BigInt(1))).to.equal(BigInt(9900));
+
+    await program.methods
+      .setProjectTransferFeeMode(new anchor.BN(feeProjectNonce.toString()), true)
+      .accountsPartial({ authority: authorityKeypair.publicKey, project: feeProjectPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    // Grossed up, the vault pays the fee on top and the recipient nets the full amount
+    const vaultBefore = await balanceOf(feeTokenVault);
+    expect(await claimReceived(BigInt(2))).to.equal(BigInt(10000));
+    const grossAmount = Number(vaultBefore - (await balanceOf(feeTokenVault)));
+    expect(grossAmount).to.be.greaterThan(10000);
+
+    // The gross amount is what counts against the project's budget
+    const feeProject = await program.account.project.fetch(feeProjectPda);
+    expect(feeProject.totalClaimed.toNumber()).to.equal(10000 + grossAmount);
+  });
+
+  it("Blocks claims while the global authority has the project frozen", async () => {
+    const setProjectFrozen = (authority: Keypair, frozen: boolean) =>
+      program.methods
+        .setProjectFrozen(new anchor.BN(projectNonce.toString()), frozen)
+        .accountsPartial({ authority: authority.publicKey, globalConfig: globalConfigPda, project: projectPda })
+        .signers([authority])
+        .rpc();
+
+    try {
+      await setProjectFrozen(partnerKeypair, true);
+      expect.fail("Should have failed for a key other than the global authority");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+
+    await setProjectFrozen(authorityKeypair, true);
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3010), BigInt(1000)));
+      expect.fail("Should have failed for a frozen project");
+    } catch (error) {
+      expect(error.message).to.include("ProjectFrozen");
+    }
+
+    await setProjectFrozen(authorityKeypair, false);
+    svm.expireBlockhash();
+    await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3010), BigInt(1000)));
+  });
+
+  it("Sends a project's fees to its own treasury when the global authority overrides its tier", async () => {
+    const feeTreasury = Keypair.generate().publicKey;
+    const feeOverrideTokenAccount = await getOrCreateAssociatedTokenAccount(svm, mint, feeTreasury, false, authorityKeypair);
+    const setProjectFeeOverride = (authority: Keypair, feeOverride: { feeBps: number; feeTreasury: PublicKey } | null) =>
+      program.methods
+        .setProjectFeeOverride(new anchor.BN(projectNonce.toString()), feeOverride)
+        .accountsPartial({ authority: authority.publicKey, globalConfig: globalConfigPda, project: projectPda })
+        .signers([authority])
+        .rpc();
+
+    try {
+      await setProjectFeeOverride(partnerKeypair, { feeBps: 500, feeTreasury });
+      expect.fail("Should have failed for a key other than the global authority");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+
+    await setProjectFeeOverride(authorityKeypair, { feeBps: 500, feeTreasury });
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3011), BigInt(10000)));
+      expect.fail("Should have failed without the treasury's token account");
+    } catch (error) {
+      expect(error.message).to.include("InvalidFeeOverrideAccount");
+    }
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(
+      svm,
+      recipientKeypair,
+      await buildSignedClaim(BigInt(3011), BigInt(10000), distributorKeypair, { feeOverrideTokenAccount })
+    );
+    expect(await getSplTokenBalance(svm, mint, recipientKeypair.publicKey) - balanceBefore).to.equal(BigInt(9500));
+    expect(await getSplTokenBalance(svm, mint, feeTreasury)).to.equal(BigInt(500));
+
+    // Fall back to the global fee config for other tests
+    await setProjectFeeOverride(authorityKeypair, null);
+  });
+
+  it("Requires a project's own signature threshold when it sets one", async () => {
+    const projectSigners = [Keypair.generate(), Keypair.generate()];
+    const setProjectThreshold = (threshold: number | null) =>
+      program.methods
+        .setProjectThreshold(new anchor.BN(projectNonce.toString()), threshold)
+        .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda, project: projectPda })
+        .signers([authorityKeypair])
+        .rpc();
+    const setProjectDistributors = (distributors: PublicKey[]) =>
+      program.methods
+        .setProjectDistributors(
+          new anchor.BN(projectNonce.toString()),
+          distributors.map((distributor) => ({ pubkey: distributor, weight: 1, active: true, keyType: { ed25519: {} } }))
+        )
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda })
+        .signers([authorityKeypair])
+        .rpc();
+
+    await setProjectDistributors(projectSigners.map((signer) => signer.publicKey));
+    try {
+      await setProjectThreshold(3);
+      expect.fail("Should have failed for a threshold the project's set cannot reach");
+    } catch (error) {
+      expect(error.message).to.include("InvalidThreshold");
+    }
+    await setProjectThreshold(2);
+
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3012), BigInt(1000), projectSigners[0]));
+      expect.fail("Should have failed below the project threshold");
+    } catch (error) {
+      expect(error.message).to.include("ThresholdNotMet");
+    }
+
+    // A signature entry repeated in the Ed25519 instruction still counts once
+    try {
+      await sendTransaction(
+        svm,
+        recipientKeypair,
+        await buildSignedClaim(BigInt(3012), BigInt(1000), [projectSigners[0], projectSigners[0]])
+      );
+      expect.fail("Should have failed for a repeated signer");
+    } catch (error) {
+      expect(error.message).to.include("ThresholdNotMet");
+    }
+    await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3012), BigInt(1000), projectSigners));
+
+    // Fall back to the global threshold and distributors for other tests
+    await setProjectThreshold(null);
+    await setProjectDistributors([]);
+  });
+
+  it("Emits VaultLowBalance once a claim drops the vault below the project's threshold", async () => {
+    const setLowBalanceThreshold = (threshold: bigint) =>
+      program.methods
+        .setProjectLowBalanceThreshold(new anchor.BN(projectNonce.toString()), new anchor.BN(threshold.toString()))
+        .accountsPartial({ signer: authorityKeypair.publicKey, project: projectPda })
+        .signers([authorityKeypair])
+        .rpc();
+
+    // Without a threshold claims never warn
+    let { logs } = await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3013), BigInt(1000)));
+    expect(findEvent(program, logs, "VaultLowBalance")).to.be.undefined;
+
+    const vaultBalance = (await getAccount(provider.connection, tokenVault)).amount;
+    await setLowBalanceThreshold(vaultBalance);
+    ({ logs } = await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3014), BigInt(1000))));
+    const event = findEvent(program, logs, "VaultLowBalance");
+    expect(event.project.toBase58()).to.equal(projectPda.toBase58());
+    expect(event.vaultBalance.toString()).to.equal((vaultBalance - BigInt(1000)).toString());
+    expect(event.lowBalanceThreshold.toString()).to.equal(vaultBalance.toString());
+
+    // Disable the warning for other tests
+    await setLowBalanceThreshold(BigInt(0));
+  });
+
+  it("Rejects claims when the mint's decimals differ from those recorded on the project", async () => {
+    const account = svm.getAccount(projectPda);
+    const project = program.coder.accounts.decode("project", Buffer.from(account.data));
+    expect(project.decimals).to.equal(9);
+
+    const rewriteDecimals = async (decimals: number) => {
+      const data = Buffer.alloc(account.data.length);
+      (await program.coder.accounts.encode("project", { ...project, decimals })).copy(data);
+      svm.setAccount(projectPda, { ...account, data });
+    };
+
+    await rewriteDecimals(6);
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3015), BigInt(1000)));
+      expect.fail("Should have failed for mismatched decimals");
+    } catch (error) {
+      expect(error.message).to.include("DecimalsMismatch");
+    }
+
+    await rewriteDecimals(9);
+    svm.expireBlockhash();
+    await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3015), BigInt(1000)));
+  });
+
+  it("Unwraps wrapped SOL grants into native SOL for the recipient", async () => {
+    const wsolUnwrapAccountFor = (project: PublicKey) =>
+      PublicKey.findProgramAddressSync([Buffer.from("wsol_unwrap"), project.toBuffer()], program.programId)[0];
+
+    // Only projects distributing wrapped SOL can unwrap
+    try {
+      await sendTransaction(
+        svm,
+        recipientKeypair,
+        await buildSignedClaim(BigInt(3016), BigInt(1000), distributorKeypair, { wsolUnwrapAccount: wsolUnwrapAccountFor(projectPda) })
+      );
+      expect.fail("Should have failed for a project not distributing wrapped SOL");
+    } catch (error) {
+      expect(error.message).to.include("InvalidUnwrap");
+    }
+
+    // A project distributing wrapped SOL, its vault funded by wrapping lamports
+    const [projectCounterPda] = PublicKey.findProgramAddressSync([Buffer.from("project_counter")], program.programId);
+    const wsolProjectNonce = BigInt((await program.account.projectCounter.fetch(projectCounterPda)).count.toNumber() + 1);
+    const [wsolProjectPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("project"), Buffer.from(new anchor.BN(wsolProjectNonce.toString()).toArray("le", 8))],
+      program.programId
+    );
+    const wsolTokenVault = getTokenVaultAddress(program.programId, wsolProjectPda);
+    await program.methods
+      .createProject(new anchor.BN(0), new anchor.BN(0), emptyProjectMetadata)
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        authority: authorityKeypair.publicKey,
+        project: wsolProjectPda,
+        mint: NATIVE_MINT,
+        tokenVault: wsolTokenVault,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+    await sendTransaction(svm, authorityKeypair, [
+      SystemProgram.transfer({ fromPubkey: authorityKeypair.publicKey, toPubkey: wsolTokenVault, lamports: 1000000000 }),
+      createSyncNativeInstruction(wsolTokenVault),
+    ]);
+
+    const amount = BigInt(100000000);
+    const nonce = BigInt(1);
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: NATIVE_MINT,
+      projectNonce: wsolProjectNonce,
+      amount,
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline: BigInt(9999999999),
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+    const wsolUnwrapAccount = wsolUnwrapAccountFor(wsolProjectPda);
+    const claimIx = await program.methods
+      .claim(new anchor.BN(wsolProjectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        payer: partnerKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: wsolProjectPda,
+        nullifier: getNullifierPda(wsolProjectPda, nonce),
+        mint: NATIVE_MINT,
+        tokenVault: wsolTokenVault,
+        wsolUnwrapAccount,
+      })
+      .instruction();
+
+    // A relayer pays, so the recipient's lamports grow by exactly the claimed amount
+    const lamportsBefore = svm.getBalance(recipientKeypair.publicKey);
+    await sendTransaction(svm, partnerKeypair, [ed25519Ix, claimIx]);
+    expect(svm.getBalance(recipientKeypair.publicKey) - lamportsBefore).to.equal(amount);
+    expect(svm.getAccount(wsolUnwrapAccount)).to.be.null;
+    expect(await getSplTokenBalance(svm, NATIVE_MINT, recipientKeypair.publicKey)).to.equal(BigInt(0));
+  });
+
+  it("Charges the claim payer the project's lamport claim fee for the fee treasury", async () => {
+    const claimFee = BigInt(1000000);
+    const setProjectClaimFee = (authority: Keypair, claimFeeLamports: bigint) =>
+      program.methods
+        .setProjectClaimFee(new anchor.BN(projectNonce.toString()), new anchor.BN(claimFeeLamports.toString()))
+        .accountsPartial({ authority: authority.publicKey, globalConfig: globalConfigPda, project: projectPda })
+        .signers([authority])
+        .rpc();
+
+    try {
+      await setProjectClaimFee(partnerKeypair, claimFee);
+      expect.fail("Should have failed for a key other than the global authority");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+    await setProjectClaimFee(authorityKeypair, claimFee);
+
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3017), BigInt(1000)));
+      expect.fail("Should have failed without the fee treasury");
+    } catch (error) {
+      expect(error.message).to.include("InvalidFeeTreasury");
+    }
+    try {
+      await sendTransaction(
+        svm,
+        recipientKeypair,
+        await buildSignedClaim(BigInt(3017), BigInt(1000), distributorKeypair, { feeTreasury: partnerKeypair.publicKey })
+      );
+      expect.fail("Should have failed for a fee treasury other than the global one");
+    } catch (error) {
+      expect(error.message).to.include("InvalidFeeTreasury");
+    }
+
+    const { feeTreasury } = await program.account.globalConfig.fetch(globalConfigPda);
+    const treasuryBefore = svm.getBalance(feeTreasury) ?? BigInt(0);
+    await sendTransaction(
+      svm,
+      recipientKeypair,
+      await buildSignedClaim(BigInt(3017), BigInt(1000), distributorKeypair, { feeTreasury })
+    );
+    expect(svm.getBalance(feeTreasury) - treasuryBefore).to.equal(claimFee);
+
+    // Stop charging for other tests
+    await setProjectClaimFee(authorityKeypair, BigInt(0));
+  });
+
+  it("Reports both the recipient and protocol fee legs in the Claimed event", async () => {
+    const { feeTreasury } = await program.account.globalConfig.fetch(globalConfigPda);
+    const setFeeBps = (feeBps: number) =>
+      program.methods
+        .setFeeConfig(feeBps, feeTreasury)
+        .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+        .signers([authorityKeypair])
+        .rpc();
+
+    await setFeeBps(1000); // 10%
+    const { logs } = await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3018), BigInt(10000)));
+
+    const event = findEvent(program, logs, "Claimed");
+    const feeVault = await getAssociatedTokenAddress(mint, globalConfigPda, true, TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID);
+    const recipientTokenAccount = await getAssociatedTokenAddress(mint, recipientKeypair.publicKey, false, TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID);
+    expect(event.recipient.toBase58()).to.equal(recipientKeypair.publicKey.toBase58());
+    expect(event.destination.toBase58()).to.equal(recipientTokenAccount.toBase58());
+    expect(event.amount.toNumber()).to.equal(10000);
+    expect(event.netAmount.toNumber()).to.equal(9000);
+    expect(event.receivedAmount.toNumber()).to.equal(9000);
+    expect(event.fee.toNumber()).to.equal(1000);
+    expect(event.feeDestination.toBase58()).to.equal(feeVault.toBase58());
+
+    // Restore the fee-free configuration for other tests
+    await setFeeBps(0);
+  });
+
+  it("Records each claim's details in a ClaimEvent", async () => {
+    const { logs } = await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3019), BigInt(1000)));
+
+    const event = findEvent(program, logs, "ClaimEvent");
+    expect(event.project.toBase58()).to.equal(projectPda.toBase58());
+    expect(event.recipient.toBase58()).to.equal(recipientKeypair.publicKey.toBase58());
+    expect(event.mint.toBase58()).to.equal(mint.toBase58());
+    expect(event.amount.toNumber()).to.equal(1000);
+    expect(event.nonce.toNumber()).to.equal(3019);
+    expect(event.timestamp.toString()).to.equal(svm.getClock().unixTimestamp.toString());
+  });
+
+  it("Records a claim receipt when the claim asks for one", async () => {
+    const [claimReceipt] = PublicKey.findProgramAddressSync(
+      [Buffer.from("claim_receipt"), projectPda.toBuffer(), recipientKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await sendTransaction(
+      svm,
+      recipientKeypair,
+      await buildSignedClaim(BigInt(3020), BigInt(1000), distributorKeypair, { claimReceipt })
+    );
+    let receipt = await program.account.claimReceipt.fetch(claimReceipt);
+    expect(receipt.project.toBase58()).to.equal(projectPda.toBase58());
+    expect(receipt.recipient.toBase58()).to.equal(recipientKeypair.publicKey.toBase58());
+    expect(receipt.amount.toNumber()).to.equal(1000);
+    expect(receipt.nonce.toNumber()).to.equal(3020);
+    expect(receipt.slot.toString()).to.equal(svm.getClock().slot.toString());
+
+    // The receipt tracks the latest receipted claim
+    await sendTransaction(
+      svm,
+      recipientKeypair,
+      await buildSignedClaim(BigInt(3021), BigInt(2000), distributorKeypair, { claimReceipt })
+    );
+    receipt = await program.account.claimReceipt.fetch(claimReceipt);
+    expect(receipt.amount.toNumber()).to.equal(2000);
+    expect(receipt.nonce.toNumber()).to.equal(3021);
+  });
+
+  it("Counts a recipient's claims in its claim history", async () => {
+    const [recipientHistory] = PublicKey.findProgramAddressSync(
+      [Buffer.from("recipient_history"), projectPda.toBuffer(), recipientKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+
+    for (const nonce of [BigInt(3022), BigInt(3023)]) {
+      await sendTransaction(
+        svm,
+        recipientKeypair,
+        await buildSignedClaim(nonce, BigInt(1000), distributorKeypair, { recipientHistory })
+      );
+    }
+    // Claims without the history account are not counted
+    await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3024), BigInt(1000)));
+
+    const history = await program.account.recipientHistory.fetch(recipientHistory);
+    expect(history.project.toBase58()).to.equal(projectPda.toBase58());
+    expect(history.recipient.toBase58()).to.equal(recipientKeypair.publicKey.toBase58());
+    expect(history.claimCount.toNumber()).to.equal(2);
+    expect(history.lastClaimTs.toString()).to.equal(svm.getClock().unixTimestamp.toString());
+  });
+
+  it("Closes nullifiers for their rent once the claim window has ended, sealing the window", async () => {
+    // A separate project, since closing its nullifiers ends its claims for good
+    const [projectCounterPda] = PublicKey.findProgramAddressSync([Buffer.from("project_counter")], program.programId);
+    const endedProjectNonce = BigInt((await program.account.projectCounter.fetch(projectCounterPda)).count.toNumber() + 1);
+    const [endedProjectPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("project"), Buffer.from(new anchor.BN(endedProjectNonce.toString()).toArray("le", 8))],
+      program.programId
+    );
+    const endedTokenVault = getTokenVaultAddress(program.programId, endedProjectPda);
+    await program.methods
+      .createProject(new anchor.BN(0), new anchor.BN(0), emptyProjectMetadata)
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        authority: authorityKeypair.publicKey,
+        project: endedProjectPda,
+        mint: mint,
+        tokenVault: endedTokenVault,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+    await sendTransaction(svm, authorityKeypair, [
+      createMintToInstruction(mint, endedTokenVault, authorityKeypair.publicKey, BigInt(1000000), [], TOKEN_PROGRAM_ID),
+    ]);
+
+    const nonce = BigInt(1);
+    const nullifier = getNullifierPda(endedProjectPda, nonce);
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: endedProjectNonce,
+      amount: BigInt(1000),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline: BigInt(9999999999),
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+    const claimIx = await program.methods
+      .claim(new anchor.BN(endedProjectNonce.toString()), new anchor.BN(nonce.toString()), 0, null)
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        payer: recipientKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: endedProjectPda,
+        nullifier,
+        mint: mint,
+        tokenVault: endedTokenVault,
+        recipientTokenAccount: await getAssociatedTokenAddress(mint, recipientKeypair.publicKey, false, TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+      })
+      .instruction();
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimIx]);
+
+    const closeNullifier = () =>
+      program.methods
+        .closeNullifier(new anchor.BN(endedProjectNonce.toString()), new anchor.BN(nonce.toString()))
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: endedProjectPda, nullifier })
+        .signers([authorityKeypair])
+        .rpc();
+    const setWindow = (endTime: number) =>
+      program.methods
+        .setProjectClaimWindow(new anchor.BN(endedProjectNonce.toString()), new anchor.BN(0), new anchor.BN(endTime))
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: endedProjectPda })
+        .signers([authorityKeypair])
+        .rpc();
+
+    try {
+      await closeNullifier();
+      expect.fail("Should have failed while the claim window is open");
+    } catch (error) {
+      expect(error.message).to.include("ClaimWindowNotEnded");
+    }
+
+    await setWindow(Number(svm.getClock().unixTimestamp) - 1);
+    const rent = svm.getBalance(nullifier);
+    const authorityBefore = svm.getBalance(authorityKeypair.publicKey);
+    await closeNullifier();
+    expect(svm.getAccount(nullifier)).to.be.null;
+    expect(svm.getBalance(authorityKeypair.publicKey) - authorityBefore).to.equal(rent);
+
+    // Reopening would let the closed nullifier's message be replayed
+    try {
+      await setWindow(0);
+      expect.fail("Should have failed for a sealed claim window");
+    } catch (error) {
+      expect(error.message).to.include("ClaimWindowSealed");
+    }
+  });
+
+  it("Reimburses the claim payer's nullifier rent from the project's rent pool", async () => {
+    const nullifierRent = svm.getBalance(getNullifierPda(projectPda, BigInt(1)));
+    const rentPool = async () => BigInt((await program.account.project.fetch(projectPda)).rentPoolLamports.toString());
+
+    await program.methods
+      .fundRentPool(new anchor.BN(projectNonce.toString()), new anchor.BN((nullifierRent * BigInt(2)).toString()))
+      .accountsPartial({ funder: partnerKeypair.publicKey, project: projectPda })
+      .signers([partnerKeypair])
+      .rpc();
+    expect(await rentPool()).to.equal(nullifierRent * BigInt(2));
+
+    // A relayer pays for the claim's accounts and is paid back out of the pool
+    for (const [nonce, remaining] of [[BigInt(3025), nullifierRent], [BigInt(3026), BigInt(0)]]) {
+      const projectBefore = svm.getBalance(projectPda);
+      await sendTransaction(
+        svm,
+        partnerKeypair,
+        await buildSignedClaim(nonce, BigInt(1000), distributorKeypair, { payer: partnerKeypair.publicKey })
+      );
+      expect(await rentPool()).to.equal(remaining);
+      expect(projectBefore - svm.getBalance(projectPda)).to.equal(nullifierRent);
+    }
+  });
+
+  it("Records used nonces as bits in nullifier shards in bitmap mode", async () => {
+    const project = await createFundedProject();
+    const [nullifierShard] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nullifier_shard"), project.pda.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const setNullifierMode = (nullifierMode: object) =>
+      program.methods
+        .setProjectNullifierMode(new anchor.BN(project.nonce.toString()), nullifierMode as any)
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: project.pda })
+        .signers([authorityKeypair])
+        .rpc();
+
+    await setNullifierMode({ bitmap: {} });
+
+    // Bitmap projects take the shard instead of a nullifier PDA
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildSignedProjectClaim(project, BigInt(9), BigInt(1000), { nullifierShard }));
+      expect.fail("Should have failed with a nullifier PDA in bitmap mode");
+    } catch (error) {
+      expect(error.message).to.include("InvalidNullifier");
+    }
+    await sendTransaction(
+      svm,
+      recipientKeypair,
+      await buildSignedProjectClaim(project, BigInt(9), BigInt(1000), { nullifier: null, nullifierShard })
+    );
+    const shard = await program.account.nullifierShard.fetch(nullifierShard);
+    expect(shard.bits[1]).to.equal(1 << 1);
+    expect(svm.getAccount(getNullifierPda(project.pda, BigInt(9)))).to.be.null;
+
+    svm.expireBlockhash();
+    try {
+      await sendTransaction(
+        svm,
+        recipientKeypair,
+        await buildSignedProjectClaim(project, BigInt(9), BigInt(1000), { nullifier: null, nullifierShard })
+      );
+      expect.fail("Should have failed for a nonce whose bit is set");
+    } catch (error) {
+      expect(error.message).to.include("InvalidNullifier");
+    }
+
+    // Used nonces live in the shards, so the mode is fixed after the first claim
+    try {
+      await setNullifierMode({ pda: {} });
+      expect.fail("Should have failed after the first claim");
+    } catch (error) {
+      expect(error.message).to.include("NullifierModeLocked");
+    }
+  });
+
+  it("Requires each recipient's next nonce in sequential nonce mode", async () => {
+    const project = await createFundedProject();
+    const [recipientNonce] = PublicKey.findProgramAddressSync(
+      [Buffer.from("recipient_nonce"), project.pda.toBuffer(), recipientKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .setProjectNullifierMode(new anchor.BN(project.nonce.toString()), { sequential: {} })
+      .accountsPartial({ authority: authorityKeypair.publicKey, project: project.pda })
+      .signers([authorityKeypair])
+      .rpc();
+    const claimSequential = async (nonce: bigint) =>
+      sendTransaction(
+        svm,
+        recipientKeypair,
+        await buildSignedProjectClaim(project, nonce, BigInt(1000), { nullifier: null, recipientNonce })
+      );
+
+    try {
+      await claimSequential(BigInt(1));
+      expect.fail("Should have failed for a nonce ahead of the counter");
+    } catch (error) {
+      expect(error.message).to.include("NonceMismatch");
+    }
+    await claimSequential(BigInt(0));
+    await claimSequential(BigInt(1));
+
+    svm.expireBlockhash();
+    try {
+      await claimSequential(BigInt(1));
+      expect.fail("Should have failed for an already used nonce");
+    } catch (error) {
+      expect(error.message).to.include("NonceMismatch");
+    }
+
+    const counter = await program.account.recipientNonce.fetch(recipientNonce);
+    expect(counter.nextNonce.toNumber()).to.equal(2);
+  });
+
+  it("Claims wrapped SOL into a stake account delegated to the project's vote account", async () => {
+    await svm.airdrop(authorityKeypair.publicKey, BigInt(10000000000));
+    const project = await createFundedProject(NATIVE_MINT);
+
+    const voteKeypair = Keypair.generate();
+    const nodeKeypair = Keypair.generate();
+    await provider.sendAndConfirm(
+      VoteProgram.createAccount({
+        fromPubkey: authorityKeypair.publicKey,
+        votePubkey: voteKeypair.publicKey,
+        voteInit: new VoteInit(nodeKeypair.publicKey, authorityKeypair.publicKey, authorityKeypair.publicKey, 0),
+        lamports: Number(svm.minimumBalanceForRentExemption(BigInt(VoteProgram.space))),
+      }),
+      [authorityKeypair, voteKeypair, nodeKeypair]
+    );
+
+    const amount = BigInt(2000000000);
+    const nonce = BigInt(1);
+    const [stakeAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stake_account"), project.pda.toBuffer(), new anchor.BN(nonce.toString()).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: NATIVE_MINT,
+      projectNonce: project.nonce,
+      amount,
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline: BigInt(9999999999),
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+    const claimAndStakeIx = await program.methods
+      .claimAndStake(new anchor.BN(project.nonce.toString()), new anchor.BN(nonce.toString()))
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        payer: recipientKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: project.pda,
+        nullifier: getNullifierPda(project.pda, nonce),
+        mint: NATIVE_MINT,
+        tokenVault: project.vault,
+        stakeAccount,
+        voteAccount: voteKeypair.publicKey,
+        stakeHistory: SYSVAR_STAKE_HISTORY_PUBKEY,
+        stakeConfig: STAKE_CONFIG_ID,
+        stakeProgram: StakeProgram.programId,
+      })
+      .instruction();
+
+    // Staking is off until the project names its vote account
+    try {
+      await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimAndStakeIx]);
+      expect.fail("Should have failed without a stake vote account");
+    } catch (error) {
+      expect(error.message).to.include("InvalidStake");
+    }
+
+    await program.methods
+      .setProjectStakeVoteAccount(new anchor.BN(project.nonce.toString()), voteKeypair.publicKey)
+      .accountsPartial({ authority: authorityKeypair.publicKey, project: project.pda })
+      .signers([authorityKeypair])
+      .rpc();
+    svm.expireBlockhash();
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimAndStakeIx]);
+
+    // The stake account holds the claim on top of its rent, delegated with the recipient as both authorities
+    const stake = svm.getAccount(stakeAccount);
+    expect(stake.owner.toBase58()).to.equal(StakeProgram.programId.toBase58());
+    expect(BigInt(stake.lamports) - svm.minimumBalanceForRentExemption(BigInt(StakeProgram.space))).to.equal(amount);
+    const data = Buffer.from(stake.data);
+    expect(data.readUInt32LE(0)).to.equal(2); // StakeStateV2::Stake
+    expect(new PublicKey(data.subarray(12, 44)).toBase58()).to.equal(recipientKeypair.publicKey.toBase58()); // staker
+    expect(new PublicKey(data.subarray(44, 76)).toBase58()).to.equal(recipientKeypair.publicKey.toBase58()); // withdrawer
+    expect(new PublicKey(data.subarray(124, 156)).toBase58()).to.equal(voteKeypair.publicKey.toBase58()); // voter
+  });
+
+  it("Only deposits claims into the stake pool the project configured", async () => {
+    const project = await createFundedProject(NATIVE_MINT);
+
+    // Pool accounts only need the right owner to reach the checks under test; the deposit
+    // itself is left to the stake pool program
+    const fakePool = () => {
+      const pool = Keypair.generate().publicKey;
+      svm.setAccount(pool, {
+        lamports: 1000000000,
+        data: Buffer.alloc(611),
+        owner: SPL_STAKE_POOL_PROGRAM_ID,
+        executable: false,
+      });
+      return pool;
+    };
+    const stakePool = fakePool();
+    const otherPool = fakePool();
+    const poolMint = await createSplToken(provider, authorityKeypair, 9);
+    const recipientPoolTokenAccount = await getOrCreateAssociatedTokenAccount(svm, poolMint, recipientKeypair.publicKey, false, authorityKeypair);
+    const partnerPoolTokenAccount = await getOrCreateAssociatedTokenAccount(svm, poolMint, partnerKeypair.publicKey, false, authorityKeypair);
+
+    const nonce = BigInt(1);
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: NATIVE_MINT,
+      projectNonce: project.nonce,
+      amount: BigInt(1000000000),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline: BigInt(9999999999),
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+    const claimIntoPool = async (pool: PublicKey, poolTokenAccount: PublicKey) => {
+      const claimIntoPoolIx = await program.methods
+        .claimIntoPool(new anchor.BN(project.nonce.toString()), new anchor.BN(nonce.toString()))
+        .accountsPartial({
+          tokenProgram: TOKEN_PROGRAM_ID,
+          payer: recipientKeypair.publicKey,
+          recipient: recipientKeypair.publicKey,
+          project: project.pda,
+          nullifier: getNullifierPda(project.pda, nonce),
+          mint: NATIVE_MINT,
+          tokenVault: project.vault,
+          stakePool: pool,
+          stakePoolWithdrawAuthority: Keypair.generate().publicKey,
+          reserveStake: Keypair.generate().publicKey,
+          managerFeeAccount: Keypair.generate().publicKey,
+          poolMint,
+          poolTokenAccount,
+          stakePoolProgram: SPL_STAKE_POOL_PROGRAM_ID,
+        })
+        .instruction();
+      await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimIntoPoolIx]);
+    };
+
+    const expectRejected = async (pool: PublicKey, poolTokenAccount: PublicKey, reason: string) => {
+      try {
+        await claimIntoPool(pool, poolTokenAccount);
+        expect.fail(reason);
+      } catch (error) {
+        expect(error.message).to.include("InvalidStakePool");
+      }
+    };
+
+    await expectRejected(stakePool, recipientPoolTokenAccount, "Should have failed without a configured stake pool");
+
+    await program.methods
+      .setProjectStakePool(new anchor.BN(project.nonce.toString()), stakePool)
+      .accountsPartial({ authority: authorityKeypair.publicKey, project: project.pda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    await expectRejected(otherPool, recipientPoolTokenAccount, "Should have failed for another stake pool");
+    await expectRejected(stakePool, partnerPoolTokenAccount, "Should have failed for pool tokens not owned by the recipient");
+  });
+
+  it("Accepts messages within the deadline grace period, preferring the project's own", async () => {
+    const nonce = BigInt(3027);
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(1000),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline: svm.getClock().unixTimestamp - BigInt(10),
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+    // The claim instruction does not depend on the message's deadline
+    const [, claimIx] = await buildSignedClaim(nonce, BigInt(1000));
+    const setDeadlineGrace = (deadlineGraceSecs: number) =>
+      program.methods
+        .setDeadlineGrace(deadlineGraceSecs)
+        .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+        .signers([authorityKeypair])
+        .rpc();
+    const setProjectDeadlineGrace = (deadlineGraceSecs: number | null) =>
+      program.methods
+        .setProjectDeadlineGrace(new anchor.BN(projectNonce.toString()), deadlineGraceSecs)
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda })
+        .signers([authorityKeypair])
+        .rpc();
+    const expectExpired = async () => {
+      svm.expireBlockhash();
+      try {
+        await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimIx]);
+        expect.fail("Should have failed past the deadline and its grace period");
+      } catch (error) {
+        expect(error.message).to.include("DeadlineExpired");
+      }
+    };
+
+    await expectExpired();
+
+    try {
+      await setProjectDeadlineGrace(301);
+      expect.fail("Should have failed above the maximum grace period");
+    } catch (error) {
+      expect(error.message).to.include("InvalidDeadlineGrace");
+    }
+
+    // The project's own grace period overrides the global one
+    await setDeadlineGrace(30);
+    await setProjectDeadlineGrace(0);
+    await expectExpired();
+
+    await setProjectDeadlineGrace(null);
+    svm.expireBlockhash();
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimIx]);
+
+    // Restore the strict deadline for other tests
+    await setDeadlineGrace(0);
+  });
+
+  it("Throttles a project's claims per window of slots", async () => {
+    const [claimThrottle] = PublicKey.findProgramAddressSync(
+      [Buffer.from("claim_throttle"), projectPda.toBuffer()],
+      program.programId
+    );
+    const setThrottle = (maxClaimsPerWindow: number, windowSlots: number) =>
+      program.methods
+        .setProjectThrottle(new anchor.BN(projectNonce.toString()), new anchor.BN(maxClaimsPerWindow), new anchor.BN(windowSlots))
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda })
+        .signers([authorityKeypair])
+        .rpc();
+    const claimThrottled = async (nonce: bigint) =>
+      sendTransaction(
+        svm,
+        recipientKeypair,
+        await buildSignedClaim(nonce, BigInt(1000), distributorKeypair, { claimThrottle })
+      );
+
+    try {
+      await setThrottle(2, 0);
+      expect.fail("Should have failed for an empty window");
+    } catch (error) {
+      expect(error.message).to.include("InvalidRateLimitWindow");
+    }
+    await setThrottle(2, 100);
+
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildSignedClaim(BigInt(3028), BigInt(1000)));
+      expect.fail("Should have failed without the throttle counter");
+    } catch (error) {
+      expect(error.message).to.include("InvalidThrottle");
+    }
+    await claimThrottled(BigInt(3028));
+    await claimThrottled(BigInt(3029));
+    try {
+      await claimThrottled(BigInt(3030));
+      expect.fail("Should have failed past the window's claim limit");
+    } catch (error) {
+      expect(error.message).to.include("ProjectRateLimitExceeded");
+    }
+
+    // A new window accepts claims again
+    svm.warpToSlot(svm.getClock().slot + BigInt(100));
+    svm.expireBlockhash();
+    await claimThrottled(BigInt(3030));
+
+    // Lift the throttle for other tests
+    await setThrottle(0, 0);
+  });
+
+  it("Pays holders of the project's verified collection once per NFT", async () => {
+    const project = await createFundedProject();
+    const collection = Keypair.generate().publicKey;
+    const setCollectionGate = (collectionGate: { collection: PublicKey; amount: anchor.BN } | null) =>
+      program.methods
+        .setProjectCollectionGate(new anchor.BN(project.nonce.toString()), collectionGate)
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: project.pda })
+        .signers([authorityKeypair])
+        .rpc();
+
+    try {
+      await setCollectionGate({ collection, amount: new anchor.BN(0) });
+      expect.fail("Should have failed for a zero amount");
+    } catch (error) {
+      expect(error.message).to.include("InvalidCollectionNft");
+    }
+    await setCollectionGate({ collection, amount: new anchor.BN(5000) });
+
+    // An NFT held by the recipient, with a Token Metadata account naming its collection
+    const nftMint = await createSplToken(provider, authorityKeypair, 0);
+    const nftTokenAccount = await getOrCreateAssociatedTokenAccount(svm, nftMint, recipientKeypair.publicKey, false, authorityKeypair);
+    await sendTransaction(svm, authorityKeypair, [
+      createMintToInstruction(nftMint, nftTokenAccount, authorityKeypair.publicKey, BigInt(1), [], TOKEN_PROGRAM_ID),
+    ]);
+    const [nftMetadata] = PublicKey.findProgramAddressSync(
+      [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), nftMint.toBuffer()],
+      TOKEN_METADATA_PROGRAM_ID
+    );
+    const setMetadata = (verified: boolean) =>
+      svm.setAccount(nftMetadata, {
+        lamports: 10000000,
+        data: metadataAccountData(nftMint, collection, verified),
+        owner: TOKEN_METADATA_PROGRAM_ID,
+        executable: false,
+      });
+    const claimWithNft = async () => {
+      const claimWithNftIx = await program.methods
+        .claimWithNft(new anchor.BN(project.nonce.toString()))
+        .accountsPartial({
+          tokenProgram: TOKEN_PROGRAM_ID,
+          payer: recipientKeypair.publicKey,
+          recipient: recipientKeypair.publicKey,
+          project: project.pda,
+          mint: mint,
+          tokenVault: project.vault,
+          nftMint,
+          nftTokenAccount,
+          nftMetadata,
+        })
+        .instruction();
+      svm.expireBlockhash();
+      await sendTransaction(svm, recipientKeypair, [claimWithNftIx]);
+    };
+
+    setMetadata(false);
+    try {
+      await claimWithNft();
+      expect.fail("Should have failed for an unverified collection");
+    } catch (error) {
+      expect(error.message).to.include("InvalidCollectionNft");
+    }
+
+    setMetadata(true);
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await claimWithNft();
+    expect(await getSplTokenBalance(svm, mint, recipientKeypair.publicKey) - balanceBefore).to.equal(BigInt(5000));
+
+    try {
+      await claimWithNft();
+      expect.fail("Should have failed for an NFT that already claimed");
+    } catch (error) {
+      // The NFT's claim record already exists, so its init fails
+      expect(error.message).to.include("Unknown error");
+    }
+  });
+  it("Only pays compressed NFT claims against the project's configured tree", async () => {
+    const project = await createFundedProject();
+    const merkleTree = Keypair.generate().publicKey;
+    const setCompressedNftGate = (compressedNftGate: { merkleTree: PublicKey; amount: anchor.BN } | null) =>
+      program.methods
+        .setProjectCompressedNftGate(new anchor.BN(project.nonce.toString()), compressedNftGate)
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: project.pda })
+        .signers([authorityKeypair])
+        .rpc();
+    const leaf = {
+      root: Array(32).fill(0),
+      dataHash: Array(32).fill(0),
+      creatorHash: Array(32).fill(0),
+      delegate: recipientKeypair.publicKey,
+      nonce: new anchor.BN(0),
+      index: 0,
+    };
+    const claimWithCnft = async (tree: PublicKey, proofLen: number) => {
+      const claimWithCnftIx = await program.methods
+        .claimWithCnft(new anchor.BN(project.nonce.toString()), leaf, proofLen)
+        .accountsPartial({
+          tokenProgram: TOKEN_PROGRAM_ID,
+          payer: recipientKeypair.publicKey,
+          recipient: recipientKeypair.publicKey,
+          project: project.pda,
+          mint: mint,
+          tokenVault: project.vault,
+          merkleTree: tree,
+        })
+        .instruction();
+      svm.expireBlockhash();
+      await sendTransaction(svm, recipientKeypair, [claimWithCnftIx]);
+    };
+
+    try {
+      await claimWithCnft(merkleTree, 0);
+      expect.fail("Should have failed without a compressed NFT gate");
+    } catch (error) {
+      expect(error.message).to.include("InvalidCompressedNft");
+    }
+
+    try {
+      await setCompressedNftGate({ merkleTree, amount: new anchor.BN(0) });
+      expect.fail("Should have failed for a zero amount");
+    } catch (error) {
+      expect(error.message).to.include("InvalidCompressedNft");
+    }
+    await setCompressedNftGate({ merkleTree, amount: new anchor.BN(5000) });
+
+    try {
+      await claimWithCnft(Keypair.generate().publicKey, 0);
+      expect.fail("Should have failed for another tree");
+    } catch (error) {
+      expect(error.message).to.include("InvalidCompressedNft");
+    }
+
+    // The proof nodes are read from the remaining accounts, so a proof longer than them is rejected
+    // before the account compression program is asked to verify it
+    try {
+      await claimWithCnft(merkleTree, 3);
+      expect.fail("Should have failed for a missing proof");
+    } catch (error) {
+      expect(error.message).to.include("InvalidCompressedNft");
+    }
+  });
+  it("Lets a wallet claim from only one project of a campaign group", async () => {
+    const projects = [await createFundedProject(), await createFundedProject()];
+    const groupId = new anchor.BN(7);
+    for (const project of projects) {
+      await program.methods
+        .setProjectClaimGroup(new anchor.BN(project.nonce.toString()), groupId)
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: project.pda })
+        .signers([authorityKeypair])
+        .rpc();
+    }
+    const [groupClaim] = PublicKey.findProgramAddressSync(
+      [Buffer.from("group_claim"), Buffer.from(groupId.toArray("le", 8)), recipientKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildSignedProjectClaim(projects[0], BigInt(1), BigInt(1000)));
+      expect.fail("Should have failed without the group claim record");
+    } catch (error) {
+      expect(error.message).to.include("InvalidGroupClaim");
+    }
+
+    await sendTransaction(svm, recipientKeypair, await buildSignedProjectClaim(projects[0], BigInt(1), BigInt(1000), { groupClaim }));
+    const record = await program.account.groupClaim.fetch(groupClaim);
+    expect(record.groupId.eq(groupId)).to.be.true;
+    expect(record.project.equals(projects[0].pda)).to.be.true;
+
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildSignedProjectClaim(projects[1], BigInt(1), BigInt(1000), { groupClaim }));
+      expect.fail("Should have failed for a second project of the group");
+    } catch (error) {
+      // The recipient's group claim record already exists, so its init fails
+      expect(error.message).to.include("Unknown error");
+    }
+
+    // Out of the group, the other project pays the wallet as usual
+    await program.methods
+      .setProjectClaimGroup(new anchor.BN(projects[1].nonce.toString()), null)
+      .accountsPartial({ authority: authorityKeypair.publicKey, project: projects[1].pda })
+      .signers([authorityKeypair])
+      .rpc();
+    const [ungroupedClaim] = PublicKey.findProgramAddressSync(
+      [Buffer.from("group_claim"), Buffer.from(new anchor.BN(0).toArray("le", 8)), recipientKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+    try {
+      await sendTransaction(
+        svm,
+        recipientKeypair,
+        await buildSignedProjectClaim(projects[1], BigInt(1), BigInt(1000), { groupClaim: ungroupedClaim })
+      );
+      expect.fail("Should have failed for a group claim record outside any group");
+    } catch (error) {
+      expect(error.message).to.include("InvalidGroupClaim");
+    }
+    await sendTransaction(svm, recipientKeypair, await buildSignedProjectClaim(projects[1], BigInt(1), BigInt(1000)));
+  });
+  it("Approves the signed token delegate over the tokens a claim pays", async () => {
+    const project = await createFundedProject();
+    const tokenDelegate = Keypair.generate().publicKey;
+    const recipientTokenAccount = await getAssociatedTokenAddress(mint, recipientKeypair.publicKey);
+    const nonce = BigInt(1);
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: project.nonce,
+      amount: BigInt(1000),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline: BigInt(9999999999),
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(
+        DelegatedClaimMessage.schema,
+        new DelegatedClaimMessage({ data: msg.data, token_delegate: tokenDelegate.toBytes(), domain: msg.domain })
+      ))
+    );
+    const claimAndDelegate = async (delegate: PublicKey) => {
+      const claimAndDelegateIx = await program.methods
+        .claimAndDelegate(new anchor.BN(project.nonce.toString()), new anchor.BN(nonce.toString()))
+        .accountsPartial({
+          tokenProgram: TOKEN_PROGRAM_ID,
+          payer: recipientKeypair.publicKey,
+          recipient: recipientKeypair.publicKey,
+          project: project.pda,
+          nullifier: getNullifierPda(project.pda, nonce),
+          mint: mint,
+          tokenVault: project.vault,
+          recipientTokenAccount,
+          tokenDelegate: delegate,
+        })
+        .instruction();
+      await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimAndDelegateIx]);
+    };
+
+    try {
+      await claimAndDelegate(Keypair.generate().publicKey);
+      expect.fail("Should have failed for a delegate the message does not name");
+    } catch (error) {
+      expect(error.message).to.include("InvalidTokenDelegate");
+    }
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await claimAndDelegate(tokenDelegate);
+    const received = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey) - balanceBefore;
+
+    // The delegate may move exactly what arrived, net of fees
+    const account = await getAccount(provider.connection, recipientTokenAccount);
+    expect(account.delegate.toBase58()).to.equal(tokenDelegate.toBase58());
+    expect(account.delegatedAmount).to.equal(received);
+  });
+  it("Emits events as self-CPIs when built with event-cpi", async function () {
+    // Only a build with `anchor build -- --features event-cpi` takes the event authority
+    const setClaimGroup = program.rawIdl.instructions.find((ix) => ix.name === "set_project_claim_group");
+    if (!setClaimGroup.accounts.some((account) => account.name === "event_authority")) {
+      this.skip();
+    }
+
+    const project = await createFundedProject();
+    const setClaimGroupIx = await program.methods
+      .setProjectClaimGroup(new anchor.BN(project.nonce.toString()), new anchor.BN(11))
+      .accountsPartial({ authority: authorityKeypair.publicKey, project: project.pda })
+      .instruction();
+    const { logs, innerInstructions } = await sendTransaction(svm, authorityKeypair, [setClaimGroupIx]);
+
+    // The event travels in the self-CPI's data rather than the logs
+    expect(findEvent(program, logs, "ProjectClaimGroupUpdated")).to.be.undefined;
+    const event = findCpiEvent(program, innerInstructions, "ProjectClaimGroupUpdated");
+    expect(event.project.equals(project.pda)).to.be.true;
+    expect(event.claimGroup.toNumber()).to.equal(11);
+  });
+  it("Claims with a secp256r1 (passkey) distributor signature", async () => {
+    const passkey = p256.utils.randomPrivateKey();
+    const updateDistributors = (update: object) =>
+      program.methods
+        .updateGlobalConfig(update)
+        .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+        .signers([authorityKeypair])
+        .rpc();
+    await updateDistributors({ add: { distributor: secp256r1KeyId(passkey), weight: 1, keyType: { secp256r1: {} } } });
+
+    const buildPasskeyClaim = async (signer: Uint8Array, nonce: bigint) => {
+      const msg = createAirdropMessage({
+        recipient: recipientKeypair.publicKey,
+        mint: mint,
+        projectNonce: projectNonce,
+        amount: BigInt(1000),
+        programId: program.programId,
+        version: 1,
+        nonce,
+        deadline: BigInt(9999999999),
+      });
+      const [, claimIx] = await buildSignedClaim(nonce, BigInt(1000));
+      return [createSecp256r1Instruction(signer, Buffer.from(serialize(AirdropMessage.schema, msg))), claimIx];
+    };
+
+    try {
+      await sendTransaction(svm, recipientKeypair, await buildPasskeyClaim(p256.utils.randomPrivateKey(), BigInt(3031)));
+      expect.fail("Should have failed for an unregistered passkey");
+    } catch (error) {
+      expect(error.message).to.include("ThresholdNotMet");
+    }
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, await buildPasskeyClaim(passkey, BigInt(3031)));
+    expect(await getSplTokenBalance(svm, mint, recipientKeypair.publicKey) - balanceBefore).to.equal(BigInt(1000));
+
+    // Remove the passkey distributor for other tests
+    await updateDistributors({ remove: { distributor: secp256r1KeyId(passkey) } });
+  });
+  it("Reads a signed message from another instruction of the transaction", async () => {
+    const nonce = BigInt(3032);
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(1000),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline: BigInt(9999999999),
+    });
+    const message = Buffer.from(serialize(AirdropMessage.schema, msg));
+    const [, claimIx] = await buildSignedClaim(nonce, BigInt(1000));
+
+    // The first instruction carries the message after its own single-signature layout (a 16 byte
+    // header, the public key and the signature); the distributor's entry points at it there
+    const carrierIx = createEd25519Instruction(Keypair.generate(), message);
+    const distributorIx = createEd25519InstructionWithExternalMessage(distributorKeypair, message, 0, 16 + 32 + 64);
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, [carrierIx, distributorIx, claimIx]);
+    expect(await getSplTokenBalance(svm, mint, recipientKeypair.publicKey) - balanceBefore).to.equal(BigInt(1000));
+  });
+  it("Reserves unreleased vesting allocations from burns and project closes", async () => {
+    const project = await createFundedProject();
+    const allocationAmount = BigInt(1000000);
+    const nonce = BigInt(1);
+    const cliffTs = svm.getClock().unixTimestamp;
+    const vestEndTs = cliffTs + BigInt(1000);
+
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: project.nonce,
+      amount: allocationAmount,
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline: BigInt(9999999999),
+      cliffTs,
+      vestEndTs,
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+    const warpTo = (unixTimestamp: bigint) => {
+      const currentClock = svm.getClock();
+      svm.setClock(new Clock(
+        currentClock.slot,
+        currentClock.epochStartTimestamp,
+        currentClock.epoch,
+        currentClock.leaderScheduleEpoch,
+        unixTimestamp
+      ));
+    };
+    const claimVestingIx = await program.methods
+      .claimVesting(new anchor.BN(project.nonce.toString()), new anchor.BN(nonce.toString()))
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        payer: recipientKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: project.pda,
+        allocation: getNullifierPda(project.pda, nonce),
+        mint: mint,
+        tokenVault: project.vault,
+        recipientTokenAccount: await getAssociatedTokenAddress(mint, recipientKeypair.publicKey, false, TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+      })
+      .instruction();
+    const outstandingVesting = async () =>
+      BigInt((await program.account.project.fetch(project.pda)).outstandingVesting.toString());
+    const closeProject = () =>
+      program.methods
+        .closeProject(new anchor.BN(project.nonce.toString()), false)
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: project.pda, mint: mint, tokenProgram: TOKEN_PROGRAM_ID })
+        .signers([authorityKeypair])
+        .rpc();
+
+    warpTo(cliffTs + BigInt(500));
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimVestingIx]);
+    expect(await outstandingVesting()).to.equal(allocationAmount / BigInt(2));
+
+    // End the claim window and burn what is left, which keeps the unreleased half
+    await program.methods
+      .setProjectClaimWindow(new anchor.BN(project.nonce.toString()), new anchor.BN(0), new anchor.BN(Number(svm.getClock().unixTimestamp)))
+      .accountsPartial({ authority: authorityKeypair.publicKey, project: project.pda })
+      .signers([authorityKeypair])
+      .rpc();
+    await program.methods
+      .burnUnclaimed(new anchor.BN(project.nonce.toString()))
+      .accountsPartial({ signer: authorityKeypair.publicKey, project: project.pda, mint: mint, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([authorityKeypair])
+      .rpc();
+    expect((await getAccount(provider.connection, project.vault)).amount).to.equal(allocationAmount / BigInt(2));
+
+    try {
+      await closeProject();
+      expect.fail("Should have failed while the allocation is still vesting");
+    } catch (error) {
+      expect(error.message).to.include("VestingOutstanding");
+    }
+
+    // The rest is released after the window has ended, after which the project can close
+    warpTo(vestEndTs);
+    svm.expireBlockhash();
+    await sendTransaction(svm, recipientKeypair, [claimVestingIx]);
+    expect(await outstandingVesting()).to.equal(BigInt(0));
+    await closeProject();
+    expect(svm.getAccount(project.pda)).to.be.null;
+  });
+  it("Reserves open streams from burns and project closes", async () => {
+    const project = await createFundedProject();
+    const streamAmount = BigInt(1000000);
+    const nonce = BigInt(1);
+    const startTs = svm.getClock().unixTimestamp;
+    const endTs = startTs + BigInt(1000);
+    const streamPda = getNullifierPda(project.pda, nonce);
+
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: project.nonce,
+      amount: streamAmount,
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline: BigInt(9999999999),
+      cliffTs: startTs,
+      vestEndTs: endTs,
+    });
+    const ed25519Ix = createEd25519Instruction(
+      distributorKeypair,
+      Buffer.from(serialize(AirdropMessage.schema, msg))
+    );
+    const createStreamIx = await program.methods
+      .createStream(new anchor.BN(project.nonce.toString()), new anchor.BN(nonce.toString()))
+      .accountsPartial({
+        payer: recipientKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: project.pda,
+        stream: streamPda,
+        mint: mint,
+      })
+      .instruction();
+    const withdrawStreamIx = await program.methods
+      .withdrawStream(new anchor.BN(project.nonce.toString()), new anchor.BN(nonce.toString()))
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        payer: recipientKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: project.pda,
+        stream: streamPda,
+        mint: mint,
+        tokenVault: project.vault,
+      })
+      .instruction();
+    const warpTo = (unixTimestamp: bigint) => {
+      const currentClock = svm.getClock();
+      svm.setClock(new Clock(
+        currentClock.slot,
+        currentClock.epochStartTimestamp,
+        currentClock.epoch,
+        currentClock.leaderScheduleEpoch,
+        unixTimestamp
+      ));
+    };
+    const outstandingStreamed = async () =>
+      BigInt((await program.account.project.fetch(project.pda)).outstandingStreamed.toString());
+    const closeProject = () =>
+      program.methods
+        .closeProject(new anchor.BN(project.nonce.toString()), false)
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: project.pda, mint: mint, tokenProgram: TOKEN_PROGRAM_ID })
+        .signers([authorityKeypair])
+        .rpc();
+
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, createStreamIx]);
+    expect(await outstandingStreamed()).to.equal(streamAmount);
+
+    warpTo(startTs + BigInt(250));
+    await sendTransaction(svm, recipientKeypair, [withdrawStreamIx]);
+    expect(await outstandingStreamed()).to.equal(streamAmount - streamAmount / BigInt(4));
+
+    // End the claim window and burn what is left, which keeps the unwithdrawn stream
+    await program.methods
+      .setProjectClaimWindow(new anchor.BN(project.nonce.toString()), new anchor.BN(0), new anchor.BN(Number(svm.getClock().unixTimestamp)))
+      .accountsPartial({ authority: authorityKeypair.publicKey, project: project.pda })
+      .signers([authorityKeypair])
+      .rpc();
+    await program.methods
+      .burnUnclaimed(new anchor.BN(project.nonce.toString()))
+      .accountsPartial({ signer: authorityKeypair.publicKey, project: project.pda, mint: mint, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([authorityKeypair])
+      .rpc();
+    expect((await getAccount(provider.connection, project.vault)).amount).to.equal(streamAmount - streamAmount / BigInt(4));
+
+    try {
+      await closeProject();
+      expect.fail("Should have failed while the stream is open");
+    } catch (error) {
+      expect(error.message).to.include("StreamsOutstanding");
+    }
+
+    warpTo(endTs);
+    svm.expireBlockhash();
+    await sendTransaction(svm, recipientKeypair, [withdrawStreamIx]);
+    expect(await outstandingStreamed()).to.equal(BigInt(0));
+    await closeProject();
+    expect(svm.getAccount(project.pda)).to.be.null;
+  });
+  it("Keeps a project with locked claims open and releases them even while frozen", async () => {
+    const project = await createFundedProject();
+    const claimAmount = BigInt(1000);
+    const projectNonceBn = new anchor.BN(project.nonce.toString());
+    const [lockedClaimPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("locked_claim"), project.pda.toBuffer(), recipientKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+    const [lockedClaimVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("locked_claim_vault"), project.pda.toBuffer(), recipientKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+    const outstandingLocked = async () =>
+      BigInt((await program.account.project.fetch(project.pda)).outstandingLocked.toString());
+    const setProjectFrozen = (frozen: boolean) =>
+      program.methods
+        .setProjectFrozen(projectNonceBn, frozen)
+        .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda, project: project.pda })
+        .signers([authorityKeypair])
+        .rpc();
+    const closeProject = () =>
+      program.methods
+        .closeProject(projectNonceBn, false)
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: project.pda, mint: mint, tokenProgram: TOKEN_PROGRAM_ID })
+        .signers([authorityKeypair])
+        .rpc();
+
+    await program.methods
+      .setProjectLockDuration(projectNonceBn, new anchor.BN(86400))
+      .accountsPartial({ authority: authorityKeypair.publicKey, project: project.pda })
+      .signers([authorityKeypair])
+      .rpc();
+    await sendTransaction(
+      svm,
+      recipientKeypair,
+      await buildSignedProjectClaim(project, BigInt(1), claimAmount, { lockedClaim: lockedClaimPda, lockedClaimVault })
+    );
+    expect(await outstandingLocked()).to.equal(claimAmount);
+
+    try {
+      await closeProject();
+      expect.fail("Should have failed while a claim is locked");
+    } catch (error) {
+      expect(error.message).to.include("LockupsOutstanding");
+    }
+
+    // A freeze blocks new claims but not the release of tokens already claimed
+    await setProjectFrozen(true);
+    const lockedClaim = await program.account.lockedClaim.fetch(lockedClaimPda);
+    const currentClock = svm.getClock();
+    svm.setClock(new Clock(
+      currentClock.slot,
+      currentClock.epochStartTimestamp,
+      currentClock.epoch,
+      currentClock.leaderScheduleEpoch,
+      BigInt(lockedClaim.unlockTs.toString())
+    ));
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await program.methods
+      .unlock(projectNonceBn)
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        payer: recipientKeypair.publicKey,
+        recipient: recipientKeypair.publicKey,
+        project: project.pda,
+        lockedClaim: lockedClaimPda,
+        mint: mint,
+      })
+      .signers([recipientKeypair])
+      .rpc();
+    expect(await getSplTokenBalance(svm, mint, recipientKeypair.publicKey) - balanceBefore).to.equal(claimAmount);
+    expect(await outstandingLocked()).to.equal(BigInt(0));
+
+    await setProjectFrozen(false);
+    await closeProject();
+    expect(svm.getAccount(project.pda)).to.be.null;
+  });
+  it("Claims with a secp256k1 (Ethereum) distributor signature", async () => {
+    const ethKey = secp256k1.utils.randomPrivateKey();
+    const updateDistributors = (update: object) =>
+      program.methods
+        .updateGlobalConfig(update)
+        .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+        .signers([authorityKeypair])
+        .rpc();
+    await updateDistributors({ add: { distributor: secp256k1KeyId(ethKey), weight: 1, keyType: { secp256k1: {} } } });
+
+    const nonce = BigInt(3034);
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(1000),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline: BigInt(9999999999),
+    });
+    const message = Buffer.from(serialize(AirdropMessage.schema, msg));
+    const [, claimIx] = await buildSignedClaim(nonce, BigInt(1000));
+
+    try {
+      await sendTransaction(svm, recipientKeypair, [createSecp256k1Instruction(secp256k1.utils.randomPrivateKey(), message, 0), claimIx]);
+      expect.fail("Should have failed for an unregistered Ethereum address");
+    } catch (error) {
+      expect(error.message).to.include("DistributorMismatch");
+    }
+
+    // The verification must come before the claim that relies on it
+    try {
+      await sendTransaction(svm, recipientKeypair, [claimIx, createSecp256k1Instruction(ethKey, message, 1)]);
+      expect.fail("Should have failed for a signature verified after the claim");
+    } catch (error) {
+      expect(error.message).to.include("InvalidInstructionSysvar");
+    }
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, [createSecp256k1Instruction(ethKey, message, 0), claimIx]);
+    expect(await getSplTokenBalance(svm, mint, recipientKeypair.publicKey) - balanceBefore).to.equal(BigInt(1000));
+
+    // Remove the Ethereum distributor for other tests
+    await updateDistributors({ remove: { distributor: secp256k1KeyId(ethKey) } });
+  });
+  it("Mints claims for a project that holds the mint authority", async () => {
+    const project = await createFundedProject();
+    const setMintOnClaim = (mintOnClaim: boolean) =>
+      program.methods
+        .setProjectMintOnClaim(new anchor.BN(project.nonce.toString()), mintOnClaim)
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: project.pda, mint: mint, tokenProgram: TOKEN_PROGRAM_ID })
+        .signers([authorityKeypair])
+        .rpc();
+
+    await sendTransaction(svm, authorityKeypair, [
+      createSetAuthorityInstruction(mint, authorityKeypair.publicKey, AuthorityType.MintTokens, project.pda, [], TOKEN_PROGRAM_ID),
+    ]);
+    await setMintOnClaim(true);
+
+    const supplyBefore = (await getMint(provider.connection, mint)).supply;
+    const vaultBefore = (await getAccount(provider.connection, project.vault)).amount;
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, await buildSignedProjectClaim(project, BigInt(1), BigInt(5000)));
+
+    // The claim is minted to the recipient rather than paid out of the vault
+    expect(await getSplTokenBalance(svm, mint, recipientKeypair.publicKey) - balanceBefore).to.equal(BigInt(5000));
+    expect((await getMint(provider.connection, mint)).supply - supplyBefore).to.equal(BigInt(5000));
+    expect((await getAccount(provider.connection, project.vault)).amount).to.equal(vaultBefore);
+
+    // Hand the mint authority back for other tests
+    await setMintOnClaim(false);
+  });
+  it("Pays a claim into another token account owned by the recipient", async () => {
+    const recipientAccount = await createTokenAccount(provider, recipientKeypair, mint, recipientKeypair.publicKey);
+    const strangerAccount = await createTokenAccount(provider, recipientKeypair, mint, Keypair.generate().publicKey);
+
+    try {
+      await sendTransaction(
+        svm,
+        recipientKeypair,
+        await buildSignedClaim(BigInt(3035), BigInt(1000), distributorKeypair, { destinationTokenAccount: strangerAccount })
+      );
+      expect.fail("Should have failed for a destination owned by someone else");
+    } catch (error) {
+      expect(error.message).to.include("InvalidDestination");
+    }
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(
+      svm,
+      recipientKeypair,
+      await buildSignedClaim(BigInt(3035), BigInt(1000), distributorKeypair, { destinationTokenAccount: recipientAccount })
+    );
+    expect(await getTokenAccountBalance(svm, recipientAccount)).to.equal(BigInt(1000));
+    expect(await getSplTokenBalance(svm, mint, recipientKeypair.publicKey)).to.equal(balanceBefore);
+  });
+  it("Names the strict Ed25519 layout rule a payload breaks", async () => {
+    const nonce = BigInt(3036);
+    const msg = createAirdropMessage({
+      recipient: recipientKeypair.publicKey,
+      mint: mint,
+      projectNonce: projectNonce,
+      amount: BigInt(1000),
+      programId: program.programId,
+      version: 1,
+      nonce,
+      deadline: BigInt(9999999999),
+    });
+    const message = Buffer.from(serialize(AirdropMessage.schema, msg));
+    const [ed25519Ix, claimIx] = await buildSignedClaim(nonce, BigInt(1000));
+
+    // The byte after the signature count is padding the precompile ignores
+    const paddedData = Buffer.from(ed25519Ix.data);
+    paddedData.writeUInt8(1, 1);
+    try {
+      await sendTransaction(svm, recipientKeypair, [
+        new TransactionInstruction({ keys: [], programId: Ed25519Program.programId, data: paddedData }),
+        claimIx,
+      ]);
+      expect.fail("Should have failed for a non-zero padding byte");
+    } catch (error) {
+      expect(error.message).to.include("Ed25519PaddingNotZero");
+    }
+
+    // A signed region whose offset starts inside the public key: the signature covers the
+    // public key followed by the message, which the precompile accepts
+    const signedRegion = Buffer.concat([distributorKeypair.publicKey.toBuffer(), message]);
+    const overlapData = Buffer.alloc(16 + 64 + signedRegion.length);
+    overlapData.writeUInt8(1, 0);
+    overlapData.writeUInt16LE(16, 2); // signature
+    overlapData.writeUInt16LE(0xffff, 4);
+    overlapData.writeUInt16LE(80, 6); // public key
+    overlapData.writeUInt16LE(0xffff, 8);
+    overlapData.writeUInt16LE(80, 10); // message, starting at the public key
+    overlapData.writeUInt16LE(signedRegion.length, 12);
+    overlapData.writeUInt16LE(0xffff, 14);
+    overlapData.set(nacl.sign.detached(signedRegion, distributorKeypair.secretKey), 16);
+    overlapData.set(signedRegion, 80);
+    try {
+      await sendTransaction(svm, recipientKeypair, [
+        new TransactionInstruction({ keys: [], programId: Ed25519Program.programId, data: overlapData }),
+        claimIx,
+      ]);
+      expect.fail("Should have failed for overlapping regions");
+    } catch (error) {
+      expect(error.message).to.include("Ed25519RegionOverlap");
+    }
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, recipientKeypair.publicKey);
+    await sendTransaction(svm, recipientKeypair, [ed25519Ix, claimIx]);
+    expect(await getSplTokenBalance(svm, mint, recipientKeypair.publicKey) - balanceBefore).to.equal(BigInt(1000));
+  });
+});
@@ -0,0 +1,83 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Airdrop } from "../../target/types/airdrop";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { fromWorkspace, LiteSVMProvider } from 'anchor-litesvm';
+import { createSplToken } from "../utils/spl";
+import { emptyProjectMetadata } from "../utils/project";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+describe("close_global_config", () => {
+  let svm: LiteSVM;
+  let provider: LiteSVMProvider;
+  let program: Program<Airdrop>;
+
+  let authorityKeypair: Keypair;
+  let globalConfigPda: PublicKey;
+
+  before(async () => {
+    svm = fromWorkspace('./')
+      .withBuiltins()
+      .withSysvars()
+      .withDefaultPrograms()
+      .withPrecompiles();
+    provider = new LiteSVMProvider(svm);
+    anchor.setProvider(provider);
+    program = anchor.workspace.Airdrop as Program<Airdrop>;
+
+    authorityKeypair = Keypair.generate();
+    await svm.airdrop(authorityKeypair.publicKey, BigInt(10000000000));
+
+    [globalConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("global_config")],
+      program.programId
+    );
+
+    await program.methods
+      .createGlobalConfig([{ pubkey: Keypair.generate().publicKey, weight: 1, active: true, keyType: { ed25519: {} } }], 1, 1)
+      .accountsPartial({
+        payer: authorityKeypair.publicKey,
+        authority: authorityKeypair.publicKey,
+        globalConfig: globalConfigPda,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+
+    // Create a project so the config has an active project
+    const mint = await createSplToken(provider, authorityKeypair, 9);
+    await program.methods
+      .createProject(new anchor.BN(0), new anchor.BN(0), emptyProjectMetadata)
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        authority: authorityKeypair.publicKey,
+        mint: mint,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+  });
+
+  it("Fails while active projects remain unless forced", async () => {
+    try {
+      await program.methods
+        .closeGlobalConfig(false)
+        .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+        .signers([authorityKeypair])
+        .rpc();
+      expect.fail("Should have failed with active projects");
+    } catch (error) {
+      expect(error.message).to.include("ActiveProjectsRemain");
+    }
+
+    const balanceBefore = svm.getBalance(authorityKeypair.publicKey);
+    await program.methods
+      .closeGlobalConfig(true)
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    expect(svm.getAccount(globalConfigPda)).to.be.null;
+    expect(svm.getBalance(authorityKeypair.publicKey) > balanceBefore).to.be.true;
+  });
+});
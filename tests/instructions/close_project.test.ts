@@ -0,0 +1,179 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Airdrop } from "../../target/types/airdrop";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { fromWorkspace, LiteSVMProvider } from 'anchor-litesvm';
+import { sendTransaction } from "../utils/svm";
+import { createSplToken, getOrCreateAssociatedTokenAccount, getSplTokenBalance } from "../utils/spl";
+import { emptyProjectMetadata, getTokenVaultAddress } from "../utils/project";
+import { AuthorityType, createMintToInstruction, createSetAuthorityInstruction, MintLayout, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+describe("close_project", () => {
+  let svm: LiteSVM;
+  let provider: LiteSVMProvider;
+  let program: Program<Airdrop>;
+
+  let authorityKeypair: Keypair;
+  let mint: PublicKey;
+
+  before(async () => {
+    svm = fromWorkspace('./')
+      .withBuiltins()
+      .withSysvars()
+      .withDefaultPrograms()
+      .withPrecompiles();
+    provider = new LiteSVMProvider(svm);
+    anchor.setProvider(provider);
+    program = anchor.workspace.Airdrop as Program<Airdrop>;
+
+    authorityKeypair = Keypair.generate();
+    await svm.airdrop(authorityKeypair.publicKey, BigInt(10000000000));
+
+    await program.methods
+      .createGlobalConfig([{ pubkey: Keypair.generate().publicKey, weight: 1, active: true, keyType: { ed25519: {} } }], 1, 1)
+      .accountsPartial({
+        payer: authorityKeypair.publicKey,
+        authority: authorityKeypair.publicKey,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+
+    mint = await createSplToken(provider, authorityKeypair, 9);
+  });
+
+  const createFundedProject = async (nonce: number) => {
+    const [projectPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("project"), new anchor.BN(nonce).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    await program.methods
+      .createProject(new anchor.BN(0), new anchor.BN(0), emptyProjectMetadata)
+      .accountsPartial({ tokenProgram: TOKEN_PROGRAM_ID, authority: authorityKeypair.publicKey, mint: mint })
+      .signers([authorityKeypair])
+      .rpc();
+
+    const mintToIx = createMintToInstruction(
+      mint,
+      getTokenVaultAddress(program.programId, projectPda),
+      authorityKeypair.publicKey,
+      BigInt(1000000),
+      [],
+      TOKEN_PROGRAM_ID
+    );
+    await sendTransaction(svm, authorityKeypair, [mintToIx]);
+
+    return projectPda;
+  };
+
+  it("Closes the project and sweeps its tokens to the authority", async () => {
+    const projectPda = await createFundedProject(1);
+    const balanceBefore = await getSplTokenBalance(svm, mint, authorityKeypair.publicKey);
+
+    await program.methods
+      .closeProject(new anchor.BN(1), false)
+      .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda, mint: mint, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([authorityKeypair])
+      .rpc();
+
+    expect(svm.getAccount(projectPda)).to.be.null;
+    const balanceAfter = await getSplTokenBalance(svm, mint, authorityKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(1000000));
+  });
+
+  it("Archives the project into a minimal record when requested", async () => {
+    const projectPda = await createFundedProject(2);
+
+    await program.methods
+      .closeProject(new anchor.BN(2), true)
+      .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda, mint: mint, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([authorityKeypair])
+      .rpc();
+
+    const archived = await program.account.archivedProject.fetch(projectPda);
+    expect(archived.nonce.toNumber()).to.equal(2);
+    expect(archived.mint.toBase58()).to.equal(mint.toBase58());
+    expect(archived.totalClaimed.toNumber()).to.equal(0);
+    expect(archived.claimCount.toNumber()).to.equal(0);
+
+    try {
+      await program.account.project.fetch(projectPda);
+      expect.fail("Archived project should no longer decode as a Project");
+    } catch (error) {
+      expect(error.message).to.not.include("Archived project should");
+    }
+  });
+
+  it("Refuses to close while funder deposits are refundable", async () => {
+    const projectPda = await createFundedProject(3);
+
+    const funder = Keypair.generate();
+    await svm.airdrop(funder.publicKey, BigInt(10000000000));
+    const funderTokenAccount = await getOrCreateAssociatedTokenAccount(svm, mint, funder.publicKey, false, funder);
+    await sendTransaction(svm, authorityKeypair, [
+      createMintToInstruction(mint, funderTokenAccount, authorityKeypair.publicKey, BigInt(300000), [], TOKEN_PROGRAM_ID),
+    ]);
+    await program.methods
+      .fundProject(new anchor.BN(3), new anchor.BN(300000))
+      .accountsPartial({ tokenProgram: TOKEN_PROGRAM_ID, funder: funder.publicKey, funderTokenAccount, project: projectPda, mint: mint })
+      .signers([funder])
+      .rpc();
+
+    const close = () =>
+      program.methods
+        .closeProject(new anchor.BN(3), false)
+        .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda, mint: mint, tokenProgram: TOKEN_PROGRAM_ID })
+        .signers([authorityKeypair])
+        .rpc();
+
+    try {
+      await close();
+      expect.fail("Should have failed while the funder's deposit is still in the vault");
+    } catch (error) {
+      expect(error.message).to.include("DepositsOutstanding");
+    }
+
+    // Once the funder is refunded, the rest of the vault is the authority's to sweep
+    const now = Number(svm.getClock().unixTimestamp);
+    await program.methods
+      .setProjectClaimWindow(new anchor.BN(3), new anchor.BN(0), new anchor.BN(now - 1))
+      .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda })
+      .signers([authorityKeypair])
+      .rpc();
+    await program.methods
+      .refundFunder(new anchor.BN(3))
+      .accountsPartial({ tokenProgram: TOKEN_PROGRAM_ID, funder: funder.publicKey, funderTokenAccount, project: projectPda, mint: mint })
+      .signers([funder])
+      .rpc();
+    expect(await getSplTokenBalance(svm, mint, funder.publicKey)).to.equal(BigInt(300000));
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, authorityKeypair.publicKey);
+    await close();
+    const balanceAfter = await getSplTokenBalance(svm, mint, authorityKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(1000000));
+  });
+
+  it("Hands the mint authority back when closing a project that mints claims", async () => {
+    const projectPda = await createFundedProject(4);
+
+    await sendTransaction(svm, authorityKeypair, [
+      createSetAuthorityInstruction(mint, authorityKeypair.publicKey, AuthorityType.MintTokens, projectPda, [], TOKEN_PROGRAM_ID),
+    ]);
+    await program.methods
+      .setProjectMintOnClaim(new anchor.BN(4), true)
+      .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda, mint: mint, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([authorityKeypair])
+      .rpc();
+
+    await program.methods
+      .closeProject(new anchor.BN(4), false)
+      .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda, mint: mint, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([authorityKeypair])
+      .rpc();
+
+    const mintState = MintLayout.decode(Buffer.from(svm.getAccount(mint).data));
+    expect(mintState.mintAuthority.toBase58()).to.equal(authorityKeypair.publicKey.toBase58());
+  });
+});
@@ -0,0 +1,84 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Airdrop } from "../../target/types/airdrop";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { fromWorkspace, LiteSVMProvider } from 'anchor-litesvm';
+import { sendTransaction } from "../utils/svm";
+import { findEvent } from "../utils/events";
+
+describe("create_global_config", () => {
+  let svm: LiteSVM;
+  let provider: LiteSVMProvider;
+  let program: Program<Airdrop>;
+
+  let authorityKeypair: Keypair;
+  let globalConfigPda: PublicKey;
+
+  before(async () => {
+    svm = fromWorkspace('./')
+      .withBuiltins()
+      .withSysvars()
+      .withDefaultPrograms()
+      .withPrecompiles();
+    provider = new LiteSVMProvider(svm);
+    anchor.setProvider(provider);
+    program = anchor.workspace.Airdrop as Program<Airdrop>;
+
+    authorityKeypair = Keypair.generate();
+    await svm.airdrop(authorityKeypair.publicKey, BigInt(10000000000));
+
+    [globalConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("global_config")],
+      program.programId
+    );
+  });
+
+  it("Rejects an empty, duplicated or oversized initial distributor set", async () => {
+    const distributor = (pubkey: PublicKey) => ({ pubkey, weight: 1, active: true, keyType: { ed25519: {} } });
+    const create = (distributors: ReturnType<typeof distributor>[]) =>
+      program.methods
+        .createGlobalConfig(distributors, 1, 1)
+        .accountsPartial({
+          payer: authorityKeypair.publicKey,
+          authority: authorityKeypair.publicKey,
+          globalConfig: globalConfigPda,
+        })
+        .signers([authorityKeypair])
+        .rpc();
+
+    const cases: [ReturnType<typeof distributor>[], string][] = [
+      [[], "EmptyDistributorSet"],
+      [[distributor(authorityKeypair.publicKey), distributor(authorityKeypair.publicKey)], "DistributorAlreadyExists"],
+      [Array.from({ length: 11 }, () => distributor(Keypair.generate().publicKey)), "TooManyDistributors"],
+    ];
+    for (const [distributors, expected] of cases) {
+      try {
+        await create(distributors);
+        expect.fail("Should have rejected the distributor set");
+      } catch (error) {
+        expect(error.message).to.include(expected);
+      }
+    }
+  });
+
+  it("Announces the new configuration in a GlobalConfigCreated event", async () => {
+    const distributor = Keypair.generate().publicKey;
+    const createIx = await program.methods
+      .createGlobalConfig([{ pubkey: distributor, weight: 1, active: true, keyType: { ed25519: {} } }], 1, 1)
+      .accountsPartial({
+        payer: authorityKeypair.publicKey,
+        authority: authorityKeypair.publicKey,
+        globalConfig: globalConfigPda,
+      })
+      .instruction();
+    const { logs } = await sendTransaction(svm, authorityKeypair, [createIx]);
+
+    const event = findEvent(program, logs, "GlobalConfigCreated");
+    expect(event.authority.toBase58()).to.equal(authorityKeypair.publicKey.toBase58());
+    expect(event.distributors.map((d) => d.pubkey.toBase58())).to.deep.equal([distributor.toBase58()]);
+    expect(event.threshold).to.equal(1);
+    expect(event.requiredWeight).to.equal(1);
+  });
+});
@@ -0,0 +1,311 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Airdrop } from "../../target/types/airdrop";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { fromWorkspace, LiteSVMProvider } from 'anchor-litesvm';
+import { createSplToken } from "../utils/spl";
+import { emptyProjectMetadata } from "../utils/project";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { sendTransaction } from "../utils/svm";
+import { findEvent } from "../utils/events";
+
+describe("create_project", () => {
+  let svm: LiteSVM;
+  let provider: LiteSVMProvider;
+  let program: Program<Airdrop>;
+
+  let authorityKeypair: Keypair;
+  let operatorKeypair: Keypair;
+  let globalConfigPda: PublicKey;
+  let mint: PublicKey;
+
+  before(async () => {
+    svm = fromWorkspace('./')
+      .withBuiltins()
+      .withSysvars()
+      .withDefaultPrograms()
+      .withPrecompiles();
+    provider = new LiteSVMProvider(svm);
+    anchor.setProvider(provider);
+    program = anchor.workspace.Airdrop as Program<Airdrop>;
+
+    authorityKeypair = Keypair.generate();
+    operatorKeypair = Keypair.generate();
+
+    await svm.airdrop(authorityKeypair.publicKey, BigInt(10000000000));
+    await svm.airdrop(operatorKeypair.publicKey, BigInt(10000000000));
+
+    [globalConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("global_config")],
+      program.programId
+    );
+
+    await program.methods
+      .createGlobalConfig([{ pubkey: Keypair.generate().publicKey, weight: 1, active: true, keyType: { ed25519: {} } }], 1, 1)
+      .accountsPartial({
+        payer: authorityKeypair.publicKey,
+        authority: authorityKeypair.publicKey,
+        globalConfig: globalConfigPda,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+
+    mint = await createSplToken(provider, authorityKeypair, 9);
+  });
+
+  it("Restricts project creation to operators when configured", async () => {
+    await program.methods
+      .setProjectCreationMode({ operatorsOnly: {} })
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    try {
+      await program.methods
+        .createProject(new anchor.BN(0), new anchor.BN(0), emptyProjectMetadata)
+        .accountsPartial({ tokenProgram: TOKEN_PROGRAM_ID, authority: operatorKeypair.publicKey, mint: mint })
+        .signers([operatorKeypair])
+        .rpc();
+      expect.fail("Should have failed for a non-operator");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+
+    await program.methods
+      .updateOperators({ add: { operator: operatorKeypair.publicKey } })
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    await program.methods
+      .createProject(new anchor.BN(0), new anchor.BN(0), emptyProjectMetadata)
+      .accountsPartial({ tokenProgram: TOKEN_PROGRAM_ID, authority: operatorKeypair.publicKey, mint: mint })
+      .signers([operatorKeypair])
+      .rpc();
+
+    const config = await program.account.globalConfig.fetch(globalConfigPda);
+    expect(config.projectCount.toNumber()).to.equal(1);
+  });
+
+  it("Admits allowlisted creators in allowlist-only mode", async () => {
+    const creator = Keypair.generate();
+    await svm.airdrop(creator.publicKey, BigInt(10000000000));
+
+    await program.methods
+      .setProjectCreationMode({ allowlistOnly: {} })
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    try {
+      await program.methods
+        .createProject(new anchor.BN(0), new anchor.BN(0), emptyProjectMetadata)
+        .accountsPartial({ tokenProgram: TOKEN_PROGRAM_ID, authority: creator.publicKey, mint: mint })
+        .signers([creator])
+        .rpc();
+      expect.fail("Should have failed for a creator that is not allowlisted");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+
+    await program.methods
+      .addCreatorToAllowlist(creator.publicKey)
+      .accountsPartial({ payer: authorityKeypair.publicKey, authority: authorityKeypair.publicKey })
+      .signers([authorityKeypair])
+      .rpc();
+
+    await program.methods
+      .createProject(new anchor.BN(0), new anchor.BN(0), emptyProjectMetadata)
+      .accountsPartial({ tokenProgram: TOKEN_PROGRAM_ID, authority: creator.publicKey, mint: mint })
+      .signers([creator])
+      .rpc();
+  });
+
+  it("Stores project metadata and lets a project manager update it", async () => {
+    const nonce = new anchor.BN(3);
+    const [projectPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("project"), nonce.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [projectMetadataPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("project_metadata"), projectPda.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .setProjectCreationMode({ open: {} })
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    await program.methods
+      .createProject(new anchor.BN(0), new anchor.BN(0), {
+        name: "Season 1",
+        symbol: "S1",
+        uri: "https://example.com/s1.json",
+        description: "First community airdrop",
+      })
+      .accountsPartial({ tokenProgram: TOKEN_PROGRAM_ID, authority: authorityKeypair.publicKey, mint: mint })
+      .signers([authorityKeypair])
+      .rpc();
+
+    // The counter assigned the next nonce after the two earlier projects
+    const project = await program.account.project.fetch(projectPda);
+    expect(project.nonce.toNumber()).to.equal(3);
+
+    // Every project so far is listed on the first registry page
+    const [registryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("project_registry"), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const registry = await program.account.projectRegistry.fetch(registryPda);
+    expect(registry.entries.map((entry) => entry.nonce.toNumber())).to.deep.equal([1, 2, 3]);
+    expect(registry.entries[2].project.toBase58()).to.equal(projectPda.toBase58());
+
+    let metadata = await program.account.projectMetadata.fetch(projectMetadataPda);
+    expect(metadata.name).to.equal("Season 1");
+    expect(metadata.project.toBase58()).to.equal(projectPda.toBase58());
+
+    // A manager may update metadata without holding the project authority key
+    await program.methods
+      .addManager(nonce, operatorKeypair.publicKey)
+      .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    await program.methods
+      .updateProjectMetadata(nonce, {
+        name: "Season 1 (extended)",
+        symbol: "S1",
+        uri: "https://example.com/s1.json",
+        description: "First community airdrop, extended",
+      })
+      .accountsPartial({ signer: operatorKeypair.publicKey, project: projectPda })
+      .signers([operatorKeypair])
+      .rpc();
+
+    metadata = await program.account.projectMetadata.fetch(projectMetadataPda);
+    expect(metadata.name).to.equal("Season 1 (extended)");
+
+    try {
+      await program.methods
+        .updateProjectMetadata(nonce, { ...emptyProjectMetadata, name: "x".repeat(33) })
+        .accountsPartial({ signer: authorityKeypair.publicKey, project: projectPda })
+        .signers([authorityKeypair])
+        .rpc();
+      expect.fail("Should have failed for an over-long name");
+    } catch (error) {
+      expect(error.message).to.include("MetadataTooLong");
+    }
+  });
+
+  it("Skips counter nonces already taken by older projects", async () => {
+    const [projectCounterPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("project_counter")],
+      program.programId
+    );
+    const projectAddress = (nonce: number) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("project"), Buffer.from(new anchor.BN(nonce).toArray("le", 8))],
+        program.programId
+      )[0];
+    const createProject = () =>
+      program.methods
+        .createProject(new anchor.BN(0), new anchor.BN(0), emptyProjectMetadata)
+        .accountsPartial({ tokenProgram: TOKEN_PROGRAM_ID, authority: authorityKeypair.publicKey, mint: mint })
+        .signers([authorityKeypair])
+        .rpc();
+
+    // A project created with a caller-chosen nonce before the counter existed
+    const takenNonce = (await program.account.projectCounter.fetch(projectCounterPda)).count.toNumber() + 1;
+    const projectDiscriminator = program.idl.accounts.find((account) => account.name === "project").discriminator;
+    svm.setAccount(projectAddress(takenNonce), {
+      lamports: 1000000000,
+      data: Buffer.concat([Buffer.from(projectDiscriminator), Buffer.alloc(256)]),
+      owner: program.programId,
+      executable: false,
+    });
+
+    try {
+      await createProject();
+      expect.fail("Should have failed while the counter's next nonce is taken");
+    } catch (error) {
+      expect(error.message).to.include("already in use");
+    }
+
+    await program.methods
+      .skipProjectNonce()
+      .accountsPartial({ payer: authorityKeypair.publicKey, project: projectAddress(takenNonce) })
+      .signers([authorityKeypair])
+      .rpc();
+
+    // Only an occupied nonce can be skipped
+    try {
+      await program.methods
+        .skipProjectNonce()
+        .accountsPartial({ payer: authorityKeypair.publicKey, project: projectAddress(takenNonce + 1) })
+        .signers([authorityKeypair])
+        .rpc();
+      expect.fail("Should have failed for a free nonce");
+    } catch (error) {
+      expect(error.message).to.include("ProjectNonceAvailable");
+    }
+
+    await createProject();
+    const project = await program.account.project.fetch(projectAddress(takenNonce + 1));
+    expect(project.nonce.toNumber()).to.equal(takenNonce + 1);
+  });
+
+  it("Announces the new project in a ProjectCreated event", async () => {
+    const [projectCounterPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("project_counter")],
+      program.programId
+    );
+    const nonce = (await program.account.projectCounter.fetch(projectCounterPda)).count.toNumber() + 1;
+    const [projectPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("project"), Buffer.from(new anchor.BN(nonce).toArray("le", 8))],
+      program.programId
+    );
+
+    const createIx = await program.methods
+      .createProject(new anchor.BN(0), new anchor.BN(0), emptyProjectMetadata)
+      .accountsPartial({ tokenProgram: TOKEN_PROGRAM_ID, authority: authorityKeypair.publicKey, mint: mint })
+      .instruction();
+    const { logs } = await sendTransaction(svm, authorityKeypair, [createIx]);
+
+    const event = findEvent(program, logs, "ProjectCreated");
+    expect(event.project.toBase58()).to.equal(projectPda.toBase58());
+    expect(event.nonce.toNumber()).to.equal(nonce);
+    expect(event.mint.toBase58()).to.equal(mint.toBase58());
+    expect(event.authority.toBase58()).to.equal(authorityKeypair.publicKey.toBase58());
+  });
+
+  it("Rejects mints with a freeze authority unless the global authority allows risky mints", async () => {
+    const freezableMint = await createSplToken(provider, authorityKeypair, 9, authorityKeypair.publicKey);
+    const createProject = () =>
+      program.methods
+        .createProject(new anchor.BN(0), new anchor.BN(0), emptyProjectMetadata)
+        .accountsPartial({ tokenProgram: TOKEN_PROGRAM_ID, authority: authorityKeypair.publicKey, mint: freezableMint })
+        .signers([authorityKeypair])
+        .rpc();
+    const setAllowRiskyMints = (allowRiskyMints: boolean) =>
+      program.methods
+        .setAllowRiskyMints(allowRiskyMints)
+        .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+        .signers([authorityKeypair])
+        .rpc();
+
+    try {
+      await createProject();
+      expect.fail("Should have failed for a freezable mint");
+    } catch (error) {
+      expect(error.message).to.include("RiskyMint");
+    }
+
+    await setAllowRiskyMints(true);
+    await createProject();
+    await setAllowRiskyMints(false);
+  });
+});
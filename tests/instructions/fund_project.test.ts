@@ -0,0 +1,238 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Airdrop } from "../../target/types/airdrop";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { fromWorkspace, LiteSVMProvider } from 'anchor-litesvm';
+import { sendTransaction } from "../utils/svm";
+import { createSplToken, getOrCreateAssociatedTokenAccount, getSplTokenBalance, getTokenAccountBalance } from "../utils/spl";
+import { createMintToInstruction, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { emptyProjectMetadata, getTokenVaultAddress } from "../utils/project";
+
+describe("fund_project", () => {
+  let svm: LiteSVM;
+  let provider: LiteSVMProvider;
+  let program: Program<Airdrop>;
+
+  let authorityKeypair: Keypair;
+  let funderKeypair: Keypair;
+  let mint: PublicKey;
+  let projectPda: PublicKey;
+  const projectNonce = BigInt(1);
+
+  before(async () => {
+    svm = fromWorkspace('./')
+      .withBuiltins()
+      .withSysvars()
+      .withDefaultPrograms()
+      .withPrecompiles();
+    provider = new LiteSVMProvider(svm);
+    anchor.setProvider(provider);
+    program = anchor.workspace.Airdrop as Program<Airdrop>;
+
+    authorityKeypair = Keypair.generate();
+    funderKeypair = Keypair.generate();
+
+    await svm.airdrop(authorityKeypair.publicKey, BigInt(10000000000));
+    await svm.airdrop(funderKeypair.publicKey, BigInt(10000000000));
+
+    await program.methods
+      .createGlobalConfig([{ pubkey: Keypair.generate().publicKey, weight: 1, active: true, keyType: { ed25519: {} } }], 1, 1)
+      .accountsPartial({
+        payer: authorityKeypair.publicKey,
+        authority: authorityKeypair.publicKey,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+
+    mint = await createSplToken(provider, authorityKeypair, 9);
+
+    [projectPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("project"), Buffer.from(new anchor.BN(projectNonce.toString()).toArray("le", 8))],
+      program.programId
+    );
+
+    await program.methods
+      .createProject(new anchor.BN(0), new anchor.BN(0), emptyProjectMetadata)
+      .accountsPartial({ tokenProgram: TOKEN_PROGRAM_ID, authority: authorityKeypair.publicKey, mint: mint })
+      .signers([authorityKeypair])
+      .rpc();
+
+    const funderTokenAccount = await getOrCreateAssociatedTokenAccount(
+      svm,
+      mint,
+      funderKeypair.publicKey,
+      false,
+      funderKeypair
+    );
+    const mintToIx = createMintToInstruction(
+      mint,
+      funderTokenAccount,
+      authorityKeypair.publicKey,
+      BigInt(1000000),
+      [],
+      TOKEN_PROGRAM_ID
+    );
+    await sendTransaction(svm, authorityKeypair, [mintToIx]);
+  });
+
+  it("Transfers tokens into the project and records the deposit", async () => {
+    const funderTokenAccount = await getOrCreateAssociatedTokenAccount(
+      svm,
+      mint,
+      funderKeypair.publicKey,
+      false,
+      funderKeypair
+    );
+
+    await program.methods
+      .fundProject(new anchor.BN(projectNonce.toString()), new anchor.BN(400000))
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        funder: funderKeypair.publicKey,
+        funderTokenAccount: funderTokenAccount,
+        project: projectPda,
+        mint: mint,
+      })
+      .signers([funderKeypair])
+      .rpc();
+
+    await program.methods
+      .fundProject(new anchor.BN(projectNonce.toString()), new anchor.BN(100000))
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        funder: funderKeypair.publicKey,
+        funderTokenAccount: funderTokenAccount,
+        project: projectPda,
+        mint: mint,
+      })
+      .signers([funderKeypair])
+      .rpc();
+
+    expect(await getTokenAccountBalance(svm, getTokenVaultAddress(program.programId, projectPda))).to.equal(BigInt(500000));
+    const project = await program.account.project.fetch(projectPda);
+    expect(project.totalFunded.toNumber()).to.equal(500000);
+  });
+
+  it("Reports the project status through return data", async () => {
+    const status = await program.methods
+      .getProjectStatus(new anchor.BN(projectNonce.toString()))
+      .accountsPartial({ project: projectPda })
+      .view();
+
+    expect(status.remainingBalance.toNumber()).to.equal(500000);
+    expect(status.totalClaimed.toNumber()).to.equal(0);
+    expect(status.paused).to.equal(false);
+  });
+
+  it("Refunds the funder's pro-rata share only outside the claim window", async () => {
+    const funderTokenAccount = await getOrCreateAssociatedTokenAccount(
+      svm,
+      mint,
+      funderKeypair.publicKey,
+      false,
+      funderKeypair
+    );
+
+    const refund = () =>
+      program.methods
+        .refundFunder(new anchor.BN(projectNonce.toString()))
+        .accountsPartial({
+          tokenProgram: TOKEN_PROGRAM_ID,
+          funder: funderKeypair.publicKey,
+          funderTokenAccount: funderTokenAccount,
+          project: projectPda,
+          mint: mint,
+        })
+        .signers([funderKeypair])
+        .rpc();
+
+    // The project's claim window is open-ended, so refunds are unavailable
+    try {
+      await refund();
+      expect.fail("Should have failed while the claim window is open");
+    } catch (error) {
+      expect(error.message).to.include("RefundNotAvailable");
+    }
+
+    const now = Number(svm.getClock().unixTimestamp);
+    await program.methods
+      .setProjectClaimWindow(new anchor.BN(projectNonce.toString()), new anchor.BN(0), new anchor.BN(now - 1))
+      .accountsPartial({ authority: authorityKeypair.publicKey, project: projectPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    // As the only funder, the whole remaining balance is returned
+    const balanceBefore = await getSplTokenBalance(svm, mint, funderKeypair.publicKey);
+    await refund();
+    const balanceAfter = await getSplTokenBalance(svm, mint, funderKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(500000));
+  });
+
+  it("Refunds only deposits, never tokens the authority added to the vault", async () => {
+    const otherProjectNonce = BigInt(2);
+    const [otherProjectPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("project"), Buffer.from(new anchor.BN(otherProjectNonce.toString()).toArray("le", 8))],
+      program.programId
+    );
+    await program.methods
+      .createProject(new anchor.BN(0), new anchor.BN(0), emptyProjectMetadata)
+      .accountsPartial({ tokenProgram: TOKEN_PROGRAM_ID, authority: authorityKeypair.publicKey, mint: mint })
+      .signers([authorityKeypair])
+      .rpc();
+
+    // Tokens minted straight into the vault are not a tracked deposit
+    await sendTransaction(svm, authorityKeypair, [
+      createMintToInstruction(
+        mint,
+        getTokenVaultAddress(program.programId, otherProjectPda),
+        authorityKeypair.publicKey,
+        BigInt(1000000),
+        [],
+        TOKEN_PROGRAM_ID
+      ),
+    ]);
+
+    const funderTokenAccount = await getOrCreateAssociatedTokenAccount(
+      svm,
+      mint,
+      funderKeypair.publicKey,
+      false,
+      funderKeypair
+    );
+    await program.methods
+      .fundProject(new anchor.BN(otherProjectNonce.toString()), new anchor.BN(200000))
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        funder: funderKeypair.publicKey,
+        funderTokenAccount: funderTokenAccount,
+        project: otherProjectPda,
+        mint: mint,
+      })
+      .signers([funderKeypair])
+      .rpc();
+
+    const now = Number(svm.getClock().unixTimestamp);
+    await program.methods
+      .setProjectClaimWindow(new anchor.BN(otherProjectNonce.toString()), new anchor.BN(0), new anchor.BN(now - 1))
+      .accountsPartial({ authority: authorityKeypair.publicKey, project: otherProjectPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    const balanceBefore = await getSplTokenBalance(svm, mint, funderKeypair.publicKey);
+    await program.methods
+      .refundFunder(new anchor.BN(otherProjectNonce.toString()))
+      .accountsPartial({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        funder: funderKeypair.publicKey,
+        funderTokenAccount: funderTokenAccount,
+        project: otherProjectPda,
+        mint: mint,
+      })
+      .signers([funderKeypair])
+      .rpc();
+    const balanceAfter = await getSplTokenBalance(svm, mint, funderKeypair.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(BigInt(200000));
+  });
+});
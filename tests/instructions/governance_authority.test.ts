@@ -0,0 +1,275 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Airdrop } from "../../target/types/airdrop";
+import { Keypair, PublicKey, TransactionInstruction } from "@solana/web3.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { fromWorkspace, LiteSVMProvider } from 'anchor-litesvm';
+import {
+  createInstructionData,
+  GovernanceConfig,
+  MintMaxVoteWeightSource,
+  PROGRAM_VERSION_V3,
+  Vote,
+  VoteThreshold,
+  VoteThresholdType,
+  VoteTipping,
+  VoteType,
+  withCastVote,
+  withCreateGovernance,
+  withCreateNativeTreasury,
+  withCreateProposal,
+  withCreateRealm,
+  withDepositGoverningTokens,
+  withExecuteTransaction,
+  withInsertTransaction,
+  withSignOffProposal,
+  YesNoVote,
+} from "@solana/spl-governance";
+import { createMintToInstruction, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { sendTransaction } from "../utils/svm";
+import { addProgramFixture } from "../utils/fixtures";
+import { createSplToken, getOrCreateAssociatedTokenAccount } from "../utils/spl";
+
+const GOVERNANCE_PROGRAM_ID = new PublicKey("GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw");
+
+describe("governance authority", () => {
+  let svm: LiteSVM;
+  let provider: LiteSVMProvider;
+  let program: Program<Airdrop>;
+
+  let memberKeypair: Keypair;
+  let communityMint: PublicKey;
+  let realm: PublicKey;
+  let tokenOwnerRecord: PublicKey;
+  let governance: PublicKey;
+  let treasury: PublicKey;
+  let globalConfigPda: PublicKey;
+
+  before(async () => {
+    svm = fromWorkspace('./')
+      .withBuiltins()
+      .withSysvars()
+      .withDefaultPrograms()
+      .withPrecompiles();
+    addProgramFixture(svm, GOVERNANCE_PROGRAM_ID, "spl_governance.so");
+    provider = new LiteSVMProvider(svm);
+    anchor.setProvider(provider);
+    program = anchor.workspace.Airdrop as Program<Airdrop>;
+
+    memberKeypair = Keypair.generate();
+    await svm.airdrop(memberKeypair.publicKey, BigInt(10000000000));
+
+    // A realm whose single member holds the whole community supply
+    communityMint = await createSplToken(provider, memberKeypair, 0);
+    const memberTokenAccount = await getOrCreateAssociatedTokenAccount(
+      svm,
+      communityMint,
+      memberKeypair.publicKey,
+      false,
+      memberKeypair
+    );
+    await sendTransaction(svm, memberKeypair, [
+      createMintToInstruction(communityMint, memberTokenAccount, memberKeypair.publicKey, BigInt(1), [], TOKEN_PROGRAM_ID),
+    ]);
+
+    const realmIxs: TransactionInstruction[] = [];
+    realm = await withCreateRealm(
+      realmIxs,
+      GOVERNANCE_PROGRAM_ID,
+      PROGRAM_VERSION_V3,
+      "airdrop-dao",
+      memberKeypair.publicKey,
+      communityMint,
+      memberKeypair.publicKey,
+      undefined,
+      MintMaxVoteWeightSource.FULL_SUPPLY_FRACTION,
+      new anchor.BN(1)
+    );
+    tokenOwnerRecord = await withDepositGoverningTokens(
+      realmIxs,
+      GOVERNANCE_PROGRAM_ID,
+      PROGRAM_VERSION_V3,
+      realm,
+      memberTokenAccount,
+      communityMint,
+      memberKeypair.publicKey,
+      memberKeypair.publicKey,
+      memberKeypair.publicKey,
+      new anchor.BN(1)
+    );
+    await sendTransaction(svm, memberKeypair, realmIxs);
+
+    const governanceIxs: TransactionInstruction[] = [];
+    governance = await withCreateGovernance(
+      governanceIxs,
+      GOVERNANCE_PROGRAM_ID,
+      PROGRAM_VERSION_V3,
+      realm,
+      undefined,
+      new GovernanceConfig({
+        communityVoteThreshold: new VoteThreshold({ type: VoteThresholdType.YesVotePercentage, value: 60 }),
+        minCommunityTokensToCreateProposal: new anchor.BN(1),
+        minInstructionHoldUpTime: 0,
+        baseVotingTime: 3600,
+        communityVoteTipping: VoteTipping.Strict,
+        minCouncilTokensToCreateProposal: new anchor.BN(1),
+        councilVoteThreshold: new VoteThreshold({ type: VoteThresholdType.Disabled }),
+        councilVetoVoteThreshold: new VoteThreshold({ type: VoteThresholdType.Disabled }),
+        communityVetoVoteThreshold: new VoteThreshold({ type: VoteThresholdType.Disabled }),
+        councilVoteTipping: VoteTipping.Disabled,
+        votingCoolOffTime: 0,
+        depositExemptProposalCount: 10,
+      }),
+      tokenOwnerRecord,
+      memberKeypair.publicKey,
+      memberKeypair.publicKey
+    );
+    treasury = await withCreateNativeTreasury(
+      governanceIxs,
+      GOVERNANCE_PROGRAM_ID,
+      PROGRAM_VERSION_V3,
+      governance,
+      memberKeypair.publicKey
+    );
+    await sendTransaction(svm, memberKeypair, governanceIxs);
+
+    [globalConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("global_config")],
+      program.programId
+    );
+    await program.methods
+      .createGlobalConfig([{ pubkey: Keypair.generate().publicKey, weight: 1, active: true, keyType: { ed25519: {} } }], 1, 1)
+      .accountsPartial({
+        payer: memberKeypair.publicKey,
+        authority: memberKeypair.publicKey,
+        globalConfig: globalConfigPda,
+      })
+      .signers([memberKeypair])
+      .rpc();
+  });
+
+  // Proposes the instructions as one option, votes them through and executes them in order;
+  // the native treasury signs each instruction through the governance program
+  const executeProposal = async (name: string, ixs: TransactionInstruction[]) => {
+    const proposalIxs: TransactionInstruction[] = [];
+    const proposal = await withCreateProposal(
+      proposalIxs,
+      GOVERNANCE_PROGRAM_ID,
+      PROGRAM_VERSION_V3,
+      realm,
+      governance,
+      tokenOwnerRecord,
+      name,
+      "",
+      communityMint,
+      memberKeypair.publicKey,
+      undefined,
+      VoteType.SINGLE_CHOICE,
+      ["Approve"],
+      true,
+      memberKeypair.publicKey
+    );
+    await sendTransaction(svm, memberKeypair, proposalIxs);
+
+    const transactions: { address: PublicKey; data: ReturnType<typeof createInstructionData>[] }[] = [];
+    for (const [index, ix] of ixs.entries()) {
+      const data = [createInstructionData(ix)];
+      const insertIxs: TransactionInstruction[] = [];
+      const address = await withInsertTransaction(
+        insertIxs,
+        GOVERNANCE_PROGRAM_ID,
+        PROGRAM_VERSION_V3,
+        governance,
+        proposal,
+        tokenOwnerRecord,
+        memberKeypair.publicKey,
+        index,
+        0,
+        0,
+        data,
+        memberKeypair.publicKey
+      );
+      await sendTransaction(svm, memberKeypair, insertIxs);
+      transactions.push({ address, data });
+    }
+
+    const voteIxs: TransactionInstruction[] = [];
+    withSignOffProposal(
+      voteIxs,
+      GOVERNANCE_PROGRAM_ID,
+      PROGRAM_VERSION_V3,
+      realm,
+      governance,
+      proposal,
+      memberKeypair.publicKey,
+      undefined,
+      tokenOwnerRecord
+    );
+    await withCastVote(
+      voteIxs,
+      GOVERNANCE_PROGRAM_ID,
+      PROGRAM_VERSION_V3,
+      realm,
+      governance,
+      proposal,
+      tokenOwnerRecord,
+      tokenOwnerRecord,
+      memberKeypair.publicKey,
+      communityMint,
+      Vote.fromYesNoVote(YesNoVote.Yes),
+      memberKeypair.publicKey
+    );
+    await sendTransaction(svm, memberKeypair, voteIxs);
+
+    for (const { address, data } of transactions) {
+      const executeIxs: TransactionInstruction[] = [];
+      await withExecuteTransaction(
+        executeIxs,
+        GOVERNANCE_PROGRAM_ID,
+        PROGRAM_VERSION_V3,
+        governance,
+        proposal,
+        address,
+        data
+      );
+      await sendTransaction(svm, memberKeypair, executeIxs);
+    }
+  };
+
+  it("Lets a realm's native treasury take over the config and change the distributor set by vote", async () => {
+    await program.methods
+      .transferAuthority(treasury)
+      .accountsPartial({ authority: memberKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([memberKeypair])
+      .rpc();
+
+    const newDistributor = Keypair.generate().publicKey;
+    await executeProposal("Take over the airdrop config and add a distributor", [
+      await program.methods
+        .acceptAuthority()
+        .accountsPartial({ pendingAuthority: treasury, globalConfig: globalConfigPda })
+        .instruction(),
+      await program.methods
+        .updateGlobalConfig({ add: { distributor: newDistributor, weight: 1, keyType: { ed25519: {} } } })
+        .accountsPartial({ authority: treasury, globalConfig: globalConfigPda })
+        .instruction(),
+    ]);
+
+    const config = await program.account.globalConfig.fetch(globalConfigPda);
+    expect(config.authority.toBase58()).to.equal(treasury.toBase58());
+    expect(config.distributors.map((d) => d.pubkey.toBase58())).to.include(newDistributor.toBase58());
+
+    // The member's own key no longer manages the configuration outside a proposal
+    try {
+      await program.methods
+        .updateGlobalConfig({ remove: { distributor: newDistributor } })
+        .accountsPartial({ authority: memberKeypair.publicKey, globalConfig: globalConfigPda })
+        .signers([memberKeypair])
+        .rpc();
+      expect.fail("Should have failed with unauthorized signer");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+});
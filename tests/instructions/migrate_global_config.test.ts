@@ -0,0 +1,76 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Airdrop } from "../../target/types/airdrop";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { fromWorkspace, LiteSVMProvider } from 'anchor-litesvm';
+
+describe("migrate_global_config", () => {
+  let svm: LiteSVM;
+  let provider: LiteSVMProvider;
+  let program: Program<Airdrop>;
+
+  let authorityKeypair: Keypair;
+  let distributorKeypair: Keypair;
+  let globalConfigPda: PublicKey;
+
+  before(async () => {
+    svm = fromWorkspace('./')
+      .withBuiltins()
+      .withSysvars()
+      .withDefaultPrograms()
+      .withPrecompiles();
+    provider = new LiteSVMProvider(svm);
+    anchor.setProvider(provider);
+    program = anchor.workspace.Airdrop as Program<Airdrop>;
+
+    authorityKeypair = Keypair.generate();
+    distributorKeypair = Keypair.generate();
+
+    await svm.airdrop(authorityKeypair.publicKey, BigInt(10000000000));
+
+    [globalConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("global_config")],
+      program.programId
+    );
+
+    await program.methods
+      .createGlobalConfig([{ pubkey: distributorKeypair.publicKey, weight: 1, active: true, keyType: { ed25519: {} } }], 1, 1)
+      .accountsPartial({
+        payer: authorityKeypair.publicKey,
+        authority: authorityKeypair.publicKey,
+        globalConfig: globalConfigPda,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+  });
+
+  it("Keeps room for every distributor when migrating an expanded config", async () => {
+    await program.methods
+      .expandDistributors(8)
+      .accountsPartial({ payer: authorityKeypair.publicKey, authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+    const expanded = svm.getAccount(globalConfigPda);
+    const capacity = (await program.account.globalConfig.fetch(globalConfigPda)).distributorCapacity;
+
+    // An expanded config laid out before the trailing fields existed is shorter than the
+    // current layout needs at its capacity, yet longer than the unexpanded current layout
+    svm.setAccount(globalConfigPda, {
+      ...expanded,
+      data: Buffer.from(expanded.data).subarray(0, expanded.data.length - 64),
+    });
+
+    await program.methods
+      .migrateGlobalConfig()
+      .accountsPartial({ payer: authorityKeypair.publicKey, authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    expect(svm.getAccount(globalConfigPda).data.length).to.equal(expanded.data.length);
+    const config = await program.account.globalConfig.fetch(globalConfigPda);
+    expect(config.distributorCapacity).to.equal(capacity);
+    expect(config.distributors.map((d) => d.pubkey.toBase58())).to.deep.equal([distributorKeypair.publicKey.toBase58()]);
+  });
+});
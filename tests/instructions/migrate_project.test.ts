@@ -0,0 +1,95 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Airdrop } from "../../target/types/airdrop";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { fromWorkspace, LiteSVMProvider } from 'anchor-litesvm';
+import { createSplToken } from "../utils/spl";
+import { emptyProjectMetadata } from "../utils/project";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+describe("migrate_project", () => {
+  let svm: LiteSVM;
+  let provider: LiteSVMProvider;
+  let program: Program<Airdrop>;
+
+  let authorityKeypair: Keypair;
+  let mint: PublicKey;
+  let projectPda: PublicKey;
+
+  before(async () => {
+    svm = fromWorkspace('./')
+      .withBuiltins()
+      .withSysvars()
+      .withDefaultPrograms()
+      .withPrecompiles();
+    provider = new LiteSVMProvider(svm);
+    anchor.setProvider(provider);
+    program = anchor.workspace.Airdrop as Program<Airdrop>;
+
+    authorityKeypair = Keypair.generate();
+    await svm.airdrop(authorityKeypair.publicKey, BigInt(10000000000));
+
+    await program.methods
+      .createGlobalConfig([{ pubkey: Keypair.generate().publicKey, weight: 1, active: true, keyType: { ed25519: {} } }], 1, 1)
+      .accountsPartial({
+        payer: authorityKeypair.publicKey,
+        authority: authorityKeypair.publicKey,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+
+    mint = await createSplToken(provider, authorityKeypair, 9);
+
+    [projectPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("project"), new anchor.BN(1).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods
+      .createProject(new anchor.BN(0), new anchor.BN(0), emptyProjectMetadata)
+      .accountsPartial({ tokenProgram: TOKEN_PROGRAM_ID, authority: authorityKeypair.publicKey, mint: mint })
+      .signers([authorityKeypair])
+      .rpc();
+  });
+
+  it("Backfills the mint decimals of a version 1 project", async () => {
+    // Rewrite the project as version 1, which did not record the mint decimals
+    const account = svm.getAccount(projectPda);
+    const project = program.coder.accounts.decode("project", Buffer.from(account.data));
+    const legacy = await program.coder.accounts.encode("project", { ...project, version: 1, decimals: 0 });
+    const data = Buffer.alloc(account.data.length);
+    legacy.copy(data);
+    svm.setAccount(projectPda, { ...account, data });
+
+    const migrate = (authority: Keypair, projectMint: PublicKey) =>
+      program.methods
+        .migrateProject(new anchor.BN(1))
+        .accountsPartial({ payer: authority.publicKey, authority: authority.publicKey, project: projectPda, mint: projectMint })
+        .signers([authority])
+        .rpc();
+
+    const stranger = Keypair.generate();
+    await svm.airdrop(stranger.publicKey, BigInt(10000000000));
+    try {
+      await migrate(stranger, mint);
+      expect.fail("Should have failed for someone other than the global authority");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+
+    const otherMint = await createSplToken(provider, authorityKeypair, 6);
+    try {
+      await migrate(authorityKeypair, otherMint);
+      expect.fail("Should have failed for a mint other than the project's");
+    } catch (error) {
+      expect(error.message).to.include("MintMismatch");
+    }
+
+    await migrate(authorityKeypair, mint);
+
+    const migrated = await program.account.project.fetch(projectPda);
+    expect(migrated.version).to.equal(2);
+    expect(migrated.decimals).to.equal(9);
+  });
+});
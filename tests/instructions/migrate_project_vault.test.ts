@@ -0,0 +1,97 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Airdrop } from "../../target/types/airdrop";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { fromWorkspace, LiteSVMProvider } from 'anchor-litesvm';
+import { sendTransaction } from "../utils/svm";
+import { createSplToken, getOrCreateAssociatedTokenAccount, getTokenAccountBalance } from "../utils/spl";
+import { emptyProjectMetadata, getTokenVaultAddress } from "../utils/project";
+import { createMintToInstruction, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+describe("migrate_project_vault", () => {
+  let svm: LiteSVM;
+  let provider: LiteSVMProvider;
+  let program: Program<Airdrop>;
+
+  let authorityKeypair: Keypair;
+  let mint: PublicKey;
+
+  before(async () => {
+    svm = fromWorkspace('./')
+      .withBuiltins()
+      .withSysvars()
+      .withDefaultPrograms()
+      .withPrecompiles();
+    provider = new LiteSVMProvider(svm);
+    anchor.setProvider(provider);
+    program = anchor.workspace.Airdrop as Program<Airdrop>;
+
+    authorityKeypair = Keypair.generate();
+    await svm.airdrop(authorityKeypair.publicKey, BigInt(10000000000));
+
+    await program.methods
+      .createGlobalConfig([{ pubkey: Keypair.generate().publicKey, weight: 1, active: true, keyType: { ed25519: {} } }], 1, 1)
+      .accountsPartial({
+        payer: authorityKeypair.publicKey,
+        authority: authorityKeypair.publicKey,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+
+    mint = await createSplToken(provider, authorityKeypair, 9);
+  });
+
+  it("Moves a legacy project's ATA balance into its token vault", async () => {
+    const [projectPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("project"), new anchor.BN(1).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods
+      .createProject(new anchor.BN(0), new anchor.BN(0), emptyProjectMetadata)
+      .accountsPartial({ tokenProgram: TOKEN_PROGRAM_ID, authority: authorityKeypair.publicKey, mint: mint })
+      .signers([authorityKeypair])
+      .rpc();
+
+    // Recreate the pre-vault layout: no token vault, tokens held in the project's ATA
+    const tokenVault = getTokenVaultAddress(program.programId, projectPda);
+    svm.setAccount(tokenVault, {
+      lamports: 0,
+      data: Buffer.alloc(0),
+      owner: SystemProgram.programId,
+      executable: false,
+    });
+    const legacyVault = await getOrCreateAssociatedTokenAccount(svm, mint, projectPda, true, authorityKeypair);
+    await sendTransaction(svm, authorityKeypair, [
+      createMintToInstruction(mint, legacyVault, authorityKeypair.publicKey, BigInt(1000000), [], TOKEN_PROGRAM_ID),
+    ]);
+
+    const migrate = (authority: Keypair) =>
+      program.methods
+        .migrateProjectVault(new anchor.BN(1))
+        .accountsPartial({
+          authority: authority.publicKey,
+          project: projectPda,
+          mint: mint,
+          legacyVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([authority])
+        .rpc();
+
+    const stranger = Keypair.generate();
+    await svm.airdrop(stranger.publicKey, BigInt(10000000000));
+    try {
+      await migrate(stranger);
+      expect.fail("Should have failed for someone other than the project authority");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+
+    await migrate(authorityKeypair);
+
+    expect(await getTokenAccountBalance(svm, tokenVault)).to.equal(BigInt(1000000));
+    expect(svm.getAccount(legacyVault)).to.be.null;
+  });
+});
@@ -0,0 +1,143 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Airdrop } from "../../target/types/airdrop";
+import { Keypair, PublicKey, TransactionInstruction, TransactionMessage } from "@solana/web3.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { fromWorkspace, LiteSVMProvider } from 'anchor-litesvm';
+import * as multisig from "@sqds/multisig";
+import { sendTransaction } from "../utils/svm";
+import { addProgramFixture } from "../utils/fixtures";
+
+describe("squads multisig authority", () => {
+  let svm: LiteSVM;
+  let provider: LiteSVMProvider;
+  let program: Program<Airdrop>;
+
+  let memberKeypair: Keypair;
+  let multisigPda: PublicKey;
+  let vaultPda: PublicKey;
+  let globalConfigPda: PublicKey;
+  let transactionIndex = BigInt(0);
+
+  // Wraps an instruction in a vault transaction, approves it and executes it through the vault PDA
+  const executeVaultTransaction = async (ix: TransactionInstruction) => {
+    transactionIndex += BigInt(1);
+
+    const createIx = multisig.instructions.vaultTransactionCreate({
+      multisigPda,
+      transactionIndex,
+      creator: memberKeypair.publicKey,
+      vaultIndex: 0,
+      ephemeralSigners: 0,
+      transactionMessage: new TransactionMessage({
+        payerKey: vaultPda,
+        recentBlockhash: svm.latestBlockhash(),
+        instructions: [ix],
+      }),
+    });
+    const proposalIx = multisig.instructions.proposalCreate({
+      multisigPda,
+      transactionIndex,
+      creator: memberKeypair.publicKey,
+    });
+    const approveIx = multisig.instructions.proposalApprove({
+      multisigPda,
+      transactionIndex,
+      member: memberKeypair.publicKey,
+    });
+    await sendTransaction(svm, memberKeypair, [createIx, proposalIx, approveIx]);
+
+    const { instruction: executeIx } = await multisig.instructions.vaultTransactionExecute({
+      connection: provider.connection,
+      multisigPda,
+      transactionIndex,
+      member: memberKeypair.publicKey,
+    });
+    await sendTransaction(svm, memberKeypair, [executeIx]);
+  };
+
+  before(async () => {
+    svm = fromWorkspace('./')
+      .withBuiltins()
+      .withSysvars()
+      .withDefaultPrograms()
+      .withPrecompiles();
+    addProgramFixture(svm, multisig.PROGRAM_ID, "squads_multisig_program.so");
+    provider = new LiteSVMProvider(svm);
+    anchor.setProvider(provider);
+    program = anchor.workspace.Airdrop as Program<Airdrop>;
+
+    memberKeypair = Keypair.generate();
+    await svm.airdrop(memberKeypair.publicKey, BigInt(10000000000));
+
+    // Seed the Squads program config, which normally exists on every cluster
+    const treasury = Keypair.generate().publicKey;
+    const [programConfigPda] = multisig.getProgramConfigPda({});
+    const [programConfigData] = multisig.accounts.ProgramConfig.fromArgs({
+      authority: memberKeypair.publicKey,
+      multisigCreationFee: 0,
+      treasury,
+      reserved: new Array(64).fill(0),
+    }).serialize();
+    svm.setAccount(programConfigPda, {
+      lamports: 1000000000,
+      data: programConfigData,
+      owner: multisig.PROGRAM_ID,
+      executable: false,
+    });
+
+    // Create a 1-of-1 multisig and fund its vault
+    const createKey = Keypair.generate();
+    [multisigPda] = multisig.getMultisigPda({ createKey: createKey.publicKey });
+    [vaultPda] = multisig.getVaultPda({ multisigPda, index: 0 });
+
+    const createMultisigIx = multisig.instructions.multisigCreateV2({
+      treasury,
+      createKey: createKey.publicKey,
+      creator: memberKeypair.publicKey,
+      multisigPda,
+      configAuthority: null,
+      threshold: 1,
+      members: [{ key: memberKeypair.publicKey, permissions: multisig.types.Permissions.all() }],
+      timeLock: 0,
+      rentCollector: null,
+    });
+    const tx = new anchor.web3.Transaction().add(createMultisigIx);
+    await provider.sendAndConfirm(tx, [memberKeypair, createKey]);
+    await svm.airdrop(vaultPda, BigInt(10000000000));
+
+    [globalConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("global_config")],
+      program.programId
+    );
+  });
+
+  it("Creates and updates the global config with a Squads vault as authority", async () => {
+    const distributor = Keypair.generate().publicKey;
+
+    const createIx = await program.methods
+      .createGlobalConfig([{ pubkey: distributor, weight: 1, active: true, keyType: { ed25519: {} } }], 1, 1)
+      .accountsPartial({
+        payer: vaultPda,
+        authority: vaultPda,
+        globalConfig: globalConfigPda,
+      })
+      .instruction();
+    await executeVaultTransaction(createIx);
+
+    const added = Keypair.generate().publicKey;
+    const updateIx = await program.methods
+      .updateGlobalConfig({ add: { distributor: added, weight: 1, keyType: { ed25519: {} } } })
+      .accountsPartial({ authority: vaultPda, globalConfig: globalConfigPda })
+      .instruction();
+    await executeVaultTransaction(updateIx);
+
+    const config = await program.account.globalConfig.fetch(globalConfigPda);
+    expect(config.authority.toBase58()).to.equal(vaultPda.toBase58());
+    expect(config.distributors.map((d) => d.pubkey.toBase58())).to.deep.equal([
+      distributor.toBase58(),
+      added.toBase58(),
+    ]);
+  });
+});
@@ -0,0 +1,95 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Airdrop } from "../../target/types/airdrop";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { fromWorkspace, LiteSVMProvider } from 'anchor-litesvm';
+
+describe("transfer_authority", () => {
+  let svm: LiteSVM;
+  let provider: LiteSVMProvider;
+  let program: Program<Airdrop>;
+
+  let authorityKeypair: Keypair;
+  // Stands in for the nominee; governance_authority.test.ts runs the same handover
+  // through a real SPL Governance proposal.
+  let treasuryKeypair: Keypair;
+  let globalConfigPda: PublicKey;
+
+  before(async () => {
+    svm = fromWorkspace('./')
+      .withBuiltins()
+      .withSysvars()
+      .withDefaultPrograms()
+      .withPrecompiles();
+    provider = new LiteSVMProvider(svm);
+    anchor.setProvider(provider);
+    program = anchor.workspace.Airdrop as Program<Airdrop>;
+
+    authorityKeypair = Keypair.generate();
+    treasuryKeypair = Keypair.generate();
+
+    await svm.airdrop(authorityKeypair.publicKey, BigInt(10000000000));
+    await svm.airdrop(treasuryKeypair.publicKey, BigInt(10000000000));
+
+    [globalConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("global_config")],
+      program.programId
+    );
+
+    await program.methods
+      .createGlobalConfig([{ pubkey: Keypair.generate().publicKey, weight: 1, active: true, keyType: { ed25519: {} } }], 1, 1)
+      .accountsPartial({
+        payer: authorityKeypair.publicKey,
+        authority: authorityKeypair.publicKey,
+        globalConfig: globalConfigPda,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+  });
+
+  it("Hands the authority over once the nominee accepts", async () => {
+    await program.methods
+      .transferAuthority(treasuryKeypair.publicKey)
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    // Nobody else can accept the nomination
+    const impostor = Keypair.generate();
+    await svm.airdrop(impostor.publicKey, BigInt(10000000000));
+    try {
+      await program.methods
+        .acceptAuthority()
+        .accountsPartial({ pendingAuthority: impostor.publicKey, globalConfig: globalConfigPda })
+        .signers([impostor])
+        .rpc();
+      expect.fail("Should have failed with a pending authority mismatch");
+    } catch (error) {
+      expect(error.message).to.include("PendingAuthorityMismatch");
+    }
+
+    await program.methods
+      .acceptAuthority()
+      .accountsPartial({ pendingAuthority: treasuryKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([treasuryKeypair])
+      .rpc();
+
+    const config = await program.account.globalConfig.fetch(globalConfigPda);
+    expect(config.authority.toBase58()).to.equal(treasuryKeypair.publicKey.toBase58());
+    expect(config.pendingAuthority).to.be.null;
+
+    // The previous authority can no longer manage the configuration
+    try {
+      await program.methods
+        .pause()
+        .accountsPartial({ signer: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+        .signers([authorityKeypair])
+        .rpc();
+      expect.fail("Should have failed with unauthorized signer");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+});
@@ -0,0 +1,290 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Airdrop } from "../../target/types/airdrop";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { LiteSVM } from "litesvm";
+import { fromWorkspace, LiteSVMProvider } from 'anchor-litesvm';
+import { createEd25519Instruction } from "../utils/ed25519";
+import { sendTransaction } from "../utils/svm";
+
+describe("update_global_config", () => {
+  let svm: LiteSVM;
+  let provider: LiteSVMProvider;
+  let program: Program<Airdrop>;
+
+  let authorityKeypair: Keypair;
+  let distributorKeypair: Keypair;
+  let globalConfigPda: PublicKey;
+
+  before(async () => {
+    svm = fromWorkspace('./')
+      .withBuiltins()
+      .withSysvars()
+      .withDefaultPrograms()
+      .withPrecompiles();
+    provider = new LiteSVMProvider(svm);
+    anchor.setProvider(provider);
+    program = anchor.workspace.Airdrop as Program<Airdrop>;
+
+    authorityKeypair = Keypair.generate();
+    distributorKeypair = Keypair.generate();
+
+    await svm.airdrop(authorityKeypair.publicKey, BigInt(10000000000));
+
+    [globalConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("global_config")],
+      program.programId
+    );
+
+    await program.methods
+      .createGlobalConfig([{ pubkey: distributorKeypair.publicKey, weight: 1, active: true, keyType: { ed25519: {} } }], 1, 1)
+      .accountsPartial({
+        payer: authorityKeypair.publicKey,
+        authority: authorityKeypair.publicKey,
+        globalConfig: globalConfigPda,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+  });
+
+  it("Adds, replaces and removes distributors", async () => {
+    const added = Keypair.generate().publicKey;
+    const replacement = Keypair.generate().publicKey;
+
+    await program.methods
+      .updateGlobalConfig({ add: { distributor: added, weight: 1, keyType: { ed25519: {} } } })
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    let config = await program.account.globalConfig.fetch(globalConfigPda);
+    expect(config.distributors.map((d) => d.pubkey.toBase58())).to.deep.equal([
+      distributorKeypair.publicKey.toBase58(),
+      added.toBase58(),
+    ]);
+
+    await program.methods
+      .updateGlobalConfig({ replace: { oldDistributor: added, newDistributor: replacement } })
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    await program.methods
+      .updateGlobalConfig({ remove: { distributor: distributorKeypair.publicKey } })
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    config = await program.account.globalConfig.fetch(globalConfigPda);
+    expect(config.distributors.map((d) => d.pubkey.toBase58())).to.deep.equal([replacement.toBase58()]);
+  });
+
+  it("Fails when adding a duplicate distributor", async () => {
+    const config = await program.account.globalConfig.fetch(globalConfigPda);
+
+    try {
+      await program.methods
+        .updateGlobalConfig({ add: { distributor: config.distributors[0].pubkey, weight: 1, keyType: { ed25519: {} } } })
+        .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+        .signers([authorityKeypair])
+        .rpc();
+      expect.fail("Should have failed with duplicate distributor");
+    } catch (error) {
+      expect(error.message).to.include("DistributorAlreadyExists");
+    }
+  });
+
+  it("Fails when signer is not the authority", async () => {
+    const impostor = Keypair.generate();
+    await svm.airdrop(impostor.publicKey, BigInt(10000000000));
+
+    try {
+      await program.methods
+        .updateGlobalConfig({ add: { distributor: Keypair.generate().publicKey, weight: 1, keyType: { ed25519: {} } } })
+        .accountsPartial({ authority: impostor.publicKey, globalConfig: globalConfigPda })
+        .signers([impostor])
+        .rpc();
+      expect.fail("Should have failed with unauthorized signer");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Updates weights and rejects a required weight above the total", async () => {
+    const config = await program.account.globalConfig.fetch(globalConfigPda);
+    const distributor = config.distributors[0].pubkey;
+
+    await program.methods
+      .updateGlobalConfig({ setWeight: { distributor, weight: 5 } })
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    await program.methods
+      .updateGlobalConfig({ setRequiredWeight: { requiredWeight: 5 } })
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    const updated = await program.account.globalConfig.fetch(globalConfigPda);
+    expect(updated.distributors[0].weight).to.equal(5);
+    expect(updated.requiredWeight).to.equal(5);
+
+    try {
+      await program.methods
+        .updateGlobalConfig({ setRequiredWeight: { requiredWeight: 6 } })
+        .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+        .signers([authorityKeypair])
+        .rpc();
+      expect.fail("Should have failed with a required weight above the total");
+    } catch (error) {
+      expect(error.message).to.include("InvalidRequiredWeight");
+    }
+  });
+
+  it("Lets the guardian pause but not unpause", async () => {
+    const guardian = Keypair.generate();
+    await svm.airdrop(guardian.publicKey, BigInt(10000000000));
+
+    await program.methods
+      .setGuardian(guardian.publicKey)
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+
+    await program.methods
+      .pause()
+      .accountsPartial({ signer: guardian.publicKey, globalConfig: globalConfigPda })
+      .signers([guardian])
+      .rpc();
+    expect((await program.account.globalConfig.fetch(globalConfigPda)).paused).to.be.true;
+
+    try {
+      await program.methods
+        .unpause()
+        .accountsPartial({ authority: guardian.publicKey, globalConfig: globalConfigPda })
+        .signers([guardian])
+        .rpc();
+      expect.fail("Should have failed because the guardian cannot unpause");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+
+    await program.methods
+      .unpause()
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+    expect((await program.account.globalConfig.fetch(globalConfigPda)).paused).to.be.false;
+  });
+
+  it("Pauses and unpauses with the authority's signature submitted by a relayer", async () => {
+    const relayer = Keypair.generate();
+    await svm.airdrop(relayer.publicKey, BigInt(10000000000));
+
+    // AdminMessage { action: SetPaused { paused }, domain } with an AdminOp domain
+    const adminMessage = (paused: boolean, nonce: bigint) => {
+      const domain = Buffer.alloc(32 + 1 + 8 + 8 + 32 + 1);
+      program.programId.toBuffer().copy(domain, 0);
+      domain.writeUInt8(1, 32); // version
+      domain.writeBigUInt64LE(nonce, 33);
+      domain.writeBigInt64LE(BigInt(9999999999), 41); // deadline
+      domain.writeUInt8(2, 81); // MessageKind::AdminOp
+      return Buffer.concat([Buffer.from([0, paused ? 1 : 0]), domain]);
+    };
+    const setPausedIx = (nonce: bigint) => {
+      const [nullifier] = PublicKey.findProgramAddressSync(
+        [Buffer.from("nullifier"), globalConfigPda.toBuffer(), new anchor.BN(nonce.toString()).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      return program.methods
+        .setPausedBySignature(new anchor.BN(nonce.toString()))
+        .accountsPartial({ payer: relayer.publicKey, globalConfig: globalConfigPda, nullifier })
+        .instruction();
+    };
+
+    try {
+      await sendTransaction(svm, relayer, [
+        createEd25519Instruction(distributorKeypair, adminMessage(true, BigInt(2900))),
+        await setPausedIx(BigInt(2900)),
+      ]);
+      expect.fail("Should have failed because only the authority may sign admin messages");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+
+    await sendTransaction(svm, relayer, [
+      createEd25519Instruction(authorityKeypair, adminMessage(true, BigInt(2900))),
+      await setPausedIx(BigInt(2900)),
+    ]);
+    expect((await program.account.globalConfig.fetch(globalConfigPda)).paused).to.be.true;
+
+    await sendTransaction(svm, relayer, [
+      createEd25519Instruction(authorityKeypair, adminMessage(false, BigInt(2901))),
+      await setPausedIx(BigInt(2901)),
+    ]);
+    expect((await program.account.globalConfig.fetch(globalConfigPda)).paused).to.be.false;
+  });
+  it("Updates distributors with the authority's signature submitted by a relayer", async () => {
+    const relayer = Keypair.generate();
+    await svm.airdrop(relayer.publicKey, BigInt(10000000000));
+    const added = Keypair.generate().publicKey;
+
+    // AdminMessage { action: UpdateDistributors(Add { distributor, weight: 1, key_type: Ed25519 }), domain }
+    const adminMessage = (nonce: bigint) => {
+      const domain = Buffer.alloc(32 + 1 + 8 + 8 + 32 + 1);
+      program.programId.toBuffer().copy(domain, 0);
+      domain.writeUInt8(1, 32); // version
+      domain.writeBigUInt64LE(nonce, 33);
+      domain.writeBigInt64LE(BigInt(9999999999), 41); // deadline
+      domain.writeUInt8(2, 81); // MessageKind::AdminOp
+      return Buffer.concat([Buffer.from([1, 0]), added.toBuffer(), Buffer.from([1, 0, 0]), domain]);
+    };
+    const updateDistributorsIx = (nonce: bigint) => {
+      const [nullifier] = PublicKey.findProgramAddressSync(
+        [Buffer.from("nullifier"), globalConfigPda.toBuffer(), new anchor.BN(nonce.toString()).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      return program.methods
+        .updateDistributorsBySignature(new anchor.BN(nonce.toString()))
+        .accountsPartial({ payer: relayer.publicKey, globalConfig: globalConfigPda, nullifier })
+        .instruction();
+    };
+    const distributorKeys = async () =>
+      (await program.account.globalConfig.fetch(globalConfigPda)).distributors.map((d) => d.pubkey.toBase58());
+
+    try {
+      await sendTransaction(svm, relayer, [
+        createEd25519Instruction(distributorKeypair, adminMessage(BigInt(2910))),
+        await updateDistributorsIx(BigInt(2910)),
+      ]);
+      expect.fail("Should have failed because only the authority may sign admin messages");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+    expect(await distributorKeys()).to.not.include(added.toBase58());
+
+    const approved = [
+      createEd25519Instruction(authorityKeypair, adminMessage(BigInt(2910))),
+      await updateDistributorsIx(BigInt(2910)),
+    ];
+    await sendTransaction(svm, relayer, approved);
+    expect(await distributorKeys()).to.include(added.toBase58());
+
+    // The message's nullifier stops it from being replayed once the change is undone
+    await program.methods
+      .updateGlobalConfig({ remove: { distributor: added } })
+      .accountsPartial({ authority: authorityKeypair.publicKey, globalConfig: globalConfigPda })
+      .signers([authorityKeypair])
+      .rpc();
+    svm.expireBlockhash();
+    try {
+      await sendTransaction(svm, relayer, approved);
+      expect.fail("Should have failed for a replayed admin message");
+    } catch (error) {
+      expect(error.message).to.include("Unknown error");
+    }
+    expect(await distributorKeys()).to.not.include(added.toBase58());
+  });
+});
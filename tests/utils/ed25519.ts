@@ -1,6 +1,12 @@
 import { Keypair, TransactionInstruction, Ed25519Program } from "@solana/web3.js";
 import * as nacl from "tweetnacl";
 
+const OFFSETS_START = 2;
+const OFFSETS_LEN = 14;
+const PUBKEY_LEN = 32;
+const SIGNATURE_LEN = 64;
+const CURRENT_INSTRUCTION = 0xffff;
+
 export function createEd25519Instruction(
     signer: Keypair,
     message: Buffer,
@@ -15,3 +21,118 @@ export function createEd25519Instruction(
         signature,
     });
 }
+
+// Builds a single Ed25519 instruction carrying one signature per signer over the same message
+export function createMultiEd25519Instruction(
+    signers: Keypair[],
+    message: Buffer,
+): TransactionInstruction {
+    const headerLen = OFFSETS_START + OFFSETS_LEN * signers.length;
+    const entryLen = PUBKEY_LEN + SIGNATURE_LEN;
+    const messageOffset = headerLen + entryLen * signers.length;
+    const data = Buffer.alloc(messageOffset + message.length);
+
+    data.writeUInt8(signers.length, 0);
+    data.writeUInt8(0, 1);
+
+    signers.forEach((signer, i) => {
+        const publicKeyOffset = headerLen + entryLen * i;
+        const signatureOffset = publicKeyOffset + PUBKEY_LEN;
+        const signature = nacl.sign.detached(message, signer.secretKey);
+
+        const entry = OFFSETS_START + OFFSETS_LEN * i;
+        data.writeUInt16LE(signatureOffset, entry);
+        data.writeUInt16LE(CURRENT_INSTRUCTION, entry + 2);
+        data.writeUInt16LE(publicKeyOffset, entry + 4);
+        data.writeUInt16LE(CURRENT_INSTRUCTION, entry + 6);
+        data.writeUInt16LE(messageOffset, entry + 8);
+        data.writeUInt16LE(message.length, entry + 10);
+        data.writeUInt16LE(CURRENT_INSTRUCTION, entry + 12);
+
+        data.set(signer.publicKey.toBytes(), publicKeyOffset);
+        data.set(signature, signatureOffset);
+    });
+
+    data.set(message, messageOffset);
+
+    return new TransactionInstruction({
+        keys: [],
+        programId: Ed25519Program.programId,
+        data,
+    });
+}
+
+// Builds a single Ed25519 instruction where each signer signs its own message
+export function createEd25519InstructionWithMessages(
+    entries: { signer: Keypair; message: Buffer }[],
+): TransactionInstruction {
+    const headerLen = OFFSETS_START + OFFSETS_LEN * entries.length;
+    const entryLen = PUBKEY_LEN + SIGNATURE_LEN;
+    const messagesLen = entries.reduce((len, { message }) => len + message.length, 0);
+    const data = Buffer.alloc(headerLen + entryLen * entries.length + messagesLen);
+
+    data.writeUInt8(entries.length, 0);
+    data.writeUInt8(0, 1);
+
+    let messageOffset = headerLen + entryLen * entries.length;
+    entries.forEach(({ signer, message }, i) => {
+        const publicKeyOffset = headerLen + entryLen * i;
+        const signatureOffset = publicKeyOffset + PUBKEY_LEN;
+        const signature = nacl.sign.detached(message, signer.secretKey);
+
+        const entry = OFFSETS_START + OFFSETS_LEN * i;
+        data.writeUInt16LE(signatureOffset, entry);
+        data.writeUInt16LE(CURRENT_INSTRUCTION, entry + 2);
+        data.writeUInt16LE(publicKeyOffset, entry + 4);
+        data.writeUInt16LE(CURRENT_INSTRUCTION, entry + 6);
+        data.writeUInt16LE(messageOffset, entry + 8);
+        data.writeUInt16LE(message.length, entry + 10);
+        data.writeUInt16LE(CURRENT_INSTRUCTION, entry + 12);
+
+        data.set(signer.publicKey.toBytes(), publicKeyOffset);
+        data.set(signature, signatureOffset);
+        data.set(message, messageOffset);
+        messageOffset += message.length;
+    });
+
+    return new TransactionInstruction({
+        keys: [],
+        programId: Ed25519Program.programId,
+        data,
+    });
+}
+
+// Builds an Ed25519 instruction whose signature and public key lie in the instruction itself but
+// whose signed message is read from another instruction of the transaction, at `messageOffset`
+// in the data of the instruction at `messageInstructionIndex`
+export function createEd25519InstructionWithExternalMessage(
+    signer: Keypair,
+    message: Buffer,
+    messageInstructionIndex: number,
+    messageOffset: number,
+): TransactionInstruction {
+    const headerLen = OFFSETS_START + OFFSETS_LEN;
+    const publicKeyOffset = headerLen;
+    const signatureOffset = publicKeyOffset + PUBKEY_LEN;
+    const data = Buffer.alloc(signatureOffset + SIGNATURE_LEN);
+    const signature = nacl.sign.detached(message, signer.secretKey);
+
+    data.writeUInt8(1, 0);
+    data.writeUInt8(0, 1);
+    data.writeUInt16LE(signatureOffset, OFFSETS_START);
+    data.writeUInt16LE(CURRENT_INSTRUCTION, OFFSETS_START + 2);
+    data.writeUInt16LE(publicKeyOffset, OFFSETS_START + 4);
+    data.writeUInt16LE(CURRENT_INSTRUCTION, OFFSETS_START + 6);
+    data.writeUInt16LE(messageOffset, OFFSETS_START + 8);
+    data.writeUInt16LE(message.length, OFFSETS_START + 10);
+    data.writeUInt16LE(messageInstructionIndex, OFFSETS_START + 12);
+
+    data.set(signer.publicKey.toBytes(), publicKeyOffset);
+    data.set(signature, signatureOffset);
+
+    return new TransactionInstruction({
+        keys: [],
+        programId: Ed25519Program.programId,
+        data,
+    });
+}
@@ -0,0 +1,33 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { InnerInstruction } from "litesvm";
+
+// The tag Anchor prefixes the self-CPI instruction data of `emit_cpi!` events with
+const EVENT_IX_TAG_LE = Buffer.from([0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d]);
+
+// Decodes the events a transaction emitted from its logs and returns the first one named `name`
+export const findEvent = (program: Program<any>, logs: string[], name: string) => {
+  const parser = new anchor.EventParser(program.programId, program.coder);
+  for (const event of parser.parseLogs(logs)) {
+    if (event.name.toLowerCase() === name.toLowerCase()) {
+      return event.data;
+    }
+  }
+  return undefined;
+};
+
+// Decodes the events a program built with `event-cpi` emitted as self-CPIs and returns the first
+// one named `name`
+export const findCpiEvent = (program: Program<any>, innerInstructions: InnerInstruction[][], name: string) => {
+  for (const inner of innerInstructions.flat()) {
+    const data = Buffer.from(inner.instruction().data());
+    if (!data.subarray(0, 8).equals(EVENT_IX_TAG_LE)) {
+      continue;
+    }
+    const event = program.coder.events.decode(data.subarray(8).toString("base64"));
+    if (event && event.name.toLowerCase() === name.toLowerCase()) {
+      return event.data;
+    }
+  }
+  return undefined;
+};
@@ -0,0 +1,12 @@
+import { PublicKey } from "@solana/web3.js";
+import { LiteSVM } from "litesvm";
+import { existsSync } from "fs";
+
+// Programs the tests load but this workspace does not build; `yarn fixtures` dumps them from mainnet
+export const addProgramFixture = (svm: LiteSVM, programId: PublicKey, file: string) => {
+  const path = `tests/fixtures/${file}`;
+  if (!existsSync(path)) {
+    throw new Error(`Missing program fixture ${path}; run \`yarn fixtures\` to dump it from mainnet`);
+  }
+  svm.addProgramFromFile(programId, path);
+};
@@ -0,0 +1,14 @@
+import { PublicKey } from "@solana/web3.js";
+
+export const emptyProjectMetadata = {
+  name: "",
+  symbol: "",
+  uri: "",
+  description: "",
+};
+
+export const getTokenVaultAddress = (programId: PublicKey, project: PublicKey) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("token_vault"), project.toBuffer()],
+    programId,
+  )[0];
@@ -0,0 +1,18 @@
+import { PublicKey, Secp256k1Program, TransactionInstruction } from "@solana/web3.js";
+import { secp256k1 } from "@noble/curves/secp256k1";
+
+// The key an Ethereum distributor is registered under: its 20 byte address, left-padded with zeros
+export function secp256k1KeyId(privateKey: Uint8Array): PublicKey {
+    const address = Secp256k1Program.publicKeyToEthAddress(secp256k1.getPublicKey(privateKey, false).slice(1));
+    return new PublicKey(Buffer.concat([Buffer.alloc(12), address]));
+}
+
+// Builds a secp256k1 instruction carrying one signature over `message`, for the instruction at
+// `instructionIndex` of the transaction
+export function createSecp256k1Instruction(
+    privateKey: Uint8Array,
+    message: Buffer,
+    instructionIndex: number,
+): TransactionInstruction {
+    return Secp256k1Program.createInstructionWithPrivateKey({ privateKey, message, instructionIndex });
+}
@@ -0,0 +1,51 @@
+import { PublicKey, TransactionInstruction } from "@solana/web3.js";
+import { p256 } from "@noble/curves/p256";
+import { sha256 } from "@noble/hashes/sha256";
+
+const OFFSETS_START = 2;
+const OFFSETS_LEN = 14;
+const PUBKEY_LEN = 33;
+const SIGNATURE_LEN = 64;
+const CURRENT_INSTRUCTION = 0xffff;
+
+export const SECP256R1_PROGRAM_ID = new PublicKey("Secp256r1SigVerify1111111111111111111111111");
+
+// The key a passkey distributor is registered under: the SHA-256 hash of its compressed public key
+export function secp256r1KeyId(privateKey: Uint8Array): PublicKey {
+    return new PublicKey(sha256(p256.getPublicKey(privateKey, true)));
+}
+
+// Builds a secp256r1 instruction carrying one signature over `message`, with every offset
+// pointing into the instruction itself
+export function createSecp256r1Instruction(
+    privateKey: Uint8Array,
+    message: Buffer,
+): TransactionInstruction {
+    const publicKeyOffset = OFFSETS_START + OFFSETS_LEN;
+    const signatureOffset = publicKeyOffset + PUBKEY_LEN;
+    const messageOffset = signatureOffset + SIGNATURE_LEN;
+    const data = Buffer.alloc(messageOffset + message.length);
+
+    // The precompile only accepts low-S signatures over the message's SHA-256 hash
+    const signature = p256.sign(sha256(message), privateKey, { lowS: true }).toCompactRawBytes();
+
+    data.writeUInt8(1, 0);
+    data.writeUInt8(0, 1);
+    data.writeUInt16LE(signatureOffset, OFFSETS_START);
+    data.writeUInt16LE(CURRENT_INSTRUCTION, OFFSETS_START + 2);
+    data.writeUInt16LE(publicKeyOffset, OFFSETS_START + 4);
+    data.writeUInt16LE(CURRENT_INSTRUCTION, OFFSETS_START + 6);
+    data.writeUInt16LE(messageOffset, OFFSETS_START + 8);
+    data.writeUInt16LE(message.length, OFFSETS_START + 10);
+    data.writeUInt16LE(CURRENT_INSTRUCTION, OFFSETS_START + 12);
+
+    data.set(p256.getPublicKey(privateKey, true), publicKeyOffset);
+    data.set(signature, signatureOffset);
+    data.set(message, messageOffset);
+
+    return new TransactionInstruction({
+        keys: [],
+        programId: SECP256R1_PROGRAM_ID,
+        data,
+    });
+}
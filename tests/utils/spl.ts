@@ -1,12 +1,19 @@
 import * as anchor from '@coral-xyz/anchor';
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
+  ACCOUNT_SIZE,
   createAssociatedTokenAccountInstruction,
+  createInitializeAccount3Instruction,
   createInitializeMint2Instruction,
+  createInitializeTransferFeeConfigInstruction,
+  ExtensionType,
   getAccount,
   getAssociatedTokenAddress,
+  getMinimumBalanceForRentExemptAccount,
   getMinimumBalanceForRentExemptMint,
+  getMintLen,
   MINT_SIZE,
+  TOKEN_2022_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
 } from '@solana/spl-token';
 import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
@@ -18,6 +25,7 @@ export const createSplToken = async (
   provider: LiteSVMProvider,
   owner: Keypair,
   decimals: number = 9,
+  freezeAuthority: PublicKey | null = null,
 ) => {
   const mintKeypair = Keypair.generate();
   const lamports = await getMinimumBalanceForRentExemptMint(provider.connection);
@@ -34,7 +42,7 @@ export const createSplToken = async (
     mintKeypair.publicKey,
     decimals,
     owner.publicKey, // mint authority
-    null, // freeze authority
+    freezeAuthority,
     TOKEN_PROGRAM_ID,
   );
 
@@ -46,6 +54,84 @@ export const createSplToken = async (
   return mintKeypair.publicKey;
 };
 
+// Creates a Token-2022 mint charging `feeBps` on every transfer, capped at `maxFee`
+export const createTransferFeeToken = async (
+  provider: LiteSVMProvider,
+  owner: Keypair,
+  feeBps: number,
+  maxFee: bigint,
+  decimals: number = 9,
+) => {
+  const mintKeypair = Keypair.generate();
+  const space = getMintLen([ExtensionType.TransferFeeConfig]);
+  const lamports = await provider.connection.getMinimumBalanceForRentExemption(space);
+
+  const createAccountIx = SystemProgram.createAccount({
+    fromPubkey: owner.publicKey,
+    newAccountPubkey: mintKeypair.publicKey,
+    lamports,
+    space,
+    programId: TOKEN_2022_PROGRAM_ID,
+  });
+
+  const initializeTransferFeeIx = createInitializeTransferFeeConfigInstruction(
+    mintKeypair.publicKey,
+    owner.publicKey, // transfer fee config authority
+    owner.publicKey, // withdraw withheld authority
+    feeBps,
+    maxFee,
+    TOKEN_2022_PROGRAM_ID,
+  );
+
+  const initializeMintIx = createInitializeMint2Instruction(
+    mintKeypair.publicKey,
+    decimals,
+    owner.publicKey, // mint authority
+    null, // freeze authority
+    TOKEN_2022_PROGRAM_ID,
+  );
+
+  await provider.sendAndConfirm(
+    new anchor.web3.Transaction().add(createAccountIx, initializeTransferFeeIx, initializeMintIx),
+    [owner, mintKeypair],
+  );
+
+  return mintKeypair.publicKey;
+};
+
+// Creates a token account for `owner` at a fresh address rather than the associated one
+export const createTokenAccount = async (
+  provider: LiteSVMProvider,
+  payer: Keypair,
+  mint: PublicKey,
+  owner: PublicKey,
+) => {
+  const accountKeypair = Keypair.generate();
+  const lamports = await getMinimumBalanceForRentExemptAccount(provider.connection);
+
+  const createAccountIx = SystemProgram.createAccount({
+    fromPubkey: payer.publicKey,
+    newAccountPubkey: accountKeypair.publicKey,
+    lamports,
+    space: ACCOUNT_SIZE,
+    programId: TOKEN_PROGRAM_ID,
+  });
+
+  const initializeAccountIx = createInitializeAccount3Instruction(
+    accountKeypair.publicKey,
+    mint,
+    owner,
+    TOKEN_PROGRAM_ID,
+  );
+
+  await provider.sendAndConfirm(
+    new anchor.web3.Transaction().add(createAccountIx, initializeAccountIx),
+    [payer, accountKeypair],
+  );
+
+  return accountKeypair.publicKey;
+};
+
 export const getOrCreateAssociatedTokenAccount = async (
   svm: LiteSVM,
   mint: PublicKey,
@@ -103,3 +189,16 @@ export const getSplTokenBalance = async (
   );
   return BigInt(ataInfo?.amount ?? 0);
 };
+
+export const getTokenAccountBalance = async (svm: LiteSVM, address: PublicKey) => {
+  if (!svm.getAccount(address)) {
+    return BigInt(0);
+  }
+  const account = await getAccount(
+    new LiteSVMProvider(svm).connection,
+    address,
+    undefined,
+    TOKEN_PROGRAM_ID,
+  );
+  return BigInt(account.amount);
+};
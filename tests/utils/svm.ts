@@ -26,5 +26,5 @@ export const sendTransaction = async (
     // Get transaction logs - result is TransactionMetadata for successful transactions
     const logs = result.logs();
   
-    return { signature: result.signature(), logs };
+    return { signature: result.signature(), logs, innerInstructions: result.innerInstructions() };
   };
\ No newline at end of file